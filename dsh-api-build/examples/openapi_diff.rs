@@ -0,0 +1,28 @@
+use dsh_api_build_helpers::json_patch::read_patches;
+use dsh_api_build_helpers::openapi_diff::generate_openapi_diff;
+use dsh_api_build_helpers::update_openapi::update_openapi;
+use openapiv3::OpenAPI;
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+
+// Usage: cargo run --example openapi_diff [old version] [new version], e.g. 1_9_2 1_10_0
+fn main() -> Result<(), Box<dyn Error>> {
+  let mut arguments = std::env::args().skip(1);
+  let old_version = arguments.next().unwrap_or("1_9_2".to_string());
+  let new_version = arguments.next().unwrap_or("1_10_0".to_string());
+  let old_openapi_spec = read_openapi_spec(&old_version)?;
+  let new_openapi_spec = read_openapi_spec(&new_version)?;
+  let mut writer: BufWriter<std::io::Stdout> = BufWriter::new(std::io::stdout());
+  generate_openapi_diff(&mut writer, &old_openapi_spec, &new_openapi_spec)?;
+  Ok(())
+}
+
+fn read_openapi_spec(version: &str) -> Result<OpenAPI, Box<dyn Error>> {
+  let openapi_spec_file_name = format!("dsh-api/openapi_spec/openapi_{}.json", version);
+  let openapi_spec_file = File::open(&openapi_spec_file_name).map_err(|error| format!("could not open '{}' ({})", openapi_spec_file_name, error))?;
+  let mut openapi_spec: OpenAPI = serde_json::from_reader(openapi_spec_file)?;
+  let patches = read_patches(format!("dsh-api/openapi_spec/patches/openapi_{}", version))?;
+  update_openapi(&mut openapi_spec, &patches, false, false)?;
+  Ok(openapi_spec)
+}
@@ -11,6 +11,8 @@ pub(crate) struct DshApiOperation {
   pub(crate) selector: String,
   pub(crate) path: String,
   pub(crate) path_elements: Vec<PathElement>,
+  // Name of the first path parameter, when it is filled in by the client
+  pub(crate) managed_path_parameter: Option<String>,
   pub(crate) description: Option<String>,
   pub(crate) parameters: Vec<(String, ParameterType, Option<String>)>,
   pub(crate) request_body: Option<RequestBodyType>,
@@ -23,11 +25,17 @@ pub(crate) struct DshApiOperation {
   pub(crate) kind: OpenApiOperationKind,
 }
 
-pub(crate) fn method_api_operations(method: &Method, path_operations: &Vec<(&String, &Operation)>) -> Result<Vec<DshApiOperation>, Box<dyn Error>> {
+// The first parameter of an operation will not be part of the parameters
+// when it is a path parameter with a name from `managed_path_parameters`
+pub(crate) fn method_api_operations(
+  method: &Method,
+  path_operations: &Vec<(&String, &Operation)>,
+  managed_path_parameters: &[&str],
+) -> Result<Vec<DshApiOperation>, Box<dyn Error>> {
   let mut method_generic_operations: Vec<DshApiOperation> = vec![];
   let mut selectors: HashSet<String> = HashSet::new();
   for (path, operation) in path_operations {
-    let mut generic_operation = create_api_operation(method.clone(), path.to_string(), operation)?;
+    let mut generic_operation = create_api_operation(method.clone(), path.to_string(), operation, managed_path_parameters)?;
     if selectors.contains(&generic_operation.selector) {
       generic_operation.selector = selector_from_path_elements(&generic_operation.path_elements, &generic_operation.ok_response, true);
     }
@@ -39,12 +47,16 @@ pub(crate) fn method_api_operations(method: &Method, path_operations: &Vec<(&Str
   Ok(method_generic_operations)
 }
 
-fn create_api_operation(method: Method, path: String, operation: &Operation) -> Result<DshApiOperation, Box<dyn Error>> {
+fn create_api_operation(method: Method, path: String, operation: &Operation, managed_path_parameters: &[&str]) -> Result<DshApiOperation, Box<dyn Error>> {
   let operation_id = operation.operation_id.clone().expect("missing operation id");
+  let managed_path_parameter = match operation.parameters.first() {
+    Some(ReferenceOr::Item(Parameter::Path { parameter_data, .. })) if managed_path_parameters.contains(&parameter_data.name.as_str()) => Some(parameter_data.name.clone()),
+    _ => None,
+  };
   let parameters: Vec<(String, ParameterType, Option<String>)> = operation
     .parameters
     .iter()
-    .skip(1)
+    .skip(if managed_path_parameter.is_some() { 1 } else { 0 })
     .map(|parameter| parameter_to_parameter_type(parameter, &operation_id))
     .collect_vec();
   let request_body = operation.request_body.clone().map(|request_body| match request_body {
@@ -74,6 +86,7 @@ fn create_api_operation(method: Method, path: String, operation: &Operation) ->
     selector,
     path,
     path_elements,
+    managed_path_parameter,
     description: operation.summary.clone().map(revise),
     parameters,
     request_body,
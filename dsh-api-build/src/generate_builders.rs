@@ -0,0 +1,185 @@
+//! Generate fluent builders for the request body types
+//!
+//! A builder will be generated for each type that is used as a request body in the
+//! openapi specification. The builder has a setter method for each field and
+//! a `build()` method that checks that all required fields are set,
+//! applies the default values from the specification and validates the result.
+
+use crate::generate_impls::{FieldDefault, GeneratedField, GeneratedTypes, StructKind};
+use crate::openapi_utils::reference_to_string;
+use indoc::formatdoc;
+use itertools::Itertools;
+use openapiv3::{OpenAPI, ReferenceOr};
+use std::error::Error;
+use std::io::Write;
+
+const HASH_MAP_PREFIX: &str = "std::collections::HashMap<String, ";
+
+/// # Generate builders for the request body types
+///
+/// The generated code expects the functions `missing_field` and `validate_built`
+/// to be in scope, together with the `DshApiResult` type.
+///
+/// # Parameters
+/// * `writer` - the generated code will be written to this writer
+/// * `openapi_spec` - openapi specification, used to find the request body types
+/// * `generated_types` - types generated by `progenitor`
+pub fn generate_builders(writer: &mut dyn Write, openapi_spec: &OpenAPI, generated_types: &GeneratedTypes) -> Result<(), Box<dyn Error>> {
+  writeln!(writer, "// Generated from the openapi specification, do not edit")?;
+  writeln!(writer, "#[allow(unused_imports)]")?;
+  writeln!(writer, "use crate::types::*;")?;
+  for schema_name in request_body_schemas(openapi_spec) {
+    if let Some(fields) = generated_types.structs.iter().find_map(|generated_struct| match &generated_struct.kind {
+      StructKind::Named(fields) if generated_struct.name == schema_name => Some(fields),
+      _ => None,
+    }) {
+      write_builder(writer, &schema_name, fields, generated_types)?;
+    }
+  }
+  Ok(())
+}
+
+fn write_builder(writer: &mut dyn Write, type_name: &str, fields: &[GeneratedField], generated_types: &GeneratedTypes) -> Result<(), Box<dyn Error>> {
+  let builder_name = format!("{}Builder", type_name);
+  let mut builder_fields = vec![];
+  let mut methods = vec![];
+  let mut field_values = vec![];
+  for field in fields {
+    let name = &field.name;
+    let field_type = inner_type(&field.field_type);
+    if let (FieldDefault::Required, Some(variant)) = (&field.default, generated_types.single_variant(field_type)) {
+      field_values.push(format!("{}: {}", name, variant));
+      continue;
+    }
+    builder_fields.push(format!("  {}: Option<{}>,", name, field_type));
+    let description = match &field.description {
+      Some(description) => format!("\n///\n/// {}", description),
+      None => "".to_string(),
+    };
+    let (parameter_type, value) = if field_type == "String" { ("impl Into<String>".to_string(), format!("{}.into()", name)) } else { (field_type.to_string(), name.to_string()) };
+    methods.push(formatdoc!(
+      "
+        /// # Set the `{name}` field{description}
+        pub fn {name}(mut self, {name}: {parameter_type}) -> Self {{
+          self.{name} = Some({value});
+          self
+        }}
+      "
+    ));
+    if let Some((map_expression, value_type)) = map_value_type(field_type, generated_types) {
+      let (value_parameter_type, value) = if value_type == "String" { ("impl Into<String>".to_string(), "value.into()") } else { (value_type, "value") };
+      methods.push(formatdoc!(
+        "
+          /// # Add an entry to the `{name}` field
+          pub fn {name}_entry(mut self, key: impl Into<String>, value: {value_parameter_type}) -> Self {{
+            self.{name}.get_or_insert_with(Default::default){map_expression}.insert(key.into(), {value});
+            self
+          }}
+        "
+      ));
+    }
+    field_values.push(match &field.default {
+      FieldDefault::Required => format!("{}: self.{}.ok_or_else(|| missing_field(\"{}\", \"{}\"))?", name, name, type_name, name),
+      FieldDefault::Optional => format!("{}: self.{}", name, name),
+      FieldDefault::Default => format!("{}: self.{}.unwrap_or_default()", name, name),
+      FieldDefault::Function(_) => format!("{}: self.{}.unwrap_or_else(|| {})", name, name, generated_types.default_expression(field)?),
+    });
+  }
+  write!(
+    writer,
+    "{}",
+    formatdoc!(
+      "
+
+      /// # Builder for [`{type_name}`]
+      ///
+      /// Create the builder with [`{type_name}::builder()`](crate::types::{type_name}::builder),
+      /// set the fields and call [`build()`]({builder_name}::build).
+      #[derive(Clone, Debug, Default)]
+      pub struct {builder_name} {{
+      {builder_fields}
+      }}
+
+      impl {type_name} {{
+        /// # Create a builder for a `{type_name}`
+        pub fn builder() -> {builder_name} {{
+          {builder_name}::default()
+        }}
+      }}
+
+      impl {builder_name} {{
+      {methods}
+
+        /// # Build the `{type_name}`
+        ///
+        /// Fields that have not been set will get their default value from the openapi specification.
+        ///
+        /// # Returns
+        /// * `Ok<{type_name}>` - the created `{type_name}`
+        /// * `Err<`[`DshApiError::Parameter`](crate::DshApiError::Parameter)`>` - when a required field
+        ///   has not been set or when a field has an invalid value
+        #[allow(clippy::unnecessary_lazy_evaluations)]
+        pub fn build(self) -> DshApiResult<{type_name}> {{
+          let value = {type_name} {{ {field_values} }};
+          validate_built(\"{type_name}\", &value)?;
+          Ok(value)
+        }}
+      }}
+      ",
+      builder_fields = builder_fields.join("\n"),
+      methods = methods
+        .iter()
+        .map(|method| method
+          .lines()
+          .map(|line| if line.is_empty() { "".to_string() } else { format!("  {}", line) })
+          .join("\n"))
+        .join("\n\n"),
+      field_values = field_values.join(", ")
+    )
+  )?;
+  Ok(())
+}
+
+// Returns the names of all schemas that are used as request body, sorted and deduplicated
+fn request_body_schemas(openapi_spec: &OpenAPI) -> Vec<String> {
+  openapi_spec
+    .paths
+    .iter()
+    .filter_map(|(_, path_item)| match path_item {
+      ReferenceOr::Item(path_item) => Some(path_item),
+      ReferenceOr::Reference { .. } => None,
+    })
+    .flat_map(|path_item| path_item.iter().map(|(_, operation)| operation))
+    .filter_map(|operation| match &operation.request_body {
+      Some(ReferenceOr::Item(request_body)) => request_body.content.get("application/json"),
+      _ => None,
+    })
+    .filter_map(|media_type| match &media_type.schema {
+      Some(ReferenceOr::Reference { reference }) => Some(reference_to_string(reference)),
+      _ => None,
+    })
+    .sorted()
+    .dedup()
+    .collect_vec()
+}
+
+fn inner_type(field_type: &str) -> &str {
+  match field_type.strip_prefix("Option<") {
+    Some(inner_type) => inner_type.strip_suffix('>').unwrap_or(inner_type),
+    None => field_type,
+  }
+}
+
+// If the type is a map with string keys, returns the expression to access the map and the value type
+fn map_value_type(field_type: &str, generated_types: &GeneratedTypes) -> Option<(&'static str, String)> {
+  match field_type.strip_prefix(HASH_MAP_PREFIX) {
+    Some(value_type) => value_type.strip_suffix('>').map(|value_type| ("", value_type.to_string())),
+    None => generated_types.structs.iter().find_map(|generated_struct| match &generated_struct.kind {
+      StructKind::Newtype { public: true, inner_type } if generated_struct.name == field_type => inner_type
+        .strip_prefix(HASH_MAP_PREFIX)
+        .and_then(|value_type| value_type.strip_suffix('>'))
+        .map(|value_type| (".0", value_type.to_string())),
+      _ => None,
+    }),
+  }
+}
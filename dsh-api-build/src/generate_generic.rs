@@ -2,7 +2,7 @@
 
 use crate::dsh_api_operation::{method_api_operations, DshApiOperation, ParameterType};
 use crate::openapi_utils::{method_path_operations, OpenApiOperationKind};
-use crate::{article, revise, Method, RequestBodyType, ResponseBodyType, MANAGED_PARAMETERS, METHODS};
+use crate::{article, revise, Method, RequestBodyType, ResponseBodyType, MANAGED_PARAMETERS, MANAGED_PATH_PARAMETERS, METHODS};
 use indoc::formatdoc;
 use itertools::Itertools;
 use openapiv3::{OpenAPI, Operation};
@@ -13,7 +13,7 @@ pub fn generate_generic(writer: &mut dyn Write, openapi_spec: &OpenAPI) -> Resul
   let mut generic_operations: Vec<(Method, Vec<DshApiOperation>)> = vec![];
   for method in &METHODS {
     let path_operations: Vec<(&String, &Operation)> = method_path_operations(method, openapi_spec);
-    generic_operations.push((method.to_owned(), method_api_operations(method, &path_operations)?));
+    generic_operations.push((method.to_owned(), method_api_operations(method, &path_operations, &MANAGED_PATH_PARAMETERS)?));
   }
   writeln!(writer, "#[cfg_attr(rustfmt, rustfmt_skip)]")?;
   writeln!(writer, "{}", USE)?;
@@ -111,9 +111,13 @@ fn write_method_operations(writer: &mut dyn Write, method: &Method, operations:
       OpenApiOperationKind::AppCatalog => {}
       OpenApiOperationKind::Manage => writeln!(writer, "  /// * _This selector is only available when the `manage` feature is enabled._")?,
       OpenApiOperationKind::Robot => writeln!(writer, "  /// * _This selector is only available when the `robot` feature is enabled._")?,
+      OpenApiOperationKind::Other(_) => {}
     }
   }
   writeln!(writer, "  {} {{", method_signature(method, ""))?;
+  if method.has_body_argument() {
+    writeln!(writer, "    let body: Option<String> = body.map(Into::into);")?;
+  }
   let mut first = true;
   for operation in operations.iter() {
     if first {
@@ -209,11 +213,11 @@ fn if_block(operation: &DshApiOperation) -> String {
   if let Some(ref request_body_type) = operation.request_body {
     match request_body_type {
             RequestBodyType::String => parameters.push(
-                "serde_json::from_str::<String>(body.unwrap().into().as_str()).map_err(|_| DshApiError::Parameter(\"json body could not be parsed as a valid String\".to_string()))?.to_string()"
+                "serde_json::from_str::<String>(body.as_deref().unwrap()).map_err(|_| DshApiError::Parameter(\"json body could not be parsed as a valid String\".to_string()))?.to_string()"
                     .to_string(),
             ),
             RequestBodyType::SerializableType(serializable_type) => parameters.push(format!(
-                "&serde_json::from_str::<{}>(body.unwrap().into().as_str()).map_err(|_| DshApiError::Parameter(\"json body could not be parsed as a valid {}\".to_string()))?",
+                "&serde_json::from_str::<{}>(body.as_deref().unwrap()).map_err(|_| DshApiError::Parameter(\"json body could not be parsed as a valid {}\".to_string()))?",
                 serializable_type, serializable_type
             )),
         }
@@ -243,6 +247,14 @@ fn if_block(operation: &DshApiOperation) -> String {
   } else {
     "".to_string()
   };
+  let validation_check = format!(
+    r#"}} else if let Err(error) = validate_request("{}", "{}", parameters, {}) {{
+        Err(error)
+      "#,
+    operation.method,
+    operation.path,
+    if operation.method.has_body_argument() { "body.as_deref()" } else { "None" }
+  );
   let selector = &operation.selector;
   let path = &operation.path;
   let comments = comments(operation).join("\n      // ");
@@ -256,7 +268,7 @@ fn if_block(operation: &DshApiOperation) -> String {
               // {comments}
               if {parameter_length_check} {{
                 Err(DshApiError::Parameter("wrong number of parameters ({wrong_parameter_length_error})".to_string()))
-              {body_check}}} else {{
+              {body_check}{validation_check}}} else {{
                 self
                   .{ok_response_processing_function}(
                     self
@@ -412,6 +424,7 @@ fn parameter_type_to_index_parameter(parameter_type: &ParameterType, index: isiz
 
 const USE: &str = r#"use crate::dsh_api_client::DshApiClient;
 use crate::types::*;
+use crate::validation::validate_request;
 use crate::{DshApiError, DshApiResult};
 use std::str::FromStr;"#;
 
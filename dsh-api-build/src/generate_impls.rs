@@ -0,0 +1,571 @@
+//! Generate `Default`, `new` and `Display` implementations for the generated types
+//!
+//! The implementations are derived from the types that `progenitor` generated from the
+//! openapi specification. Required fields become parameters of the `new` functions and
+//! default values are taken from the specification.
+//!
+//! When the `dsh_api` crate contains a hand-written implementation for a type,
+//! no implementation will be generated for that type. The hand-written implementations
+//! are found by parsing the source code of the modules that contain them.
+
+use crate::revise;
+use indoc::formatdoc;
+use itertools::Itertools;
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::io::Write;
+use syn::{Attribute, Fields, ImplItem, Item, Lit, Meta, NestedMeta, Stmt, Visibility};
+
+/// Types that are generated by `progenitor`
+pub struct GeneratedTypes {
+  pub(crate) structs: Vec<GeneratedStruct>,
+  enums: Vec<GeneratedEnum>,
+  default_functions: HashMap<String, String>,
+  to_string_types: HashSet<String>,
+}
+
+pub(crate) struct GeneratedStruct {
+  pub(crate) name: String,
+  pub(crate) kind: StructKind,
+}
+
+pub(crate) enum StructKind {
+  Named(Vec<GeneratedField>),
+  Newtype { public: bool, inner_type: String },
+}
+
+pub(crate) struct GeneratedField {
+  pub(crate) name: String,
+  pub(crate) field_type: String,
+  pub(crate) default: FieldDefault,
+  pub(crate) description: Option<String>,
+}
+
+pub(crate) enum FieldDefault {
+  // Field is required
+  Required,
+  // Field is optional and will default to `None`
+  Optional,
+  // Field will default to `Default::default()`
+  Default,
+  // Field will default to a value from the openapi specification
+  Function(String),
+}
+
+struct GeneratedEnum {
+  name: String,
+  // Variant names, if all variants are unit variants
+  unit_variants: Option<Vec<String>>,
+}
+
+/// Types that have a hand-written implementation
+#[derive(Default)]
+pub struct HandWrittenImpls {
+  defaults: HashSet<String>,
+  displays: HashSet<String>,
+  news: HashSet<String>,
+}
+
+impl GeneratedTypes {
+  /// # Parse the types from the generated `progenitor` code
+  ///
+  /// # Parameters
+  /// * `generated_code` - code generated by `progenitor`, containing the `types` module
+  pub fn from_generated_code(generated_code: &str) -> Result<Self, String> {
+    let file = syn::parse_file(generated_code).map_err(|error| format!("could not parse generated code ({})", error))?;
+    let types_items = file
+      .items
+      .iter()
+      .find_map(|item| match item {
+        Item::Mod(item_mod) if item_mod.ident == "types" => item_mod.content.as_ref().map(|(_, items)| items),
+        _ => None,
+      })
+      .ok_or_else(|| "generated code does not contain a types module".to_string())?;
+    let mut generated_types = GeneratedTypes { structs: vec![], enums: vec![], default_functions: HashMap::new(), to_string_types: HashSet::new() };
+    for item in types_items {
+      match item {
+        Item::Struct(item_struct) => {
+          let kind = match &item_struct.fields {
+            Fields::Named(fields) => StructKind::Named(
+              fields
+                .named
+                .iter()
+                .map(|field| GeneratedField {
+                  name: field.ident.as_ref().unwrap().to_string(),
+                  field_type: type_string(&field.ty),
+                  default: field_default(&field.attrs, &type_string(&field.ty)),
+                  description: description(&field.attrs),
+                })
+                .collect_vec(),
+            ),
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+              let field = fields.unnamed.first().unwrap();
+              StructKind::Newtype { public: matches!(field.vis, Visibility::Public(_)), inner_type: type_string(&field.ty) }
+            }
+            _ => continue,
+          };
+          generated_types.structs.push(GeneratedStruct { name: item_struct.ident.to_string(), kind });
+        }
+        Item::Enum(item_enum) => generated_types.enums.push(GeneratedEnum {
+          name: item_enum.ident.to_string(),
+          unit_variants: if item_enum.variants.iter().all(|variant| variant.fields.is_empty()) {
+            Some(item_enum.variants.iter().map(|variant| variant.ident.to_string()).collect_vec())
+          } else {
+            None
+          },
+        }),
+        Item::Impl(item_impl) => {
+          if let Some((_, trait_path, _)) = &item_impl.trait_ {
+            let trait_name = trait_path.segments.last().map(|segment| segment.ident.to_string()).unwrap_or_default();
+            if trait_name == "ToString" || trait_name == "Display" {
+              generated_types.to_string_types.insert(type_string(&item_impl.self_ty));
+            }
+          }
+        }
+        Item::Mod(item_mod) if item_mod.ident == "defaults" => {
+          for default_item in item_mod.content.iter().flat_map(|(_, items)| items) {
+            if let Item::Fn(item_fn) = default_item {
+              if let [Stmt::Expr(expression)] = item_fn.block.stmts.as_slice() {
+                let expression = normalize(&expression.to_token_stream().to_string()).replace("super::", "");
+                generated_types.default_functions.insert(item_fn.sig.ident.to_string(), expression);
+              }
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+    Ok(generated_types)
+  }
+
+  fn unit_enum(&self, name: &str) -> Option<&Vec<String>> {
+    self
+      .enums
+      .iter()
+      .find(|generated_enum| generated_enum.name == name)
+      .and_then(|generated_enum| generated_enum.unit_variants.as_ref())
+  }
+
+  pub(crate) fn single_variant(&self, type_name: &str) -> Option<String> {
+    match self.unit_enum(type_name) {
+      Some(variants) if variants.len() == 1 => Some(format!("{}::{}", type_name, variants[0])),
+      _ => None,
+    }
+  }
+
+  pub(crate) fn default_expression(&self, field: &GeneratedField) -> Result<String, String> {
+    match &field.default {
+      FieldDefault::Function(function) => {
+        let (function_name, generic_arguments) = match function.trim_start_matches("defaults::").split_once("::<") {
+          Some((function_name, generic_arguments)) => (function_name, Some(generic_arguments.trim_end_matches('>'))),
+          None => (function.trim_start_matches("defaults::"), None),
+        };
+        match generic_arguments {
+          Some(generic_arguments) => {
+            let value = generic_arguments.rsplit(',').next().unwrap().trim();
+            if field.field_type.contains("NonZero") {
+              Ok(format!("{}::new({}).unwrap()", field.field_type, value))
+            } else {
+              Ok(value.to_string())
+            }
+          }
+          None => self
+            .default_functions
+            .get(function_name)
+            .cloned()
+            .ok_or_else(|| format!("default function '{}' not found in generated code", function_name)),
+        }
+      }
+      _ => Ok("Default::default()".to_string()),
+    }
+  }
+}
+
+impl HandWrittenImpls {
+  /// # Find the hand-written implementations
+  ///
+  /// # Parameters
+  /// * `default_source` - source code of the module with the hand-written `Default` implementations
+  /// * `new_source` - source code of the module with the hand-written `new` functions
+  /// * `display_source` - source code of the module with the hand-written `Display` implementations
+  pub fn from_sources(default_source: &str, new_source: &str, display_source: &str) -> Result<Self, String> {
+    let mut hand_written_impls = HandWrittenImpls::default();
+    for (item_impl, trait_name) in impls(default_source)?.iter().chain(impls(display_source)?.iter()) {
+      match trait_name.as_deref() {
+        Some("Default") => hand_written_impls.defaults.insert(type_string(&item_impl.self_ty)),
+        Some("Display") => hand_written_impls.displays.insert(type_string(&item_impl.self_ty)),
+        _ => false,
+      };
+    }
+    for (item_impl, trait_name) in impls(new_source)? {
+      if trait_name.is_none()
+        && item_impl
+          .items
+          .iter()
+          .any(|impl_item| matches!(impl_item, ImplItem::Method(method) if method.sig.ident == "new"))
+      {
+        hand_written_impls.news.insert(type_string(&item_impl.self_ty));
+      }
+    }
+    Ok(hand_written_impls)
+  }
+}
+
+/// # Generate `Default` implementations
+///
+/// A `Default` implementation will be generated for all structs that do not have a
+/// hand-written implementation, for which a default value can be determined for all fields.
+pub fn generate_default_impls(writer: &mut dyn Write, generated_types: &GeneratedTypes, hand_written_impls: &HandWrittenImpls) -> Result<(), Box<dyn Error>> {
+  let mut default_types: HashSet<String> = hand_written_impls.defaults.clone();
+  let mut default_impls: Vec<(String, String)> = vec![];
+  for generated_enum in &generated_types.enums {
+    if let Some(variant) = generated_types.single_variant(&generated_enum.name) {
+      if !default_types.contains(&generated_enum.name) {
+        default_types.insert(generated_enum.name.clone());
+        default_impls.push((generated_enum.name.clone(), variant));
+      }
+    }
+  }
+  // Since the default value of a struct can depend on the default values of other structs,
+  // repeat until no more default implementations can be generated
+  loop {
+    let mut added = false;
+    for generated_struct in &generated_types.structs {
+      if default_types.contains(&generated_struct.name) {
+        continue;
+      }
+      let default_value = match &generated_struct.kind {
+        StructKind::Named(fields) => {
+          let mut field_values = vec![];
+          for field in fields {
+            match &field.default {
+              FieldDefault::Required => match required_default_value(&field.field_type, generated_types, &default_types) {
+                Some(value) => field_values.push(format!("{}: {}", field.name, value)),
+                None => break,
+              },
+              FieldDefault::Optional => field_values.push(format!("{}: None", field.name)),
+              _ => field_values.push(format!("{}: {}", field.name, generated_types.default_expression(field)?)),
+            }
+          }
+          if field_values.len() == fields.len() {
+            Some(format!("Self {{ {} }}", field_values.join(", ")))
+          } else {
+            None
+          }
+        }
+        StructKind::Newtype { public: true, inner_type } => required_default_value(inner_type, generated_types, &default_types).map(|value| format!("Self({})", value)),
+        StructKind::Newtype { public: false, .. } => None,
+      };
+      if let Some(default_value) = default_value {
+        default_types.insert(generated_struct.name.clone());
+        default_impls.push((generated_struct.name.clone(), default_value));
+        added = true;
+      }
+    }
+    if !added {
+      break;
+    }
+  }
+  write_header(writer)?;
+  for (type_name, default_value) in default_impls.iter().sorted() {
+    write!(
+      writer,
+      "{}",
+      formatdoc!(
+        "
+
+        #[allow(clippy::derivable_impls)]
+        impl Default for {type_name} {{
+          fn default() -> Self {{
+            {default_value}
+          }}
+        }}
+        "
+      )
+    )?;
+  }
+  Ok(())
+}
+
+/// # Generate `new` functions
+///
+/// A `new` function will be generated for all structs with named fields that do not have a
+/// hand-written `new` function. The required fields will become the parameters of the function.
+/// Required fields that can only have one value (e.g. the `name` field of a `LimitValueCpu`)
+/// will be set automatically. Optional fields will be set to `None`, or to the default value
+/// from the openapi specification.
+pub fn generate_new_functions(writer: &mut dyn Write, generated_types: &GeneratedTypes, hand_written_impls: &HandWrittenImpls) -> Result<(), Box<dyn Error>> {
+  write_header(writer)?;
+  for generated_struct in generated_types.structs.iter().sorted_by_key(|generated_struct| &generated_struct.name) {
+    let fields = match &generated_struct.kind {
+      StructKind::Named(fields) if !hand_written_impls.news.contains(&generated_struct.name) => fields,
+      _ => continue,
+    };
+    let type_name = &generated_struct.name;
+    let mut parameters = vec![];
+    let mut parameter_docs = vec![];
+    let mut field_values = vec![];
+    for field in fields {
+      match &field.default {
+        FieldDefault::Required => match generated_types.single_variant(&field.field_type) {
+          Some(variant) => field_values.push(format!("{}: {}", field.name, variant)),
+          None => {
+            if field.field_type == "String" {
+              parameters.push(format!("{}: impl Into<String>", field.name));
+              field_values.push(format!("{}: {}.into()", field.name, field.name));
+            } else {
+              parameters.push(format!("{}: {}", field.name, field.field_type));
+              field_values.push(field.name.clone());
+            }
+            match &field.description {
+              Some(description) => parameter_docs.push(format!("  /// * `{}` - {}", field.name, description)),
+              None => parameter_docs.push(format!("  /// * `{}` - value for the `{}` field", field.name, field.name)),
+            }
+          }
+        },
+        FieldDefault::Optional => field_values.push(format!("{}: None", field.name)),
+        _ => field_values.push(format!("{}: {}", field.name, generated_types.default_expression(field)?)),
+      }
+    }
+    let parameters_doc = if parameter_docs.is_empty() { "".to_string() } else { format!("  ///\n  /// # Parameters\n{}\n", parameter_docs.join("\n")) };
+    write!(
+      writer,
+      "{}",
+      formatdoc!(
+        "
+
+        impl {type_name} {{
+          /// # Create a new `{type_name}`
+          ///
+          /// Create a new `{type_name}` from the values of the required fields.
+          /// The optional fields will be set to `None` or to their default value.
+        {parameters_doc}  ///
+          /// # Returns
+          /// The created `{type_name}`.
+          #[allow(clippy::too_many_arguments, clippy::new_without_default)]
+          pub fn new({parameters}) -> Self {{
+            Self {{ {field_values} }}
+          }}
+        }}
+        ",
+        parameters = parameters.join(", "),
+        field_values = field_values.join(", ")
+      )
+    )?;
+  }
+  Ok(())
+}
+
+/// # Generate `Display` implementations
+///
+/// A `Display` implementation will be generated for all structs and all enums with data
+/// that do not have a hand-written implementation. Enums without data already have a
+/// `ToString` implementation. The generated implementations use the function
+/// `write_serializable` from the `display` module of the `dsh_api` crate.
+pub fn generate_display_impls(writer: &mut dyn Write, generated_types: &GeneratedTypes, hand_written_impls: &HandWrittenImpls) -> Result<(), Box<dyn Error>> {
+  let type_names = generated_types
+    .structs
+    .iter()
+    .map(|generated_struct| &generated_struct.name)
+    .chain(
+      generated_types
+        .enums
+        .iter()
+        .filter(|generated_enum| generated_enum.unit_variants.is_none())
+        .map(|generated_enum| &generated_enum.name),
+    )
+    .filter(|type_name| !hand_written_impls.displays.contains(*type_name) && !generated_types.to_string_types.contains(*type_name))
+    .sorted();
+  write_header(writer)?;
+  for type_name in type_names {
+    write!(
+      writer,
+      "{}",
+      formatdoc!(
+        "
+
+        impl std::fmt::Display for {type_name} {{
+          fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+            write_serializable(f, self)
+          }}
+        }}
+        "
+      )
+    )?;
+  }
+  Ok(())
+}
+
+fn write_header(writer: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+  writeln!(writer, "// Generated from the openapi specification, do not edit")?;
+  writeln!(writer, "#[allow(unused_imports)]")?;
+  writeln!(writer, "use crate::types::*;")?;
+  Ok(())
+}
+
+fn required_default_value(field_type: &str, generated_types: &GeneratedTypes, default_types: &HashSet<String>) -> Option<String> {
+  const DEFAULT_TYPES: [&str; 13] = ["String", "bool", "f32", "f64", "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "serde_json::Value"];
+  const DEFAULT_TYPE_PREFIXES: [&str; 5] = ["Vec<", "std::collections::HashMap<", "std::collections::BTreeMap<", "serde_json::Map<", "::std::collections::HashMap<"];
+  if DEFAULT_TYPES.contains(&field_type) || DEFAULT_TYPE_PREFIXES.iter().any(|prefix| field_type.starts_with(prefix)) || default_types.contains(field_type) {
+    Some("Default::default()".to_string())
+  } else if field_type.starts_with("std::num::NonZero") {
+    Some(format!("{}::MIN", field_type))
+  } else {
+    generated_types.single_variant(field_type)
+  }
+}
+
+fn field_default(attributes: &[Attribute], field_type: &str) -> FieldDefault {
+  for attribute in attributes.iter().filter(|attribute| attribute.path.is_ident("serde")) {
+    if let Ok(Meta::List(meta_list)) = attribute.parse_meta() {
+      for nested_meta in meta_list.nested {
+        match nested_meta {
+          NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("default") => {
+            if let Lit::Str(function) = name_value.lit {
+              return FieldDefault::Function(function.value());
+            }
+          }
+          NestedMeta::Meta(Meta::Path(path)) if path.is_ident("default") => {
+            return if field_type.starts_with("Option<") { FieldDefault::Optional } else { FieldDefault::Default };
+          }
+          _ => {}
+        }
+      }
+    }
+  }
+  if field_type.starts_with("Option<") {
+    FieldDefault::Optional
+  } else {
+    FieldDefault::Required
+  }
+}
+
+fn description(attributes: &[Attribute]) -> Option<String> {
+  attributes
+    .iter()
+    .filter(|attribute| attribute.path.is_ident("doc"))
+    .filter_map(|attribute| match attribute.parse_meta() {
+      Ok(Meta::NameValue(name_value)) => match name_value.lit {
+        Lit::Str(doc) => doc.value().lines().map(|line| line.trim().to_string()).find(|line| !line.is_empty()),
+        _ => None,
+      },
+      _ => None,
+    })
+    .next()
+    .map(revise)
+}
+
+fn impls(source: &str) -> Result<Vec<(syn::ItemImpl, Option<String>)>, String> {
+  let file = syn::parse_file(source).map_err(|error| format!("could not parse source code ({})", error))?;
+  Ok(
+    file
+      .items
+      .into_iter()
+      .filter_map(|item| match item {
+        Item::Impl(item_impl) => {
+          let trait_name = item_impl
+            .trait_
+            .as_ref()
+            .and_then(|(_, path, _)| path.segments.last().map(|segment| segment.ident.to_string()));
+          Some((item_impl, trait_name))
+        }
+        _ => None,
+      })
+      .collect_vec(),
+  )
+}
+
+fn type_string<T: ToTokens>(tokens: &T) -> String {
+  normalize(&tokens.to_token_stream().to_string())
+}
+
+// Remove the whitespace that `to_string()` inserts between tokens where it is not needed
+fn normalize(tokens: &str) -> String {
+  tokens
+    .replace(" :: ", "::")
+    .replace(":: ", "::")
+    .replace(" < ", "<")
+    .replace("< ", "<")
+    .replace(" >", ">")
+    .replace(" ,", ",")
+    .replace(" (", "(")
+    .replace("( ", "(")
+    .replace(" )", ")")
+    .replace("! [", "![")
+    .replace(" . ", ".")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const GENERATED_CODE: &str = r#"
+    pub mod types {
+      pub struct Proxy {
+        ///Number of instances
+        pub instances: std::num::NonZeroU64,
+        #[serde(default = "defaults::proxy_path")]
+        pub path: String,
+        #[serde(default = "defaults::default_u64::<u64, 7070>")]
+        pub port: u64,
+        pub kind: ProxyKind,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub zone: Option<String>,
+      }
+      pub enum ProxyKind {
+        #[serde(rename = "proxy")]
+        Proxy,
+      }
+      impl ToString for ProxyKind {
+        fn to_string(&self) -> String {
+          "proxy".to_string()
+        }
+      }
+      pub struct Other {
+        pub name: String,
+      }
+      pub mod defaults {
+        pub(super) fn proxy_path() -> String {
+          "/proxy".to_string()
+        }
+      }
+    }
+  "#;
+
+  const HAND_WRITTEN_DEFAULT: &str = "impl Default for Other { fn default() -> Self { todo!() } }";
+
+  type Generator = fn(&mut dyn Write, &GeneratedTypes, &HandWrittenImpls) -> Result<(), Box<dyn Error>>;
+
+  fn generate(generator: Generator) -> String {
+    let generated_types = GeneratedTypes::from_generated_code(GENERATED_CODE).unwrap();
+    let hand_written_impls = HandWrittenImpls::from_sources(HAND_WRITTEN_DEFAULT, "", "").unwrap();
+    let mut generated = vec![];
+    generator(&mut generated, &generated_types, &hand_written_impls).unwrap();
+    String::from_utf8(generated).unwrap()
+  }
+
+  #[test]
+  fn test_generate_default_impls() {
+    let generated = generate(generate_default_impls);
+    assert!(generated.contains("Self { instances: std::num::NonZeroU64::MIN, path: \"/proxy\".to_string(), port: 7070, kind: Default::default(), zone: None }"));
+    assert!(generated.contains("impl Default for ProxyKind"));
+    assert!(!generated.contains("impl Default for Other"));
+  }
+
+  #[test]
+  fn test_generate_new_functions() {
+    let generated = generate(generate_new_functions);
+    assert!(generated.contains("pub fn new(instances: std::num::NonZeroU64) -> Self"));
+    assert!(generated.contains("/// * `instances` - Number of instances."));
+    assert!(generated.contains("pub fn new(name: impl Into<String>) -> Self"));
+  }
+
+  #[test]
+  fn test_generate_display_impls() {
+    let generated = generate(generate_display_impls);
+    assert!(generated.contains("impl std::fmt::Display for Proxy {"));
+    assert!(generated.contains("impl std::fmt::Display for Other {"));
+    assert!(!generated.contains("impl std::fmt::Display for ProxyKind"));
+  }
+}
@@ -0,0 +1,130 @@
+//! Generate the operations table for the mock server
+//!
+//! The mock server in the `dsh_api` crate implements the allocation, app catalog and manage
+//! operations from the openapi specification on an in-memory store.
+//! This module generates the table that describes how the mock server must handle each operation.
+//! The way an operation is handled is derived from its method, its path
+//! and the schemas of its request and response bodies.
+
+use crate::openapi_utils::reference_to_string;
+use openapiv3::{OpenAPI, Operation, ReferenceOr, Schema, SchemaKind, StatusCode, Type};
+use std::error::Error;
+use std::io::Write;
+
+/// Path prefixes of the operations that will be implemented by the mock server
+const MOCKED_PATH_PREFIXES: [&str; 3] = ["/allocation/", "/appcatalog/", "/manage/"];
+
+/// # Generate the operations table for the mock server
+///
+/// The generated code expects the types `MockOperation` and `MockOperationKind`
+/// to be in scope.
+///
+/// # Parameters
+/// * `writer` - the generated code will be written to this writer
+/// * `openapi_spec` - openapi specification that contains the operations
+pub fn generate_mock_operations(writer: &mut dyn Write, openapi_spec: &OpenAPI) -> Result<(), Box<dyn Error>> {
+  writeln!(writer, "// Generated from the openapi specification, do not edit")?;
+  writeln!(writer, "pub(crate) static MOCK_OPERATIONS: &[MockOperation] = &[")?;
+  for (path, path_item) in openapi_spec.paths.iter() {
+    if !MOCKED_PATH_PREFIXES.iter().any(|prefix| path.starts_with(prefix)) {
+      continue;
+    }
+    if let ReferenceOr::Item(path_item) = path_item {
+      for (method, operation) in path_item.iter() {
+        writeln!(
+          writer,
+          "  MockOperation {{ method: \"{}\", path: \"{}\", kind: MockOperationKind::{}, status: {} }},",
+          method.to_uppercase(),
+          path,
+          operation_kind(method, path, operation, openapi_spec),
+          success_status(operation)
+        )?;
+      }
+    }
+  }
+  writeln!(writer, "];")?;
+  Ok(())
+}
+
+fn operation_kind(method: &str, path: &str, operation: &Operation, openapi_spec: &OpenAPI) -> &'static str {
+  match method {
+    "get" => get_operation_kind(path, operation, openapi_spec),
+    "delete" => "Delete",
+    _ => {
+      let request_body = match &operation.request_body {
+        Some(ReferenceOr::Item(request_body)) => Some(request_body),
+        _ => None,
+      };
+      match request_body {
+        Some(request_body) if request_body.content.contains_key("text/plain") => "StoreText",
+        Some(request_body) => match request_body.content.get("application/json").and_then(|media_type| media_type.schema.as_ref()) {
+          Some(ReferenceOr::Item(schema)) if matches!(schema.schema_kind, SchemaKind::Type(Type::Array(_))) => "StoreEach",
+          Some(ReferenceOr::Reference { .. }) if method == "post" && !path.ends_with("/configuration") => "StoreNamed",
+          _ => "Store",
+        },
+        None => "Store",
+      }
+    }
+  }
+}
+
+fn get_operation_kind(path: &str, operation: &Operation, openapi_spec: &OpenAPI) -> &'static str {
+  let response = operation
+    .responses
+    .responses
+    .iter()
+    .find_map(|(status_code, response)| match (status_code, response) {
+      (StatusCode::Code(200..=299), ReferenceOr::Item(response)) => Some(response),
+      _ => None,
+    });
+  let Some(response) = response else { return "Value" };
+  if response.content.contains_key("text/plain") {
+    return "Text";
+  }
+  match response.content.get("application/json").and_then(|media_type| media_type.schema.as_ref()) {
+    Some(ReferenceOr::Reference { reference }) => {
+      let schema_name = reference_to_string(reference);
+      if schema_name == "ChildList" {
+        "Ids"
+      } else if schema_name == "Empty" {
+        "Empty"
+      } else if path.ends_with("/status") {
+        "Status"
+      } else if schema_name.ends_with("Status") {
+        "Aggregate"
+      } else {
+        "Value"
+      }
+    }
+    Some(ReferenceOr::Item(schema)) => match &schema.schema_kind {
+      SchemaKind::Type(Type::Array(array_type)) => match &array_type.items {
+        Some(ReferenceOr::Reference { reference }) if is_string_schema(&reference_to_string(reference), openapi_spec) => "Ids",
+        Some(ReferenceOr::Item(items)) if matches!(items.schema_kind, SchemaKind::Type(Type::String(_))) => "Ids",
+        _ => "Values",
+      },
+      SchemaKind::Type(Type::Object(object_type)) if object_type.additional_properties.is_some() => "Map",
+      _ => "Value",
+    },
+    None => "Value",
+  }
+}
+
+fn is_string_schema(schema_name: &str, openapi_spec: &OpenAPI) -> bool {
+  openapi_spec
+    .components
+    .as_ref()
+    .and_then(|components| components.schemas.get(schema_name))
+    .is_some_and(|schema| matches!(schema, ReferenceOr::Item(Schema { schema_kind: SchemaKind::Type(Type::String(_)), .. })))
+}
+
+fn success_status(operation: &Operation) -> u16 {
+  operation
+    .responses
+    .responses
+    .keys()
+    .find_map(|status_code| match status_code {
+      StatusCode::Code(code @ 200..=299) => Some(*code),
+      _ => None,
+    })
+    .unwrap_or(200)
+}
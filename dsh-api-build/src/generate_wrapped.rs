@@ -1,45 +1,196 @@
-//! Generate the generic client code
+//! Generate the wrapped client code
+//!
+//! The wrapper methods call the methods generated by the `progenitor` library,
+//! fill in the managed parameters and the token, and process the responses.
+//! By default the methods are generated for the `DshApiClient` struct,
+//! but the generator can be configured with a [`WrapperConfig`]
+//! to generate a client for other DSH-style openapi specifications.
 
 use crate::dsh_api_operation::{method_api_operations, DshApiOperation, ParameterType};
-use crate::openapi_utils::{method_path_operations, OpenApiOperationKind};
-use crate::{capitalize, RequestBodyType, ResponseBodyType, MANAGED_PARAMETERS, METHODS};
+use crate::openapi_utils::method_path_operations;
+use crate::{capitalize, Method, RequestBodyType, ResponseBodyType, MANAGED_PARAMETERS, METHODS};
 use indoc::formatdoc;
 use itertools::Itertools;
 use openapiv3::{OpenAPI, Operation};
 use std::error::Error;
 use std::io::Write;
 
+/// # Configuration of the wrapper generator
+///
+/// The default configuration generates the wrapper methods for the `DshApiClient` struct
+/// from the `dsh_api` crate.
+/// The struct for which the wrapper methods are generated must have
+/// * a field or method that yields the client generated by `progenitor`
+///   (see [`generated_client`](WrapperConfig::generated_client)),
+/// * the async methods `process` and `process_string`, that convert the results of the
+///   `progenitor` methods to a `Result<(status, value), E>`, where `E` is the error type,
+/// * when [`strict_mode`](WrapperConfig::strict_mode) is enabled, the methods `strict_mode()`
+///   and `strict_get(path, parameters, token)`.
+///
+/// # Examples
+///
+/// ```
+/// use dsh_api_build_helpers::generate_wrapped::{ManagedParameter, WrapperConfig};
+///
+/// let wrapper_config = WrapperConfig {
+///   client_struct: "InventoryClient".to_string(),
+///   imports: vec!["crate::InventoryClient".to_string(), "crate::types::*".to_string()],
+///   managed_path_parameters: vec![ManagedParameter::new("organisation", "self.organisation()")],
+///   token_expression: "self.access_token().await?.as_str()".to_string(),
+///   result_type: "Result<{}, InventoryError>".to_string(),
+///   strict_mode: false,
+///   ..WrapperConfig::default()
+/// };
+/// ```
+#[derive(Clone, Debug)]
+pub struct WrapperConfig {
+  /// Name of the struct for which the wrapper methods will be generated
+  pub client_struct: String,
+  /// Paths that will be imported by the generated code with `use` declarations
+  pub imports: Vec<String>,
+  /// Path parameters that are filled in by the client.
+  /// A managed path parameter is only filled in when it is the first parameter of an operation.
+  pub managed_path_parameters: Vec<ManagedParameter>,
+  /// Header and query parameters, other than the token parameter, that are filled in by the client
+  pub managed_parameters: Vec<ManagedParameter>,
+  /// Name of the header parameter that contains the token
+  pub token_parameter: String,
+  /// Expression that yields the token as a `&str`, evaluated in an async method of the client
+  pub token_expression: String,
+  /// Return type of the wrapper methods, where `{}` will be replaced by the type of the returned value
+  /// (e.g. `DshApiResult<{}>` or `Result<{}, MyError>`).
+  /// The error type must implement `From` for the errors returned by the token expression.
+  pub result_type: String,
+  /// Expression that yields the client generated by `progenitor`
+  pub generated_client: String,
+  /// Whether `get` operations that return `json` will be performed by the strict mode
+  /// implementation of the client, when strict mode is enabled.
+  /// Operations with query or header parameters other than the token parameter
+  /// are always performed by the generated client.
+  pub strict_mode: bool,
+  /// Path prefixes of operations that are only available when a feature is enabled,
+  /// together with the name of the feature
+  pub features: Vec<(String, String)>,
+}
+
+/// # Parameter that is filled in by the client
+#[derive(Clone, Debug)]
+pub struct ManagedParameter {
+  /// Name of the parameter in the openapi specification
+  pub name: String,
+  /// Expression that yields the value of the parameter
+  pub expression: String,
+}
+
+impl ManagedParameter {
+  /// Creates a new managed parameter
+  ///
+  /// # Parameters
+  /// * `name` - name of the parameter in the openapi specification
+  /// * `expression` - expression that yields the value of the parameter,
+  ///   evaluated in a method of the client struct (e.g. `self.tenant_name()`)
+  pub fn new(name: impl Into<String>, expression: impl Into<String>) -> Self {
+    Self { name: name.into(), expression: expression.into() }
+  }
+}
+
+impl Default for WrapperConfig {
+  fn default() -> Self {
+    Self {
+      client_struct: "DshApiClient".to_string(),
+      imports: vec!["crate::dsh_api_client::DshApiClient".to_string(), "crate::types::*".to_string(), "std::collections::HashMap".to_string()],
+      managed_path_parameters: vec![ManagedParameter::new("manager", "self.tenant_name()"), ManagedParameter::new("tenant", "self.tenant_name()")],
+      managed_parameters: vec![],
+      token_parameter: MANAGED_PARAMETERS[0].to_string(),
+      token_expression: "self.token().await?.as_str()".to_string(),
+      result_type: "DshApiResult<{}>".to_string(),
+      generated_client: "self.generated_client".to_string(),
+      strict_mode: true,
+      features: vec![("/manage/".to_string(), "manage".to_string()), ("/robot/".to_string(), "robot".to_string())],
+    }
+  }
+}
+
+impl WrapperConfig {
+  fn is_managed(&self, parameter_name: &str) -> bool {
+    parameter_name == self.token_parameter || self.managed_parameters.iter().any(|managed_parameter| managed_parameter.name == parameter_name)
+  }
+
+  // Returns the expression for a parameter, which is either the expression for a managed parameter
+  // or the name of the parameter of the wrapper method. String parameters of the wrapper method
+  // accept any `impl AsRef<str>`, which is converted to a `&str` for the generated client.
+  fn parameter_expression(&self, parameter_name: &str, parameter_type: &ParameterType) -> String {
+    if parameter_name == self.token_parameter {
+      self.token_expression.clone()
+    } else {
+      match self.managed_parameters.iter().find(|managed_parameter| managed_parameter.name == parameter_name) {
+        Some(managed_parameter) => managed_parameter.expression.clone(),
+        None => match parameter_type {
+          ParameterType::RefStr => format!("{}.as_ref()", parameter_name.to_lowercase()),
+          _ => parameter_name.to_lowercase(),
+        },
+      }
+    }
+  }
+
+  fn result_type(&self, value_type: &str) -> String {
+    self.result_type.replace("{}", value_type)
+  }
+}
+
+/// # Generate the wrapper methods for the `DshApiClient`
+///
+/// # Parameters
+/// * `writer` - the generated code will be written to this writer
+/// * `openapi` - openapi specification, updated with
+///   [`update_openapi`](crate::update_openapi::update_openapi)
 pub fn generate_wrapped(writer: &mut dyn Write, openapi: &OpenAPI) -> Result<(), Box<dyn Error>> {
+  generate_wrapped_with_config(writer, openapi, &WrapperConfig::default())
+}
+
+/// # Generate the wrapper methods for a configurable client struct
+///
+/// # Parameters
+/// * `writer` - the generated code will be written to this writer
+/// * `openapi` - openapi specification, updated with
+///   [`update_openapi`](crate::update_openapi::update_openapi)
+/// * `wrapper_config` - configuration of the generated code
+pub fn generate_wrapped_with_config(writer: &mut dyn Write, openapi: &OpenAPI, wrapper_config: &WrapperConfig) -> Result<(), Box<dyn Error>> {
+  let managed_path_parameters = wrapper_config
+    .managed_path_parameters
+    .iter()
+    .map(|managed_parameter| managed_parameter.name.as_str())
+    .collect_vec();
   let mut wrapped_operations: Vec<DshApiOperation> = vec![];
   for method in &METHODS {
     let path_operations: Vec<(&String, &Operation)> = method_path_operations(method, openapi);
-    let method_generic_operations = method_api_operations(method, &path_operations)?;
+    let method_generic_operations = method_api_operations(method, &path_operations, &managed_path_parameters)?;
     wrapped_operations.extend(method_generic_operations);
   }
   wrapped_operations.sort_by(|operation_a, operation_b| operation_a.selector.cmp(&operation_b.selector));
   writeln!(writer, "#[cfg_attr(rustfmt, rustfmt_skip)]")?;
-  writeln!(writer, "use crate::dsh_api_client::DshApiClient;")?;
-  writeln!(writer, "use crate::types::*;")?;
-  writeln!(writer, "use std::collections::HashMap;")?;
+  for import in &wrapper_config.imports {
+    writeln!(writer, "use {};", import)?;
+  }
   writeln!(writer)?;
   writeln!(writer, "/// # API methods")?;
   writeln!(writer, "///")?;
   writeln!(writer, "/// Module that contains all methods to call the API methods.")?;
   writeln!(writer, "/// These methods are wrappers around the methods generated from the `progenitor` library.")?;
-  writeln!(writer, "impl DshApiClient {{")?;
+  writeln!(writer, "impl {} {{", wrapper_config.client_struct)?;
   let mut first = true;
   for operation in &wrapped_operations {
     if !first {
       writeln!(writer)?;
     }
-    write_wrapped_operation(writer, operation)?;
+    write_wrapped_operation(writer, operation, wrapper_config)?;
     first = false;
   }
   writeln!(writer, "}}")?;
   Ok(())
 }
 
-fn write_wrapped_operation(writer: &mut dyn Write, operation: &DshApiOperation) -> Result<(), Box<dyn Error>> {
+fn write_wrapped_operation(writer: &mut dyn Write, operation: &DshApiOperation, wrapper_config: &WrapperConfig) -> Result<(), Box<dyn Error>> {
   writeln!(
     writer,
     "  /// # {} {}",
@@ -57,16 +208,22 @@ fn write_wrapped_operation(writer: &mut dyn Write, operation: &DshApiOperation)
   writeln!(writer, "  /// `{}` `{}`", operation.method.to_string().as_str().to_uppercase(), operation.path)?;
   let mut parameters_header_written = false;
   for (parameter_name, parameter_type, description) in &operation.parameters {
-    if !MANAGED_PARAMETERS.contains(&parameter_name.as_str()) {
+    if !wrapper_config.is_managed(parameter_name) {
       if !parameters_header_written {
         writeln!(writer, "  ///")?;
         writeln!(writer, "  /// # Parameters")?;
         parameters_header_written = true;
       }
       if let Some(description) = description {
-        writeln!(writer, "  /// * `{}` : `{}` - {}", parameter_name, parameter_type, description)?;
+        writeln!(
+          writer,
+          "  /// * `{}` : `{}` - {}",
+          parameter_name,
+          wrapper_parameter_type(parameter_type),
+          description
+        )?;
       } else {
-        writeln!(writer, "  /// * `{}` : `{}`", parameter_name, parameter_type)?;
+        writeln!(writer, "  /// * `{}` : `{}`", parameter_name, wrapper_parameter_type(parameter_type))?;
       }
     }
   }
@@ -81,27 +238,23 @@ fn write_wrapped_operation(writer: &mut dyn Write, operation: &DshApiOperation)
     }
   }
   writeln!(writer, "  ///")?;
-  match operation.kind {
-    OpenApiOperationKind::Allocation | OpenApiOperationKind::AppCatalog => {}
-    OpenApiOperationKind::Manage | OpenApiOperationKind::Robot => writeln!(writer, "  /// _This method is only available when the `{}` feature is enabled._", operation.kind)?,
+  if let Some((_, feature)) = wrapper_config
+    .features
+    .iter()
+    .find(|(path_prefix, _)| operation.path.starts_with(path_prefix.as_str()))
+  {
+    writeln!(writer, "  /// _This method is only available when the `{}` feature is enabled._", feature)?;
   }
-  writeln!(writer, "  {}", wrapped_method(operation))?;
+  writeln!(writer, "  {}", wrapped_method(operation, wrapper_config))?;
   Ok(())
 }
 
-fn wrapped_method(dsh_api_operation: &DshApiOperation) -> String {
+fn wrapped_method(dsh_api_operation: &DshApiOperation, wrapper_config: &WrapperConfig) -> String {
   let mut signature_parameters = dsh_api_operation
     .parameters
     .iter()
-    .filter_map(
-      |(parameter_name, parameter_type, _)| {
-        if parameter_name != "Authorization" {
-          Some(wrapper_signature_parameter(parameter_type, parameter_name.to_lowercase().as_str()))
-        } else {
-          None
-        }
-      },
-    )
+    .filter(|(parameter_name, _, _)| !wrapper_config.is_managed(parameter_name))
+    .map(|(parameter_name, parameter_type, _)| wrapper_signature_parameter(parameter_type, parameter_name.to_lowercase().as_str()))
     .collect_vec();
   if let Some(ref request_body_type) = dsh_api_operation.request_body {
     match request_body_type {
@@ -110,38 +263,37 @@ fn wrapped_method(dsh_api_operation: &DshApiOperation) -> String {
     }
   }
   let signature_parameters = if signature_parameters.is_empty() { "".to_string() } else { format!(", {}", signature_parameters.join(", ")) };
-  let mut call_parameters = dsh_api_operation
-    .parameters
-    .iter()
-    .map(
-      |(parameter_name, _, _)| {
-        if parameter_name == "Authorization" {
-          "self.token().await?.as_str()".to_string()
-        } else {
-          parameter_name.to_lowercase()
-        }
-      },
-    )
-    .collect_vec();
+  let mut call_parameters = vec![];
+  if let Some(ref managed_path_parameter) = dsh_api_operation.managed_path_parameter {
+    if let Some(managed_parameter) = wrapper_config
+      .managed_path_parameters
+      .iter()
+      .find(|managed_parameter| &managed_parameter.name == managed_path_parameter)
+    {
+      call_parameters.push(managed_parameter.expression.clone());
+    }
+  }
+  call_parameters.extend(
+    dsh_api_operation
+      .parameters
+      .iter()
+      .map(|(parameter_name, parameter_type, _)| wrapper_config.parameter_expression(parameter_name, parameter_type)),
+  );
   if dsh_api_operation.request_body.is_some() {
     call_parameters.push("body".to_string());
   }
-  let call_parameters = if call_parameters.is_empty() { "".to_string() } else { format!(", {}", call_parameters.join(", ")) };
+  let request = request_expression(dsh_api_operation, call_parameters.join(", ").as_str(), wrapper_config);
   let method = &dsh_api_operation.method.to_string();
   let selector = &dsh_api_operation.selector.to_lowercase().replace('-', "_");
-  let operation_id = &dsh_api_operation.operation_id;
-  let return_type = wrapped_return_value_type(&dsh_api_operation.ok_response);
+  let return_type = wrapper_config.result_type(&wrapped_return_value_type(&dsh_api_operation.ok_response));
   let processing_function = dsh_api_operation.ok_response.processing_function();
   if dsh_api_operation.ok_response == ResponseBodyType::Ids {
     formatdoc!(
       r#"
-          pub async fn {method}_{selector}(&self{signature_parameters}) -> DshApiResult<{return_type}> {{
+          pub async fn {method}_{selector}(&self{signature_parameters}) -> {return_type} {{
               match self
                 .{processing_function}(
-                  self
-                    .generated_client
-                    .{operation_id}(self.tenant_name(){call_parameters})
-                    .await
+                  {request}
                 )
                 .await
                 .map(|(_, result)| result)
@@ -157,13 +309,10 @@ fn wrapped_method(dsh_api_operation: &DshApiOperation) -> String {
   } else {
     formatdoc!(
       r#"
-          pub async fn {method}_{selector}(&self{signature_parameters}) -> DshApiResult<{return_type}> {{
+          pub async fn {method}_{selector}(&self{signature_parameters}) -> {return_type} {{
               self
                 .{processing_function}(
-                  self
-                    .generated_client
-                    .{operation_id}(self.tenant_name(){call_parameters})
-                    .await
+                  {request}
                 )
                 .await
                 .map(|(_, result)| result)
@@ -172,6 +321,40 @@ fn wrapped_method(dsh_api_operation: &DshApiOperation) -> String {
   }
 }
 
+// Returns the expression that performs the request. For `GET` operations that return `json`,
+// the request will be performed by the strict mode implementation when strict mode is enabled.
+// Since the strict mode implementation only fills in path parameters, operations with
+// query or header parameters other than the token parameter are always performed by the generated client.
+fn request_expression(dsh_api_operation: &DshApiOperation, call_parameters: &str, wrapper_config: &WrapperConfig) -> String {
+  let operation_id = &dsh_api_operation.operation_id;
+  let generated_request = format!("{}.{}({}).await", wrapper_config.generated_client, operation_id, call_parameters);
+  let validated_response = matches!(
+    dsh_api_operation.ok_response,
+    ResponseBodyType::Ids | ResponseBodyType::SerializableMap(_) | ResponseBodyType::SerializableScalar(_) | ResponseBodyType::SerializableVector(_)
+  );
+  let only_path_parameters = dsh_api_operation
+    .parameters
+    .iter()
+    .all(|(parameter_name, _, _)| parameter_name == &wrapper_config.token_parameter || dsh_api_operation.path.contains(&format!("{{{}}}", parameter_name)));
+  if wrapper_config.strict_mode && dsh_api_operation.method == Method::Get && validated_response && only_path_parameters {
+    let strict_parameters = dsh_api_operation
+      .parameters
+      .iter()
+      .filter(|(parameter_name, _, _)| !wrapper_config.is_managed(parameter_name))
+      .map(|(parameter_name, parameter_type, _)| match parameter_type {
+        ParameterType::RefStr => format!("(\"{}\", {}.as_ref().to_string())", parameter_name, parameter_name.to_lowercase()),
+        _ => format!("(\"{}\", {}.to_string())", parameter_name, parameter_name.to_lowercase()),
+      })
+      .join(", ");
+    format!(
+      "if self.strict_mode() {{ self.strict_get(\"{}\", &[{}], {}).await }} else {{ {} }}",
+      dsh_api_operation.path, strict_parameters, wrapper_config.token_expression, generated_request
+    )
+  } else {
+    generated_request
+  }
+}
+
 fn wrapped_return_value_type(response_body_type: &ResponseBodyType) -> String {
   match response_body_type {
     ResponseBodyType::Ids => "Vec<String>".to_string(),
@@ -184,10 +367,91 @@ fn wrapped_return_value_type(response_body_type: &ResponseBodyType) -> String {
 }
 
 fn wrapper_signature_parameter(parameter_type: &ParameterType, parameter_name: &str) -> String {
+  format!("{}: {}", parameter_name, wrapper_parameter_type(parameter_type))
+}
+
+pub(crate) fn wrapper_parameter_type(parameter_type: &ParameterType) -> String {
   match parameter_type {
-    ParameterType::ConstructedTypeOwned(constructed_type) => format!("{}: {}", parameter_name, constructed_type),
-    ParameterType::ConstructedTypeRef(constructed_type) => format!("{}: &{}", parameter_name, constructed_type),
-    ParameterType::SerializableType(serializable_type) => format!("{}: &{}", parameter_name, serializable_type),
-    ParameterType::RefStr => format!("{}: &str", parameter_name),
+    ParameterType::RefStr => "impl AsRef<str>".to_string(),
+    _ => parameter_type.to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::update_openapi::update_openapi;
+  use serde_json::json;
+
+  #[test]
+  fn test_generate_wrapped_with_config() {
+    let mut openapi_spec: OpenAPI = serde_json::from_value(json!({
+      "openapi": "3.0.1",
+      "info": { "title": "inventory", "version": "1.0.0" },
+      "paths": { "/inventory/{organisation}/widget/{id}": { "get": {
+        "parameters": [
+          { "name": "organisation", "in": "path", "required": true, "schema": { "type": "string" } },
+          { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } },
+          { "name": "X-Request-Id", "in": "header", "required": true, "schema": { "type": "string" } }
+        ],
+        "responses": { "200": { "description": "ok", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Widget" } } } } }
+      }}},
+      "components": { "schemas": { "Widget": { "type": "object" } } }
+    }))
+    .unwrap();
+    update_openapi(&mut openapi_spec, &[], false, false).unwrap();
+    let wrapper_config = WrapperConfig {
+      client_struct: "InventoryClient".to_string(),
+      imports: vec!["crate::InventoryClient".to_string()],
+      managed_path_parameters: vec![ManagedParameter::new("organisation", "self.organisation()")],
+      managed_parameters: vec![ManagedParameter::new("X-Request-Id", "self.request_id()")],
+      token_expression: "self.access_token().await?.as_str()".to_string(),
+      result_type: "Result<{}, InventoryError>".to_string(),
+      strict_mode: false,
+      ..WrapperConfig::default()
+    };
+    let mut generated_code = vec![];
+    generate_wrapped_with_config(&mut generated_code, &openapi_spec, &wrapper_config).unwrap();
+    let generated_code = String::from_utf8(generated_code).unwrap();
+    assert!(generated_code.contains("use crate::InventoryClient;\n"));
+    assert!(generated_code.contains("impl InventoryClient {\n"));
+    assert!(generated_code.contains("pub async fn get_inventory_widget(&self, id: impl AsRef<str>) -> Result<Widget, InventoryError> {"));
+    assert!(generated_code.contains(".get_widget_by_organisation_by_id(self.organisation(), id.as_ref(), self.request_id(), self.access_token().await?.as_str()).await"));
+    assert!(!generated_code.contains("strict_get"));
+    assert!(!generated_code.contains("feature is enabled"));
+  }
+
+  #[test]
+  fn test_generate_wrapped_strict_mode_query_parameter() {
+    let mut openapi_spec: OpenAPI = serde_json::from_value(json!({
+      "openapi": "3.0.1",
+      "info": { "title": "inventory", "version": "1.0.0" },
+      "paths": {
+        "/inventory/{organisation}/widget": { "get": {
+          "parameters": [
+            { "name": "organisation", "in": "path", "required": true, "schema": { "type": "string" } },
+            { "name": "color", "in": "query", "required": true, "schema": { "type": "string" } }
+          ],
+          "responses": { "200": { "description": "ok", "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Widget" } } } } } }
+        }},
+        "/inventory/{organisation}/widget/{id}": { "get": {
+          "parameters": [
+            { "name": "organisation", "in": "path", "required": true, "schema": { "type": "string" } },
+            { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }
+          ],
+          "responses": { "200": { "description": "ok", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Widget" } } } } }
+        }}
+      },
+      "components": { "schemas": { "Widget": { "type": "object" } } }
+    }))
+    .unwrap();
+    update_openapi(&mut openapi_spec, &[], false, false).unwrap();
+    let wrapper_config =
+      WrapperConfig { managed_path_parameters: vec![ManagedParameter::new("organisation", "self.organisation()")], strict_mode: true, ..WrapperConfig::default() };
+    let mut generated_code = vec![];
+    generate_wrapped_with_config(&mut generated_code, &openapi_spec, &wrapper_config).unwrap();
+    let generated_code = String::from_utf8(generated_code).unwrap();
+    assert!(generated_code.contains("self.strict_get(\"/inventory/{organisation}/widget/{id}\", &[(\"id\", id.as_ref().to_string())]"));
+    assert!(!generated_code.contains("self.strict_get(\"/inventory/{organisation}/widget\","));
   }
 }
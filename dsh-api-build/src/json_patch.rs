@@ -0,0 +1,367 @@
+//! Apply JSON Patch documents to a json value
+//!
+//! This module contains a minimal implementation of
+//! [RFC 6902 (JSON Patch)](https://datatracker.ietf.org/doc/html/rfc6902),
+//! which is used to fix bugs in the original openapi specification before the code is generated.
+//!
+//! * A patch document is a json array of operations (`add`, `remove`, `replace`, `move`, `copy`
+//!   and `test`) that will be applied in order.
+//! * The operations in a patch document are applied atomically.
+//!   If one of the operations fails, the value will not be changed.
+//! * Patch documents are read from a directory and applied in the lexicographical
+//!   order of their file names.
+
+use itertools::Itertools;
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+/// Single JSON Patch operation
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "op", rename_all = "lowercase", deny_unknown_fields)]
+pub enum PatchOperation {
+  Add { path: String, value: Value },
+  Remove { path: String },
+  Replace { path: String, value: Value },
+  Move { from: String, path: String },
+  Copy { from: String, path: String },
+  Test { path: String, value: Value },
+}
+
+/// JSON Patch document
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonPatch {
+  /// Name of the patch document, used in error messages
+  pub name: String,
+  /// Ordered list of operations
+  pub operations: Vec<PatchOperation>,
+}
+
+impl JsonPatch {
+  /// # Parse and validate a JSON Patch document
+  ///
+  /// # Parameters
+  /// * `name` - name of the patch document, used in error messages
+  /// * `json` - json representation of the patch document
+  ///
+  /// # Returns
+  /// * `Ok<JsonPatch>` - when the patch document is valid
+  /// * `Err<String>` - when the patch document could not be parsed or is invalid
+  pub fn from_json<T: Into<String>>(name: T, json: &str) -> Result<Self, String> {
+    let name = name.into();
+    let operations = serde_json::from_str::<Vec<PatchOperation>>(json).map_err(|error| format!("invalid json patch document '{}' ({})", name, error))?;
+    let patch = Self { name, operations };
+    patch.validate()?;
+    Ok(patch)
+  }
+
+  /// # Read, parse and validate a JSON Patch document from a file
+  ///
+  /// # Parameters
+  /// * `file_name` - name of the file that contains the patch document
+  ///
+  /// # Returns
+  /// * `Ok<JsonPatch>` - when the patch document is valid
+  /// * `Err<String>` - when the file could not be read or the patch document is invalid
+  pub fn from_file<P: AsRef<Path>>(file_name: P) -> Result<Self, String> {
+    let name = file_name.as_ref().display().to_string();
+    let json = fs::read_to_string(file_name.as_ref()).map_err(|error| format!("could not read json patch document '{}' ({})", name, error))?;
+    Self::from_json(name, &json)
+  }
+
+  /// # Apply this patch document to a json value
+  ///
+  /// The operations are applied in order and atomically.
+  /// When one of the operations fails, `value` will be left unchanged.
+  ///
+  /// # Parameters
+  /// * `value` - json value that will be patched
+  ///
+  /// # Returns
+  /// * `Ok(())` - when all operations were applied successfully
+  /// * `Err<String>` - when one of the operations could not be applied,
+  ///   the error message contains the name of the patch document and the failing operation
+  pub fn apply(&self, value: &mut Value) -> Result<(), String> {
+    let mut patched_value = value.clone();
+    for (index, operation) in self.operations.iter().enumerate() {
+      apply_operation(&mut patched_value, operation).map_err(|error| {
+        format!(
+          "json patch document '{}' no longer applies, operation {} ({}) failed: {}",
+          self.name, index, operation, error
+        )
+      })?;
+    }
+    *value = patched_value;
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<(), String> {
+    for (index, operation) in self.operations.iter().enumerate() {
+      let validation = match operation {
+        PatchOperation::Add { path, .. } | PatchOperation::Replace { path, .. } | PatchOperation::Test { path, .. } => parse_pointer(path).map(|_| ()),
+        PatchOperation::Remove { path } => match parse_pointer(path)?.is_empty() {
+          true => Err("the root of the document can not be removed".to_string()),
+          false => Ok(()),
+        },
+        PatchOperation::Move { from, path } => {
+          let from_tokens = parse_pointer(from)?;
+          let path_tokens = parse_pointer(path)?;
+          if path_tokens.len() > from_tokens.len() && path_tokens.starts_with(&from_tokens) {
+            Err(format!("'{}' can not be moved into one of its children", from))
+          } else {
+            Ok(())
+          }
+        }
+        PatchOperation::Copy { from, path } => parse_pointer(from).and(parse_pointer(path)).map(|_| ()),
+      };
+      validation.map_err(|error| format!("invalid json patch document '{}', operation {} ({}): {}", self.name, index, operation, error))?;
+    }
+    Ok(())
+  }
+}
+
+impl Display for PatchOperation {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Add { path, .. } => write!(f, "add {}", path),
+      Self::Remove { path } => write!(f, "remove {}", path),
+      Self::Replace { path, .. } => write!(f, "replace {}", path),
+      Self::Move { from, path } => write!(f, "move {} to {}", from, path),
+      Self::Copy { from, path } => write!(f, "copy {} to {}", from, path),
+      Self::Test { path, .. } => write!(f, "test {}", path),
+    }
+  }
+}
+
+/// # Read all JSON Patch documents from a directory
+///
+/// All files with the extension `json` in the directory will be read,
+/// in the lexicographical order of their file names.
+/// When the directory does not exist, an empty list will be returned.
+///
+/// # Parameters
+/// * `directory` - directory that contains the patch documents
+///
+/// # Returns
+/// * `Ok<Vec<JsonPatch>>` - ordered list of patch documents
+/// * `Err<String>` - when the directory could not be read or one of the patch documents is invalid
+pub fn read_patches<P: AsRef<Path>>(directory: P) -> Result<Vec<JsonPatch>, String> {
+  let directory = directory.as_ref();
+  if !directory.exists() {
+    return Ok(vec![]);
+  }
+  let entries = fs::read_dir(directory).map_err(|error| format!("could not read json patch directory '{}' ({})", directory.display(), error))?;
+  let mut file_names = vec![];
+  for entry in entries {
+    let path = entry
+      .map_err(|error| format!("could not read json patch directory '{}' ({})", directory.display(), error))?
+      .path();
+    if path.is_file() && path.extension().is_some_and(|extension| extension == "json") {
+      file_names.push(path);
+    }
+  }
+  file_names.sort();
+  file_names.iter().map(JsonPatch::from_file).collect()
+}
+
+/// # Apply an ordered list of JSON Patch documents to a json value
+///
+/// # Parameters
+/// * `value` - json value that will be patched
+/// * `patches` - ordered list of patch documents
+///
+/// # Returns
+/// * `Ok(())` - when all patch documents were applied successfully
+/// * `Err<String>` - when one of the patch documents could not be applied
+pub fn apply_patches(value: &mut Value, patches: &[JsonPatch]) -> Result<(), String> {
+  for patch in patches {
+    patch.apply(value)?;
+  }
+  Ok(())
+}
+
+fn apply_operation(value: &mut Value, operation: &PatchOperation) -> Result<(), String> {
+  match operation {
+    PatchOperation::Add { path, value: new_value } => add(value, &parse_pointer(path)?, new_value.clone()),
+    PatchOperation::Remove { path } => remove(value, &parse_pointer(path)?).map(|_| ()),
+    PatchOperation::Replace { path, value: new_value } => {
+      *resolve_mut(value, &parse_pointer(path)?)? = new_value.clone();
+      Ok(())
+    }
+    PatchOperation::Move { from, path } => {
+      let from_tokens = parse_pointer(from)?;
+      let path_tokens = parse_pointer(path)?;
+      if from_tokens == path_tokens {
+        return resolve_mut(value, &from_tokens).map(|_| ());
+      }
+      let moved_value = remove(value, &from_tokens)?;
+      add(value, &path_tokens, moved_value)
+    }
+    PatchOperation::Copy { from, path } => {
+      let copied_value = resolve_mut(value, &parse_pointer(from)?)?.clone();
+      add(value, &parse_pointer(path)?, copied_value)
+    }
+    PatchOperation::Test { path, value: expected_value } => {
+      let actual_value = resolve_mut(value, &parse_pointer(path)?)?;
+      if actual_value == expected_value {
+        Ok(())
+      } else {
+        Err(format!("expected value {}, found {}", expected_value, actual_value))
+      }
+    }
+  }
+}
+
+fn add(value: &mut Value, tokens: &[String], new_value: Value) -> Result<(), String> {
+  match tokens.split_last() {
+    None => {
+      *value = new_value;
+      Ok(())
+    }
+    Some((last_token, parent_tokens)) => match resolve_mut(value, parent_tokens)? {
+      Value::Object(object) => {
+        object.insert(last_token.to_string(), new_value);
+        Ok(())
+      }
+      Value::Array(array) => {
+        let index = if last_token == "-" { array.len() } else { parse_index(last_token, array.len() + 1)? };
+        array.insert(index, new_value);
+        Ok(())
+      }
+      _ => Err(format!("parent of '{}' is not an object or an array", to_pointer(tokens))),
+    },
+  }
+}
+
+fn remove(value: &mut Value, tokens: &[String]) -> Result<Value, String> {
+  match tokens.split_last() {
+    None => Err("the root of the document can not be removed".to_string()),
+    Some((last_token, parent_tokens)) => match resolve_mut(value, parent_tokens)? {
+      Value::Object(object) => object.shift_remove(last_token).ok_or_else(|| format!("'{}' does not exist", to_pointer(tokens))),
+      Value::Array(array) => {
+        let index = parse_index(last_token, array.len())?;
+        Ok(array.remove(index))
+      }
+      _ => Err(format!("parent of '{}' is not an object or an array", to_pointer(tokens))),
+    },
+  }
+}
+
+fn resolve_mut<'a>(value: &'a mut Value, tokens: &[String]) -> Result<&'a mut Value, String> {
+  let mut current = value;
+  for (depth, token) in tokens.iter().enumerate() {
+    current = match current {
+      Value::Object(object) => object.get_mut(token),
+      Value::Array(array) => {
+        let length = array.len();
+        array.get_mut(parse_index(token, length)?)
+      }
+      _ => None,
+    }
+    .ok_or_else(|| format!("'{}' does not exist", to_pointer(&tokens[0..=depth])))?;
+  }
+  Ok(current)
+}
+
+fn parse_index(token: &str, length: usize) -> Result<usize, String> {
+  if token.is_empty() || (token.len() > 1 && token.starts_with('0')) || !token.chars().all(|c| c.is_ascii_digit()) {
+    return Err(format!("'{}' is not a valid array index", token));
+  }
+  match token.parse::<usize>() {
+    Ok(index) if index < length => Ok(index),
+    _ => Err(format!("array index {} is out of bounds", token)),
+  }
+}
+
+/// Parse a json pointer ([RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901)) into its reference tokens
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, String> {
+  if pointer.is_empty() {
+    return Ok(vec![]);
+  }
+  match pointer.strip_prefix('/') {
+    Some(stripped_pointer) => stripped_pointer
+      .split('/')
+      .map(|token| {
+        if token.replace("~0", "").replace("~1", "").contains('~') {
+          Err(format!("invalid escape sequence in json pointer '{}'", pointer))
+        } else {
+          Ok(token.replace("~1", "/").replace("~0", "~"))
+        }
+      })
+      .collect(),
+    None => Err(format!("json pointer '{}' does not start with '/'", pointer)),
+  }
+}
+
+fn to_pointer(tokens: &[String]) -> String {
+  tokens.iter().map(|token| format!("/{}", token.replace('~', "~0").replace('/', "~1"))).join("")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn patch(operations: Value) -> JsonPatch {
+    JsonPatch::from_json("test-patch", &operations.to_string()).unwrap()
+  }
+
+  #[test]
+  fn test_add_replace_remove() {
+    let mut value = json!({ "paths": { "/a/b": { "get": { "tags": ["x"] } } } });
+    patch(json!([
+      { "op": "add", "path": "/paths/~1a~1b/get/tags/-", "value": "y" },
+      { "op": "add", "path": "/paths/~1a~1b/get/tags/0", "value": "w" },
+      { "op": "replace", "path": "/paths/~1a~1b/get/tags/1", "value": "z" },
+      { "op": "remove", "path": "/paths/~1a~1b/get/tags/2" },
+      { "op": "add", "path": "/info", "value": { "version": "1" } }
+    ]))
+    .apply(&mut value)
+    .unwrap();
+    assert_eq!(value, json!({ "paths": { "/a/b": { "get": { "tags": ["w", "z"] } } }, "info": { "version": "1" } }));
+  }
+
+  #[test]
+  fn test_move_copy_test() {
+    let mut value = json!({ "a": { "b": 1 }, "c": [] });
+    patch(json!([
+      { "op": "test", "path": "/a/b", "value": 1 },
+      { "op": "copy", "from": "/a/b", "path": "/c/0" },
+      { "op": "move", "from": "/a", "path": "/d" }
+    ]))
+    .apply(&mut value)
+    .unwrap();
+    assert_eq!(value, json!({ "c": [1], "d": { "b": 1 } }));
+  }
+
+  #[test]
+  fn test_failing_patch_is_atomic() {
+    let original_value = json!({ "a": 1 });
+    let mut value = original_value.clone();
+    let error = patch(json!([
+      { "op": "replace", "path": "/a", "value": 2 },
+      { "op": "test", "path": "/a", "value": 3 }
+    ]))
+    .apply(&mut value)
+    .unwrap_err();
+    assert_eq!(value, original_value);
+    assert_eq!(
+      error,
+      "json patch document 'test-patch' no longer applies, operation 1 (test /a) failed: expected value 3, found 2"
+    );
+    let error = patch(json!([{ "op": "remove", "path": "/b/c" }])).apply(&mut value).unwrap_err();
+    assert!(error.ends_with("'/b' does not exist"));
+  }
+
+  #[test]
+  fn test_invalid_patches() {
+    assert!(JsonPatch::from_json("p", r#"[{ "op": "rename", "path": "/a" }]"#).is_err());
+    assert!(JsonPatch::from_json("p", r#"[{ "op": "add", "path": "/a" }]"#).is_err());
+    assert!(JsonPatch::from_json("p", r#"[{ "op": "remove", "path": "a" }]"#).is_err());
+    assert!(JsonPatch::from_json("p", r#"[{ "op": "remove", "path": "/a~2" }]"#).is_err());
+    assert!(JsonPatch::from_json("p", r#"[{ "op": "move", "from": "/a", "path": "/a/b" }]"#).is_err());
+    assert!(JsonPatch::from_json("p", r#"{ "op": "remove", "path": "/a" }"#).is_err());
+  }
+}
@@ -6,17 +6,28 @@
 )]
 //! Helper functions for the build phase of the `dsh_api` crate.
 //!
+//! * Generate fluent builders for the request body types
 //! * Generate the generic client code
+//! * Generate the operations table for the mock server
+//! * Generate the wrapped client code, for the `DshApiClient` or for a configurable client struct
+//! * Generate `Default`, `new` and `Display` implementations for the generated types
 //! * Generate Progenitor client from an openapi specification
+//! * Compare two versions of the openapi specification
 //! * Update the openapi specification
+//! * Apply JSON Patch documents to the openapi specification
 
 use itertools::Itertools;
 use std::fmt::{Display, Formatter};
 
 pub mod dsh_api_operation;
+pub mod generate_builders;
 pub mod generate_client;
 pub mod generate_generic;
+pub mod generate_impls;
+pub mod generate_mock;
 pub mod generate_wrapped;
+pub mod json_patch;
+pub mod openapi_diff;
 pub mod openapi_utils;
 pub mod update_openapi;
 
@@ -135,6 +146,9 @@ impl Display for Method {
 
 pub const MANAGED_PARAMETERS: [&str; 1] = ["Authorization"];
 
+/// Path parameters that are filled in by the `DshApiClient`, when they are the first parameter of an operation
+pub const MANAGED_PATH_PARAMETERS: [&str; 2] = ["manager", "tenant"];
+
 pub fn capitalize<T: AsRef<str>>(string: T) -> String {
   let mut chars = string.as_ref().chars();
   match chars.next() {
@@ -0,0 +1,746 @@
+//! Compare two versions of the openapi specification
+//!
+//! This module compares two openapi specifications at the level of operations and schemas,
+//! and reports the differences as Markdown. The report contains
+//! * the added and removed operations, together with the generated wrapper methods,
+//! * the changed operations, with added, removed and changed parameters,
+//!   changed request bodies and changed responses,
+//! * the added and removed schemas,
+//! * the changed schemas, with added and removed fields, changed field types,
+//!   changed required-ness and changed enumeration values.
+//!
+//! The wrapper method names are derived in the same way as in the
+//! [`generate_wrapped`](crate::generate_wrapped) module, which requires that both specifications
+//! have been updated with [`update_openapi`](crate::update_openapi::update_openapi).
+
+use crate::dsh_api_operation::{method_api_operations, DshApiOperation};
+use crate::generate_wrapped::wrapper_parameter_type;
+use crate::openapi_utils::{method_path_operations, reference_to_string};
+use crate::{Method, RequestBodyType, MANAGED_PARAMETERS, MANAGED_PATH_PARAMETERS, METHODS};
+use itertools::Itertools;
+use openapiv3::{AdditionalProperties, OpenAPI, Operation, Parameter, ParameterSchemaOrContent, ReferenceOr, RequestBody, Response, Schema, SchemaKind, StatusCode, Type};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+
+/// # Differences between two openapi specifications
+///
+/// The `Display` implementation renders the differences as a Markdown document.
+#[derive(Debug, Default)]
+pub struct OpenApiDiff {
+  pub old_version: String,
+  pub new_version: String,
+  pub added_operations: Vec<OperationDescription>,
+  pub removed_operations: Vec<OperationDescription>,
+  pub changed_operations: Vec<OperationChanges>,
+  pub added_schemas: Vec<String>,
+  pub removed_schemas: Vec<String>,
+  pub changed_schemas: Vec<SchemaChanges>,
+}
+
+/// # Operation that was added or removed
+#[derive(Debug)]
+pub struct OperationDescription {
+  /// Upper case http method, e.g. `GET`
+  pub method: String,
+  pub path: String,
+  /// Signature of the generated wrapper method, e.g. `get_topic(id: impl AsRef<str>)`
+  pub wrapper_method: String,
+}
+
+/// # Operation that exists in both specifications, but has changed
+#[derive(Debug)]
+pub struct OperationChanges {
+  /// Upper case http method, e.g. `GET`
+  pub method: String,
+  pub path: String,
+  /// Signature of the generated wrapper method in the new specification
+  pub wrapper_method: String,
+  pub changes: Vec<OperationChange>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum OperationChange {
+  WrapperMethodChanged { old: String, new: String },
+  ParameterAdded { name: String, location: String, parameter_type: String, required: bool },
+  ParameterRemoved { name: String, location: String },
+  ParameterTypeChanged { name: String, old: String, new: String },
+  ParameterRequiredChanged { name: String, required: bool },
+  ParameterEnumChanged { name: String, added: Vec<String>, removed: Vec<String> },
+  RequestBodyChanged { old: String, new: String },
+  ResponseChanged { old: String, new: String },
+}
+
+/// # Schema that exists in both specifications, but has changed
+#[derive(Debug)]
+pub struct SchemaChanges {
+  pub schema: String,
+  pub changes: Vec<SchemaChange>,
+}
+
+/// # Change to a schema
+///
+/// Fields of inline object schemas are identified by their dotted path, e.g. `resources.cpu`.
+/// Enumeration changes with `field` equal to `None` apply to the schema itself.
+#[derive(Debug, PartialEq)]
+pub enum SchemaChange {
+  TypeChanged { old: String, new: String },
+  FieldAdded { name: String, field_type: String, required: bool },
+  FieldRemoved { name: String },
+  FieldTypeChanged { name: String, old: String, new: String },
+  FieldRequiredChanged { name: String, required: bool },
+  EnumChanged { field: Option<String>, added: Vec<String>, removed: Vec<String> },
+}
+
+/// # Compare two openapi specifications
+///
+/// # Parameters
+/// * `old_openapi_spec` - old version of the openapi specification
+/// * `new_openapi_spec` - new version of the openapi specification
+///
+/// # Returns
+/// * `Ok<OpenApiDiff>` - the differences between the two specifications
+/// * `Err` - when the wrapper methods could not be derived from one of the specifications
+pub fn diff_openapi(old_openapi_spec: &OpenAPI, new_openapi_spec: &OpenAPI) -> Result<OpenApiDiff, Box<dyn Error>> {
+  let mut diff = OpenApiDiff { old_version: old_openapi_spec.info.version.clone(), new_version: new_openapi_spec.info.version.clone(), ..Default::default() };
+  let old_wrapper_methods = wrapper_methods(old_openapi_spec)?;
+  let new_wrapper_methods = wrapper_methods(new_openapi_spec)?;
+  let old_operations = operations(old_openapi_spec);
+  let new_operations = operations(new_openapi_spec);
+  for ((path, method), old_operation) in &old_operations {
+    let key = (path.clone(), method.clone());
+    let old_wrapper_method = old_wrapper_methods.get(&key).cloned().unwrap_or_default();
+    match new_operations.get(&key) {
+      Some(new_operation) => {
+        let new_wrapper_method = new_wrapper_methods.get(&key).cloned().unwrap_or_default();
+        let mut changes = vec![];
+        if old_wrapper_method != new_wrapper_method {
+          changes.push(OperationChange::WrapperMethodChanged { old: old_wrapper_method, new: new_wrapper_method.clone() });
+        }
+        changes.append(&mut diff_operation(old_operation, new_operation));
+        if !changes.is_empty() {
+          diff
+            .changed_operations
+            .push(OperationChanges { method: method.to_uppercase(), path: path.clone(), wrapper_method: new_wrapper_method, changes });
+        }
+      }
+      None => diff
+        .removed_operations
+        .push(OperationDescription { method: method.to_uppercase(), path: path.clone(), wrapper_method: old_wrapper_method }),
+    }
+  }
+  for (key, _) in new_operations.iter().filter(|(key, _)| !old_operations.contains_key(*key)) {
+    diff.added_operations.push(OperationDescription {
+      method: key.1.to_uppercase(),
+      path: key.0.clone(),
+      wrapper_method: new_wrapper_methods.get(key).cloned().unwrap_or_default(),
+    });
+  }
+  diff.added_operations.sort_by(|a, b| a.wrapper_method.cmp(&b.wrapper_method));
+  diff.removed_operations.sort_by(|a, b| a.wrapper_method.cmp(&b.wrapper_method));
+  diff.changed_operations.sort_by(|a, b| a.wrapper_method.cmp(&b.wrapper_method));
+  let old_schemas = schemas(old_openapi_spec);
+  let new_schemas = schemas(new_openapi_spec);
+  for (schema_name, old_schema) in &old_schemas {
+    match new_schemas.get(schema_name) {
+      Some(new_schema) => {
+        let changes = diff_schema(old_schema, new_schema);
+        if !changes.is_empty() {
+          diff.changed_schemas.push(SchemaChanges { schema: schema_name.to_string(), changes });
+        }
+      }
+      None => diff.removed_schemas.push(schema_name.to_string()),
+    }
+  }
+  diff.added_schemas = new_schemas
+    .keys()
+    .filter(|schema_name| !old_schemas.contains_key(*schema_name))
+    .map(|schema_name| schema_name.to_string())
+    .collect_vec();
+  Ok(diff)
+}
+
+/// # Write the differences between two openapi specifications as Markdown
+///
+/// # Parameters
+/// * `writer` - the Markdown document will be written to this writer
+/// * `old_openapi_spec` - old version of the openapi specification
+/// * `new_openapi_spec` - new version of the openapi specification
+pub fn generate_openapi_diff(writer: &mut dyn Write, old_openapi_spec: &OpenAPI, new_openapi_spec: &OpenAPI) -> Result<(), Box<dyn Error>> {
+  write!(writer, "{}", diff_openapi(old_openapi_spec, new_openapi_spec)?)?;
+  Ok(())
+}
+
+impl OpenApiDiff {
+  /// # Returns whether the specifications have no differences
+  ///
+  /// Differences in the version number are not taken into account.
+  pub fn is_empty(&self) -> bool {
+    self.added_operations.is_empty()
+      && self.removed_operations.is_empty()
+      && self.changed_operations.is_empty()
+      && self.added_schemas.is_empty()
+      && self.removed_schemas.is_empty()
+      && self.changed_schemas.is_empty()
+  }
+}
+
+// Returns a map from (path, method) to the signature of the wrapper method
+fn wrapper_methods(openapi_spec: &OpenAPI) -> Result<HashMap<(String, String), String>, Box<dyn Error>> {
+  let mut wrapper_methods = HashMap::new();
+  for method in &METHODS {
+    let path_operations: Vec<(&String, &Operation)> = method_path_operations(method, openapi_spec);
+    for operation in method_api_operations(method, &path_operations, &MANAGED_PATH_PARAMETERS)? {
+      wrapper_methods.insert((operation.path.clone(), method.to_string()), wrapper_signature(method, &operation));
+    }
+  }
+  Ok(wrapper_methods)
+}
+
+fn wrapper_signature(method: &Method, operation: &DshApiOperation) -> String {
+  let mut parameters = operation
+    .parameters
+    .iter()
+    .filter(|(parameter_name, _, _)| !MANAGED_PARAMETERS.contains(&parameter_name.as_str()))
+    .map(|(parameter_name, parameter_type, _)| format!("{}: {}", parameter_name.to_lowercase(), wrapper_parameter_type(parameter_type)))
+    .collect_vec();
+  match operation.request_body {
+    Some(RequestBodyType::String) => parameters.push("body: String".to_string()),
+    Some(RequestBodyType::SerializableType(ref serializable_type)) => parameters.push(format!("body: &{}", serializable_type)),
+    None => {}
+  }
+  format!("{}_{}({})", method, operation.selector.to_lowercase().replace('-', "_"), parameters.join(", "))
+}
+
+// Returns all operations, ordered by path and method
+fn operations(openapi_spec: &OpenAPI) -> BTreeMap<(String, String), &Operation> {
+  let mut operations = BTreeMap::new();
+  for (path, path_item) in openapi_spec.paths.iter() {
+    if let ReferenceOr::Item(path_item) = path_item {
+      for (method, operation) in path_item.iter() {
+        operations.insert((path.to_string(), method.to_string()), operation);
+      }
+    }
+  }
+  operations
+}
+
+// Returns all schemas, ordered by name
+fn schemas(openapi_spec: &OpenAPI) -> BTreeMap<&str, &ReferenceOr<Schema>> {
+  match openapi_spec.components {
+    Some(ref components) => components.schemas.iter().map(|(schema_name, schema)| (schema_name.as_str(), schema)).collect(),
+    None => BTreeMap::new(),
+  }
+}
+
+struct ParameterDescription {
+  location: &'static str,
+  parameter_type: String,
+  required: bool,
+  enumeration: Vec<String>,
+}
+
+fn diff_operation(old_operation: &Operation, new_operation: &Operation) -> Vec<OperationChange> {
+  let mut changes = vec![];
+  let old_parameters = parameters(old_operation);
+  let new_parameters = parameters(new_operation);
+  for (name, old_parameter) in &old_parameters {
+    match new_parameters.get(name) {
+      Some(new_parameter) => {
+        if old_parameter.parameter_type != new_parameter.parameter_type {
+          changes.push(OperationChange::ParameterTypeChanged { name: name.clone(), old: old_parameter.parameter_type.clone(), new: new_parameter.parameter_type.clone() });
+        }
+        if old_parameter.required != new_parameter.required {
+          changes.push(OperationChange::ParameterRequiredChanged { name: name.clone(), required: new_parameter.required });
+        }
+        let (added, removed) = diff_enumeration(&old_parameter.enumeration, &new_parameter.enumeration);
+        if !added.is_empty() || !removed.is_empty() {
+          changes.push(OperationChange::ParameterEnumChanged { name: name.clone(), added, removed });
+        }
+      }
+      None => changes.push(OperationChange::ParameterRemoved { name: name.clone(), location: old_parameter.location.to_string() }),
+    }
+  }
+  for (name, new_parameter) in new_parameters.iter().filter(|(name, _)| !old_parameters.contains_key(*name)) {
+    changes.push(OperationChange::ParameterAdded {
+      name: name.clone(),
+      location: new_parameter.location.to_string(),
+      parameter_type: new_parameter.parameter_type.clone(),
+      required: new_parameter.required,
+    });
+  }
+  let (old_request_body, new_request_body) = (request_body_type(old_operation), request_body_type(new_operation));
+  if old_request_body != new_request_body {
+    changes.push(OperationChange::RequestBodyChanged { old: old_request_body, new: new_request_body });
+  }
+  let (old_response, new_response) = (response_type(old_operation), response_type(new_operation));
+  if old_response != new_response {
+    changes.push(OperationChange::ResponseChanged { old: old_response, new: new_response });
+  }
+  changes
+}
+
+// Returns the parameters of an operation, ordered by name, without the managed parameters
+fn parameters(operation: &Operation) -> BTreeMap<String, ParameterDescription> {
+  operation
+    .parameters
+    .iter()
+    .filter_map(|parameter| match parameter {
+      ReferenceOr::Item(parameter) => Some(parameter),
+      ReferenceOr::Reference { .. } => None,
+    })
+    .filter(|parameter| !MANAGED_PARAMETERS.contains(&parameter.parameter_data_ref().name.as_str()))
+    .map(|parameter| {
+      let location = match parameter {
+        Parameter::Query { .. } => "query",
+        Parameter::Header { .. } => "header",
+        Parameter::Path { .. } => "path",
+        Parameter::Cookie { .. } => "cookie",
+      };
+      let parameter_data = parameter.parameter_data_ref();
+      let (parameter_type, enumeration) = match parameter_data.format {
+        ParameterSchemaOrContent::Schema(ref schema) => (schema_type(schema), schema_enumeration(schema)),
+        ParameterSchemaOrContent::Content(_) => ("content".to_string(), vec![]),
+      };
+      (
+        parameter_data.name.clone(),
+        ParameterDescription { location, parameter_type, required: parameter_data.required, enumeration },
+      )
+    })
+    .collect()
+}
+
+fn request_body_type(operation: &Operation) -> String {
+  match operation.request_body {
+    Some(ReferenceOr::Item(ref request_body)) => content_type(request_body),
+    Some(ReferenceOr::Reference { ref reference }) => reference_to_string(reference),
+    None => "none".to_string(),
+  }
+}
+
+fn content_type(request_body: &RequestBody) -> String {
+  media_types_type(
+    request_body
+      .content
+      .iter()
+      .map(|(media_type_name, media_type)| (media_type_name.as_str(), media_type.schema.as_ref())),
+  )
+}
+
+// Returns the type of the successful response with the lowest status code
+fn response_type(operation: &Operation) -> String {
+  let response = operation
+    .responses
+    .responses
+    .iter()
+    .filter_map(|(status_code, response)| match status_code {
+      StatusCode::Code(code @ 200..=299) => Some((code, response)),
+      _ => None,
+    })
+    .min_by_key(|(code, _)| **code);
+  match response {
+    Some((code, ReferenceOr::Item(response))) => format!("{} {}", code, response_content_type(response)),
+    Some((code, ReferenceOr::Reference { reference })) => format!("{} {}", code, reference_to_string(reference)),
+    None => "none".to_string(),
+  }
+}
+
+fn response_content_type(response: &Response) -> String {
+  media_types_type(
+    response
+      .content
+      .iter()
+      .map(|(media_type_name, media_type)| (media_type_name.as_str(), media_type.schema.as_ref())),
+  )
+}
+
+fn media_types_type<'a>(media_types: impl Iterator<Item = (&'a str, Option<&'a ReferenceOr<Schema>>)>) -> String {
+  let media_types = media_types
+    .map(|(media_type_name, schema)| match (media_type_name, schema) {
+      ("application/json", Some(schema)) => schema_type(schema),
+      (media_type_name, _) => media_type_name.to_string(),
+    })
+    .collect_vec();
+  if media_types.is_empty() {
+    "empty".to_string()
+  } else {
+    media_types.join(" | ")
+  }
+}
+
+struct FieldDescription {
+  field_type: String,
+  required: bool,
+  enumeration: Vec<String>,
+}
+
+fn diff_schema(old_schema: &ReferenceOr<Schema>, new_schema: &ReferenceOr<Schema>) -> Vec<SchemaChange> {
+  let mut changes = vec![];
+  let (old_type, new_type) = (schema_type(old_schema), schema_type(new_schema));
+  if old_type != new_type {
+    changes.push(SchemaChange::TypeChanged { old: old_type, new: new_type });
+  }
+  let (added, removed) = diff_enumeration(&schema_enumeration(old_schema), &schema_enumeration(new_schema));
+  if !added.is_empty() || !removed.is_empty() {
+    changes.push(SchemaChange::EnumChanged { field: None, added, removed });
+  }
+  let old_fields = fields(old_schema);
+  let new_fields = fields(new_schema);
+  for (name, old_field) in &old_fields {
+    match new_fields.get(name) {
+      Some(new_field) => {
+        if old_field.field_type != new_field.field_type {
+          changes.push(SchemaChange::FieldTypeChanged { name: name.clone(), old: old_field.field_type.clone(), new: new_field.field_type.clone() });
+        }
+        if old_field.required != new_field.required {
+          changes.push(SchemaChange::FieldRequiredChanged { name: name.clone(), required: new_field.required });
+        }
+        let (added, removed) = diff_enumeration(&old_field.enumeration, &new_field.enumeration);
+        if !added.is_empty() || !removed.is_empty() {
+          changes.push(SchemaChange::EnumChanged { field: Some(name.clone()), added, removed });
+        }
+      }
+      None => changes.push(SchemaChange::FieldRemoved { name: name.clone() }),
+    }
+  }
+  for (name, new_field) in new_fields.iter().filter(|(name, _)| !old_fields.contains_key(*name)) {
+    changes.push(SchemaChange::FieldAdded { name: name.clone(), field_type: new_field.field_type.clone(), required: new_field.required });
+  }
+  changes
+}
+
+// Returns the fields of an object schema, including the fields of inline object schemas
+fn fields(schema: &ReferenceOr<Schema>) -> BTreeMap<String, FieldDescription> {
+  let mut fields = BTreeMap::new();
+  if let ReferenceOr::Item(schema) = schema {
+    add_fields(&mut fields, "", schema);
+  }
+  fields
+}
+
+fn add_fields(fields: &mut BTreeMap<String, FieldDescription>, prefix: &str, schema: &Schema) {
+  match schema.schema_kind {
+    SchemaKind::Type(Type::Object(ref object_type)) => {
+      for (property_name, property_schema) in &object_type.properties {
+        let name = format!("{}{}", prefix, property_name);
+        let required = object_type.required.contains(property_name);
+        match property_schema {
+          ReferenceOr::Reference { reference } => {
+            fields.insert(name, FieldDescription { field_type: reference_to_string(reference), required, enumeration: vec![] });
+          }
+          ReferenceOr::Item(property_schema) => {
+            fields.insert(
+              name.clone(),
+              FieldDescription { field_type: schema_kind_type(&property_schema.schema_kind), required, enumeration: enumeration(&property_schema.schema_kind) },
+            );
+            add_fields(fields, &format!("{}.", name), property_schema);
+          }
+        }
+      }
+    }
+    SchemaKind::AllOf { ref all_of } => {
+      for item in all_of {
+        if let ReferenceOr::Item(item) = item {
+          add_fields(fields, prefix, item);
+        }
+      }
+    }
+    _ => {}
+  }
+}
+
+fn diff_enumeration(old_enumeration: &[String], new_enumeration: &[String]) -> (Vec<String>, Vec<String>) {
+  let added = new_enumeration.iter().filter(|value| !old_enumeration.contains(value)).cloned().collect_vec();
+  let removed = old_enumeration.iter().filter(|value| !new_enumeration.contains(value)).cloned().collect_vec();
+  (added, removed)
+}
+
+fn schema_enumeration(schema: &ReferenceOr<Schema>) -> Vec<String> {
+  match schema {
+    ReferenceOr::Reference { .. } => vec![],
+    ReferenceOr::Item(schema) => enumeration(&schema.schema_kind),
+  }
+}
+
+fn enumeration(schema_kind: &SchemaKind) -> Vec<String> {
+  match schema_kind {
+    SchemaKind::Type(Type::String(string_type)) => string_type.enumeration.iter().flatten().cloned().collect_vec(),
+    SchemaKind::Type(Type::Integer(integer_type)) => integer_type.enumeration.iter().flatten().map(|value| value.to_string()).collect_vec(),
+    SchemaKind::Type(Type::Number(number_type)) => number_type.enumeration.iter().flatten().map(|value| value.to_string()).collect_vec(),
+    _ => vec![],
+  }
+}
+
+// Returns a short description of the type of a schema, e.g. `string`, `array<Topic>` or `map<string>`
+fn schema_type(schema: &ReferenceOr<Schema>) -> String {
+  match schema {
+    ReferenceOr::Reference { reference } => reference_to_string(reference),
+    ReferenceOr::Item(schema) => schema_kind_type(&schema.schema_kind),
+  }
+}
+
+fn boxed_schema_type(schema: &ReferenceOr<Box<Schema>>) -> String {
+  match schema {
+    ReferenceOr::Reference { reference } => reference_to_string(reference),
+    ReferenceOr::Item(schema) => schema_kind_type(&schema.schema_kind),
+  }
+}
+
+fn schema_kind_type(schema_kind: &SchemaKind) -> String {
+  match schema_kind {
+    SchemaKind::Type(Type::String(string_type)) => with_format("string", &serde_json::to_value(&string_type.format).unwrap_or_default()),
+    SchemaKind::Type(Type::Integer(integer_type)) => with_format("integer", &serde_json::to_value(&integer_type.format).unwrap_or_default()),
+    SchemaKind::Type(Type::Number(number_type)) => with_format("number", &serde_json::to_value(&number_type.format).unwrap_or_default()),
+    SchemaKind::Type(Type::Boolean(_)) => "boolean".to_string(),
+    SchemaKind::Type(Type::Array(array_type)) => match array_type.items {
+      Some(ref items) => format!("array<{}>", boxed_schema_type(items)),
+      None => "array".to_string(),
+    },
+    SchemaKind::Type(Type::Object(object_type)) => match object_type.additional_properties {
+      Some(AdditionalProperties::Schema(ref schema)) => format!("map<{}>", schema_type(schema)),
+      Some(AdditionalProperties::Any(true)) => "map<any>".to_string(),
+      _ => "object".to_string(),
+    },
+    SchemaKind::OneOf { one_of } => format!("oneOf<{}>", one_of.iter().map(schema_type).join(", ")),
+    SchemaKind::AllOf { all_of } => format!("allOf<{}>", all_of.iter().map(schema_type).join(", ")),
+    SchemaKind::AnyOf { any_of } => format!("anyOf<{}>", any_of.iter().map(schema_type).join(", ")),
+    SchemaKind::Not { not } => format!("not<{}>", schema_type(not)),
+    SchemaKind::Any(_) => "any".to_string(),
+  }
+}
+
+fn with_format(type_name: &str, format: &serde_json::Value) -> String {
+  match format.as_str() {
+    Some(format) if !format.is_empty() => format!("{} ({})", type_name, format),
+    _ => type_name.to_string(),
+  }
+}
+
+fn code_list(values: &[String]) -> String {
+  values.iter().map(|value| format!("`{}`", value)).join(", ")
+}
+
+impl Display for OperationChange {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::WrapperMethodChanged { old, new } => write!(f, "wrapper method changed from `{}` to `{}`", old, new),
+      Self::ParameterAdded { name, location, parameter_type, required } => {
+        write!(
+          f,
+          "{} parameter `{}` added (`{}`, {})",
+          location,
+          name,
+          parameter_type,
+          if *required { "required" } else { "optional" }
+        )
+      }
+      Self::ParameterRemoved { name, location } => write!(f, "{} parameter `{}` removed", location, name),
+      Self::ParameterTypeChanged { name, old, new } => write!(f, "type of parameter `{}` changed from `{}` to `{}`", name, old, new),
+      Self::ParameterRequiredChanged { name, required } => write!(f, "parameter `{}` is now {}", name, if *required { "required" } else { "optional" }),
+      Self::ParameterEnumChanged { name, added, removed } => write!(f, "values of parameter `{}` changed{}", name, enumeration_changes(added, removed)),
+      Self::RequestBodyChanged { old, new } => write!(f, "request body changed from `{}` to `{}`", old, new),
+      Self::ResponseChanged { old, new } => write!(f, "response changed from `{}` to `{}`", old, new),
+    }
+  }
+}
+
+impl Display for SchemaChange {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::TypeChanged { old, new } => write!(f, "type changed from `{}` to `{}`", old, new),
+      Self::FieldAdded { name, field_type, required } => write!(f, "field `{}` added (`{}`, {})", name, field_type, if *required { "required" } else { "optional" }),
+      Self::FieldRemoved { name } => write!(f, "field `{}` removed", name),
+      Self::FieldTypeChanged { name, old, new } => write!(f, "type of field `{}` changed from `{}` to `{}`", name, old, new),
+      Self::FieldRequiredChanged { name, required } => write!(f, "field `{}` is now {}", name, if *required { "required" } else { "optional" }),
+      Self::EnumChanged { field: Some(field), added, removed } => write!(f, "values of field `{}` changed{}", field, enumeration_changes(added, removed)),
+      Self::EnumChanged { field: None, added, removed } => write!(f, "values changed{}", enumeration_changes(added, removed)),
+    }
+  }
+}
+
+fn enumeration_changes(added: &[String], removed: &[String]) -> String {
+  let mut changes = String::new();
+  if !added.is_empty() {
+    changes.push_str(&format!(", added {}", code_list(added)));
+  }
+  if !removed.is_empty() {
+    changes.push_str(&format!(", removed {}", code_list(removed)));
+  }
+  changes
+}
+
+impl Display for OpenApiDiff {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    writeln!(f, "# Changes from openapi specification {} to {}", self.old_version, self.new_version)?;
+    if self.is_empty() {
+      writeln!(f)?;
+      writeln!(f, "No changes.")?;
+      return Ok(());
+    }
+    for (title, operations) in [("Added operations", &self.added_operations), ("Removed operations", &self.removed_operations)] {
+      if !operations.is_empty() {
+        writeln!(f)?;
+        writeln!(f, "## {}", title)?;
+        writeln!(f)?;
+        for operation in operations {
+          writeln!(f, "* `{}` - `{} {}`", operation.wrapper_method, operation.method, operation.path)?;
+        }
+      }
+    }
+    if !self.changed_operations.is_empty() {
+      writeln!(f)?;
+      writeln!(f, "## Changed operations")?;
+      for operation in &self.changed_operations {
+        writeln!(f)?;
+        writeln!(f, "### `{}` - `{} {}`", operation.wrapper_method, operation.method, operation.path)?;
+        writeln!(f)?;
+        for change in &operation.changes {
+          writeln!(f, "* {}", change)?;
+        }
+      }
+    }
+    for (title, schemas) in [("Added schemas", &self.added_schemas), ("Removed schemas", &self.removed_schemas)] {
+      if !schemas.is_empty() {
+        writeln!(f)?;
+        writeln!(f, "## {}", title)?;
+        writeln!(f)?;
+        for schema in schemas {
+          writeln!(f, "* `{}`", schema)?;
+        }
+      }
+    }
+    if !self.changed_schemas.is_empty() {
+      writeln!(f)?;
+      writeln!(f, "## Changed schemas")?;
+      for schema in &self.changed_schemas {
+        writeln!(f)?;
+        writeln!(f, "### `{}`", schema.schema)?;
+        writeln!(f)?;
+        for change in &schema.changes {
+          writeln!(f, "* {}", change)?;
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::update_openapi::update_openapi;
+  use serde_json::{json, Value};
+
+  fn openapi_spec(version: &str, paths: Value, schemas: Value) -> OpenAPI {
+    let mut openapi_spec: OpenAPI = serde_json::from_value(json!({
+      "openapi": "3.0.1",
+      "info": { "title": "test", "version": version },
+      "paths": paths,
+      "components": { "schemas": schemas }
+    }))
+    .unwrap();
+    update_openapi(&mut openapi_spec, &[], false, false).unwrap();
+    openapi_spec
+  }
+
+  fn get_operation(schema_name: &str) -> Value {
+    json!({ "get": {
+      "parameters": [
+        { "name": "tenant", "in": "path", "required": true, "schema": { "type": "string" } },
+        { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }
+      ],
+      "responses": { "200": { "description": "ok", "content": { "application/json": { "schema": { "$ref": format!("#/components/schemas/{}", schema_name) } } } } }
+    }})
+  }
+
+  #[test]
+  fn test_diff_openapi() {
+    let old_openapi_spec = openapi_spec(
+      "1.0.0",
+      json!({
+        "/allocation/{tenant}/topic/{id}/configuration": get_operation("Topic"),
+        "/allocation/{tenant}/volume/{id}/configuration": get_operation("Volume")
+      }),
+      json!({
+        "Topic": { "type": "object", "required": ["partitions"], "properties": {
+          "partitions": { "type": "integer" },
+          "kind": { "type": "string", "enum": ["a", "b"] },
+          "obsolete": { "type": "string" }
+        }},
+        "Volume": { "type": "object", "properties": { "size": { "type": "integer" } } }
+      }),
+    );
+    let new_openapi_spec = openapi_spec(
+      "2.0.0",
+      json!({
+        "/allocation/{tenant}/topic/{id}/configuration": get_operation("Topic"),
+        "/allocation/{tenant}/bucket/{id}/configuration": get_operation("Bucket")
+      }),
+      json!({
+        "Topic": { "type": "object", "required": ["kind"], "properties": {
+          "partitions": { "type": "integer" },
+          "kind": { "type": "string", "enum": ["a", "c"] },
+          "replication": { "type": "integer", "format": "int64" }
+        }},
+        "Bucket": { "type": "object", "properties": { "encrypted": { "type": "boolean" } } }
+      }),
+    );
+    let diff = diff_openapi(&old_openapi_spec, &new_openapi_spec).unwrap();
+    assert_eq!(diff.added_operations.len(), 1);
+    assert_eq!(diff.added_operations[0].wrapper_method, "get_bucket_configuration(id: impl AsRef<str>)");
+    assert_eq!(diff.removed_operations[0].wrapper_method, "get_volume_configuration(id: impl AsRef<str>)");
+    assert!(diff.changed_operations.is_empty());
+    assert_eq!(diff.added_schemas, vec!["Bucket"]);
+    assert_eq!(diff.removed_schemas, vec!["Volume"]);
+    assert_eq!(diff.changed_schemas[0].schema, "Topic");
+    assert_eq!(
+      diff.changed_schemas[0].changes,
+      vec![
+        SchemaChange::FieldRequiredChanged { name: "kind".to_string(), required: true },
+        SchemaChange::EnumChanged { field: Some("kind".to_string()), added: vec!["c".to_string()], removed: vec!["b".to_string()] },
+        SchemaChange::FieldRemoved { name: "obsolete".to_string() },
+        SchemaChange::FieldRequiredChanged { name: "partitions".to_string(), required: false },
+        SchemaChange::FieldAdded { name: "replication".to_string(), field_type: "integer (int64)".to_string(), required: false },
+      ]
+    );
+    let markdown = diff.to_string();
+    assert!(markdown.starts_with("# Changes from openapi specification 1.0.0 to 2.0.0\n"));
+    assert!(markdown.contains("## Added operations\n\n* `get_bucket_configuration(id: impl AsRef<str>)` - `GET /allocation/{tenant}/bucket/{id}/configuration`\n"));
+    assert!(markdown.contains("### `Topic`\n\n* field `kind` is now required\n* values of field `kind` changed, added `c`, removed `b`\n"));
+  }
+
+  #[test]
+  fn test_diff_operation_parameters() {
+    let old_openapi_spec = openapi_spec(
+      "1.0.0",
+      json!({ "/allocation/{tenant}/topic/{id}/configuration": get_operation("Topic") }),
+      json!({ "Topic": { "type": "object" } }),
+    );
+    let mut operation = get_operation("Topic");
+    operation["get"]["parameters"]
+      .as_array_mut()
+      .unwrap()
+      .push(json!({ "name": "kind", "in": "query", "required": false, "schema": { "type": "string" } }));
+    operation["get"]["responses"] = json!({ "200": { "description": "ok", "content": { "text/plain": {} } } });
+    let new_openapi_spec = openapi_spec(
+      "1.0.1",
+      json!({ "/allocation/{tenant}/topic/{id}/configuration": operation }),
+      json!({ "Topic": { "type": "object" } }),
+    );
+    let diff = diff_openapi(&old_openapi_spec, &new_openapi_spec).unwrap();
+    assert_eq!(diff.changed_operations.len(), 1);
+    assert_eq!(
+      diff.changed_operations[0].changes,
+      vec![
+        OperationChange::WrapperMethodChanged {
+          old: "get_topic_configuration(id: impl AsRef<str>)".to_string(),
+          new: "get_topic_configuration(id: impl AsRef<str>, kind: impl AsRef<str>)".to_string()
+        },
+        OperationChange::ParameterAdded { name: "kind".to_string(), location: "query".to_string(), parameter_type: "string".to_string(), required: false },
+        OperationChange::ResponseChanged { old: "200 Topic".to_string(), new: "200 text/plain".to_string() },
+      ]
+    );
+    assert!(diff_openapi(&old_openapi_spec, &old_openapi_spec).unwrap().is_empty());
+  }
+}
@@ -8,6 +8,8 @@ pub enum OpenApiOperationKind {
   AppCatalog,
   Manage,
   Robot,
+  /// Operations from specifications other than the DSH specification
+  Other(String),
 }
 
 impl From<&str> for OpenApiOperationKind {
@@ -17,9 +19,7 @@ impl From<&str> for OpenApiOperationKind {
       "manage" => Self::Manage,
       "appcatalog" => Self::AppCatalog,
       "robot" => Self::Robot,
-      _ => {
-        panic!("unrecognized operation kind '{}'", kind)
-      }
+      other => Self::Other(other.to_string()),
     }
   }
 }
@@ -31,6 +31,7 @@ impl Display for OpenApiOperationKind {
       Self::Manage => write!(f, "manage"),
       Self::AppCatalog => write!(f, "appcatalog"),
       Self::Robot => write!(f, "robot"),
+      Self::Other(other) => write!(f, "{}", other),
     }
   }
 }
@@ -1,16 +1,20 @@
 //! Update the openapi specification
 //!
 //! This method will make the following (in place) updates to an `OpenApi` object:
+//! * Apply an ordered list of [JSON Patch](crate::json_patch) documents, to fix bugs in the specification
 //! * Add authorization header to each operation
 //! * Add operation id to each operation
 //! * Add a description
 
+use crate::json_patch::{apply_patches, JsonPatch};
 use crate::openapi_utils::OpenApiOperationKind;
 use crate::PathElement;
 use itertools::Itertools;
 use openapiv3::{OpenAPI, Operation, Parameter, ParameterData, ParameterSchemaOrContent, ReferenceOr};
 
-pub fn update_openapi(original_openapi_spec: &mut OpenAPI, prune_manage: bool, prune_robot: bool) -> Result<(), String> {
+pub fn update_openapi(original_openapi_spec: &mut OpenAPI, patches: &[JsonPatch], prune_manage: bool, prune_robot: bool) -> Result<(), String> {
+  // Apply the json patch documents, in order, to the original openapi spec
+  patch_openapi(original_openapi_spec, patches)?;
   // If feature manage is not enabled, prune all /manage/... paths
   if prune_manage {
     prune_paths(original_openapi_spec, |path| path.starts_with("/manage/"))?;
@@ -28,6 +32,16 @@ pub fn update_openapi(original_openapi_spec: &mut OpenAPI, prune_manage: bool, p
   Ok(())
 }
 
+fn patch_openapi(openapi: &mut OpenAPI, patches: &[JsonPatch]) -> Result<(), String> {
+  if patches.is_empty() {
+    return Ok(());
+  }
+  let mut openapi_value = serde_json::to_value(&*openapi).map_err(|error| format!("could not convert openapi specification to json ({})", error))?;
+  apply_patches(&mut openapi_value, patches)?;
+  *openapi = serde_json::from_value(openapi_value).map_err(|error| format!("patched openapi specification is not valid ({})", error))?;
+  Ok(())
+}
+
 fn prune_paths(openapi: &mut OpenAPI, predicate: fn(&str) -> bool) -> Result<(), String> {
   let paths = openapi.paths.paths.keys().map(|path| path.to_string()).collect_vec();
   for path in paths {
@@ -1,6 +1,10 @@
+use dsh_api_build_helpers::generate_builders::generate_builders;
 use dsh_api_build_helpers::generate_client::generate_client;
 use dsh_api_build_helpers::generate_generic::generate_generic;
+use dsh_api_build_helpers::generate_impls::{generate_default_impls, generate_display_impls, generate_new_functions, GeneratedTypes, HandWrittenImpls};
+use dsh_api_build_helpers::generate_mock::generate_mock_operations;
 use dsh_api_build_helpers::generate_wrapped::generate_wrapped;
+use dsh_api_build_helpers::json_patch::read_patches;
 use dsh_api_build_helpers::update_openapi::update_openapi;
 use openapiv3::OpenAPI;
 use std::error::Error;
@@ -8,19 +12,83 @@ use std::fs;
 use std::fs::File;
 use std::io::BufWriter;
 
+/// Environment variable that overrides the openapi specification version selected by the features
+const OPENAPI_VERSION_ENV_VAR: &str = "DSH_API_OPENAPI_VERSION";
+
+/// Source files with the hand-written `Default`, `new` and `Display` implementations
+const DEFAULT_SOURCE_FILE_NAME: &str = "src/default.rs";
+const NEW_SOURCE_FILE_NAME: &str = "src/new.rs";
+const DISPLAY_SOURCE_FILE_NAME: &str = "src/display.rs";
+
+/// Supported openapi specification version
+struct OpenapiVersion {
+  /// Cargo feature that selects this version
+  feature: &'static str,
+  /// Full version number, as it appears in the openapi specification
+  version: &'static str,
+  /// Short version number, accepted as alias for the full version number
+  alias: &'static str,
+  /// Openapi specification file
+  file_name: &'static str,
+  /// Directory with the json patch documents that will be applied to the openapi specification
+  patches_directory: &'static str,
+  /// Name of the `cfg` flag that will be set when this version is selected
+  cfg_flag: &'static str,
+}
+
+/// Supported openapi specification versions, ordered from newest to oldest
+const OPENAPI_VERSIONS: [OpenapiVersion; 3] = [
+  OpenapiVersion {
+    feature: "api-1-10",
+    version: "1.10.0",
+    alias: "1.10",
+    file_name: "openapi_spec/openapi_1_10_0.json",
+    patches_directory: "openapi_spec/patches/openapi_1_10_0",
+    cfg_flag: "dsh_api_1_10",
+  },
+  OpenapiVersion {
+    feature: "api-1-9",
+    version: "1.9.2",
+    alias: "1.9",
+    file_name: "openapi_spec/openapi_1_9_2.json",
+    patches_directory: "openapi_spec/patches/openapi_1_9_2",
+    cfg_flag: "dsh_api_1_9",
+  },
+  OpenapiVersion {
+    feature: "api-1-8",
+    version: "1.8.0",
+    alias: "1.8",
+    file_name: "openapi_spec/openapi_1_8_0.json",
+    patches_directory: "openapi_spec/patches/openapi_1_8_0",
+    cfg_flag: "dsh_api_1_8",
+  },
+];
+
 fn main() -> Result<(), Box<dyn Error>> {
   let out_dir = std::env::var("OUT_DIR").unwrap();
 
+  // Select the openapi specification version
+  println!("cargo:rerun-if-env-changed={}", OPENAPI_VERSION_ENV_VAR);
+  let openapi_version = select_openapi_version()?;
+  for supported_version in &OPENAPI_VERSIONS {
+    println!("cargo::rustc-check-cfg=cfg({})", supported_version.cfg_flag);
+  }
+  println!("cargo:rustc-cfg={}", openapi_version.cfg_flag);
+
   // Read the openapi specification
-  let original_openapi_spec_file_name = "openapi_spec/openapi_1_10_0.json";
+  let original_openapi_spec_file_name = openapi_version.file_name;
   println!("cargo:rerun-if-changed={}", original_openapi_spec_file_name);
   let original_openapi_spec_file = File::open(original_openapi_spec_file_name).unwrap();
   let mut openapi_spec: OpenAPI = serde_json::from_reader(original_openapi_spec_file).unwrap();
 
-  // Update openapi specification, add authorization and operation ids
+  // Read the json patch documents that fix bugs in the openapi specification
+  println!("cargo:rerun-if-changed={}", openapi_version.patches_directory);
+  let patches = read_patches(openapi_version.patches_directory)?;
+
+  // Update openapi specification, apply patches, add authorization and operation ids
   let prune_manage = std::env::var("CARGO_FEATURE_MANAGE").is_err();
   let prune_robot = std::env::var("CARGO_FEATURE_ROBOT").is_err();
-  update_openapi(&mut openapi_spec, prune_manage, prune_robot)?;
+  update_openapi(&mut openapi_spec, &patches, prune_manage, prune_robot)?;
 
   // Make updated openapi spec available to the crate code
   let updated_openapi_spec_json = serde_json::to_string_pretty(&openapi_spec).unwrap();
@@ -31,8 +99,39 @@ fn main() -> Result<(), Box<dyn Error>> {
   // Build Progenitor client code
   let mut generated_progenitor_client_file_name = std::path::Path::new(&out_dir).to_path_buf();
   generated_progenitor_client_file_name.push("progenitor_client.rs");
-  let mut writer: BufWriter<File> = BufWriter::new(File::create(generated_progenitor_client_file_name).unwrap());
+  let mut writer: BufWriter<File> = BufWriter::new(File::create(&generated_progenitor_client_file_name).unwrap());
   generate_client(&mut writer, &updated_openapi_spec_json)?;
+  drop(writer);
+
+  // Create Default, new and Display implementations for the generated types,
+  // except for the types that have a hand-written implementation
+  let generated_types = GeneratedTypes::from_generated_code(&fs::read_to_string(&generated_progenitor_client_file_name)?)?;
+  println!("cargo:rerun-if-changed={}", DEFAULT_SOURCE_FILE_NAME);
+  println!("cargo:rerun-if-changed={}", NEW_SOURCE_FILE_NAME);
+  println!("cargo:rerun-if-changed={}", DISPLAY_SOURCE_FILE_NAME);
+  let hand_written_impls = HandWrittenImpls::from_sources(
+    &fs::read_to_string(DEFAULT_SOURCE_FILE_NAME)?,
+    &fs::read_to_string(NEW_SOURCE_FILE_NAME)?,
+    &fs::read_to_string(DISPLAY_SOURCE_FILE_NAME)?,
+  )?;
+  let mut generated_default_file_name = std::path::Path::new(&out_dir).to_path_buf();
+  generated_default_file_name.push("generated_default.rs");
+  let mut writer: BufWriter<File> = BufWriter::new(File::create(generated_default_file_name).unwrap());
+  generate_default_impls(&mut writer, &generated_types, &hand_written_impls)?;
+  let mut generated_new_file_name = std::path::Path::new(&out_dir).to_path_buf();
+  generated_new_file_name.push("generated_new.rs");
+  let mut writer: BufWriter<File> = BufWriter::new(File::create(generated_new_file_name).unwrap());
+  generate_new_functions(&mut writer, &generated_types, &hand_written_impls)?;
+  let mut generated_display_file_name = std::path::Path::new(&out_dir).to_path_buf();
+  generated_display_file_name.push("generated_display.rs");
+  let mut writer: BufWriter<File> = BufWriter::new(File::create(generated_display_file_name).unwrap());
+  generate_display_impls(&mut writer, &generated_types, &hand_written_impls)?;
+
+  // Create builders for the request body types
+  let mut builders_file_name = std::path::Path::new(&out_dir).to_path_buf();
+  builders_file_name.push("builders.rs");
+  let mut writer: BufWriter<File> = BufWriter::new(File::create(builders_file_name).unwrap());
+  generate_builders(&mut writer, &openapi_spec, &generated_types)?;
 
   // Create wrapped client code
   let mut wrapped_client_file_name = std::path::Path::new(&out_dir).to_path_buf();
@@ -48,5 +147,49 @@ fn main() -> Result<(), Box<dyn Error>> {
     generate_generic(&mut writer, &openapi_spec)?;
   }
 
+  // If enabled, create the operations table for the mock server
+  if std::env::var("CARGO_FEATURE_MOCK").is_ok() {
+    let mut mock_operations_file_name = std::path::Path::new(&out_dir).to_path_buf();
+    mock_operations_file_name.push("mock_operations.rs");
+    let mut writer: BufWriter<File> = BufWriter::new(File::create(mock_operations_file_name).unwrap());
+    generate_mock_operations(&mut writer, &openapi_spec)?;
+  }
+
   Ok(())
 }
+
+/// Selects the openapi specification version
+///
+/// When the environment variable `DSH_API_OPENAPI_VERSION` is set, its value determines
+/// the selected version. Else the version is selected by the enabled `api-*` features.
+/// When more than one of these features is enabled, the newest version will be selected.
+/// When none of the features is enabled, the newest version is the default.
+fn select_openapi_version() -> Result<&'static OpenapiVersion, Box<dyn Error>> {
+  match std::env::var(OPENAPI_VERSION_ENV_VAR) {
+    Ok(requested_version) => match OPENAPI_VERSIONS
+      .iter()
+      .find(|supported_version| supported_version.version == requested_version.trim() || supported_version.alias == requested_version.trim())
+    {
+      Some(openapi_version) => Ok(openapi_version),
+      None => Err(
+        format!(
+          "unsupported openapi version '{}' in environment variable {} (supported versions are {})",
+          requested_version,
+          OPENAPI_VERSION_ENV_VAR,
+          OPENAPI_VERSIONS
+            .iter()
+            .map(|supported_version| supported_version.version)
+            .collect::<Vec<_>>()
+            .join(", ")
+        )
+        .into(),
+      ),
+    },
+    Err(_) => Ok(
+      OPENAPI_VERSIONS
+        .iter()
+        .find(|supported_version| std::env::var(format!("CARGO_FEATURE_{}", supported_version.feature.to_uppercase().replace('-', "_"))).is_ok())
+        .unwrap_or(&OPENAPI_VERSIONS[0]),
+    ),
+  }
+}
@@ -21,7 +21,7 @@ async fn main() -> Result<(), String> {
   println!("{}", serde_json::to_string_pretty(&app_catalog_app).unwrap());
 
   let app_catalog_apps: HashMap<String, AppCatalogApp> = client.get_appcatalogapp_configuration_map().await?;
-  let mut keys = app_catalog_apps.keys().into_iter().map(|k| k.to_string()).collect_vec();
+  let mut keys = app_catalog_apps.keys().map(|k| k.to_string()).collect_vec();
   keys.sort();
   for key in keys {
     let app = app_catalog_apps.get(&key).unwrap();
@@ -29,7 +29,7 @@ async fn main() -> Result<(), String> {
   }
 
   let deployed_apps: HashMap<String, AppCatalogApp> = client.get_appcatalogapp_actual_map().await?;
-  let mut keys = deployed_apps.keys().into_iter().map(|k| k.to_string()).collect_vec();
+  let mut keys = deployed_apps.keys().map(|k| k.to_string()).collect_vec();
   keys.sort();
   for key in keys {
     let app = app_catalog_apps.get(&key).unwrap();
@@ -33,6 +33,7 @@ async fn main() -> Result<(), String> {
 
   print_header("client.find_applications_that_use_env_value(query)");
   let query_processor = RegexQueryProcessor::create(QUERY).unwrap();
+  #[allow(clippy::type_complexity)]
   let applications_that_use_env_value: Vec<(String, Application, Vec<(String, Match)>)> = client.applications_that_use_env_value(&query_processor).await?;
   for (application_id, _, matching_env_vars) in applications_that_use_env_value {
     println!("{}", application_id);
@@ -8,7 +8,7 @@ use std::io::Write;
 // DSH_API_SECRET_NPLZ_GREENBOX_DEV=...
 
 #[allow(unused)]
-pub async fn get_client<'a>() -> Result<DshApiClient, String> {
+pub async fn get_client() -> Result<DshApiClient, String> {
   // Explicit try_factory variable declaration is important since type inference will not work here
   let try_factory: Result<DshApiClientFactory, DshApiError> = DshApiClientFactory::try_default();
   match try_factory {
@@ -1,7 +1,7 @@
 #[path = "common.rs"]
 mod common;
 
-#[cfg(feature = "manage")]
+#[cfg(all(feature = "manage", not(dsh_api_1_8)))]
 #[tokio::main]
 async fn main() -> Result<(), String> {
   use crate::common::{get_client, initialize_logger, print_header};
@@ -174,5 +174,5 @@ async fn main() -> Result<(), String> {
   Ok(())
 }
 
-#[cfg(not(feature = "manage"))]
+#[cfg(not(all(feature = "manage", not(dsh_api_1_8))))]
 fn main() {}
@@ -0,0 +1,24 @@
+#[allow(unused_imports)]
+#[path = "common.rs"]
+mod common;
+
+use dsh_api::validation::validate_json;
+use std::error::Error;
+
+// Usage: cargo run --example validate -- <schema name> <json file>...
+fn main() -> Result<(), Box<dyn Error>> {
+  let mut arguments = std::env::args().skip(1);
+  let schema_name = arguments.next().ok_or("schema name expected (e.g. Application)")?;
+  for file_name in arguments {
+    let json = std::fs::read_to_string(&file_name)?;
+    let errors = validate_json(schema_name.as_str(), json.as_str())?;
+    if errors.is_empty() {
+      println!("{}: ok", file_name);
+    } else {
+      for error in errors {
+        println!("{}: {}", file_name, error);
+      }
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,312 @@
+//! # Additional methods to manage Kafka ACL groups
+//!
+//! _These functions are not available when the crate is generated from openapi specification version 1.8.0._
+//!
+//! Module that contains methods and functions to manage Kafka ACL groups.
+//! A Kafka ACL group grants read and/or write rights on a list of topics and streams.
+//!
+//! # Generated methods
+//!
+//! [`DshApiClient`] methods that are generated from the `openapi` specification.
+//!
+//! * [`delete_aclgroup_configuration(id)`](DshApiClient::delete_aclgroup_configuration)
+//! * [`get_aclgroup_configuration(id) -> KafkaAclGroup`](DshApiClient::get_aclgroup_configuration)
+//! * [`get_aclgroup_ids() -> [id]`](DshApiClient::get_aclgroup_ids)
+//! * [`put_aclgroup_configuration(id, body)`](DshApiClient::put_aclgroup_configuration)
+//!
+//! # Derived methods
+//!
+//! [`DshApiClient`] methods that add extra capabilities but do not directly call the
+//! DSH resource management API. These derived methods depend on the API methods for this.
+//!
+//! * [`acl_group_coverage() -> coverage`](DshApiClient::acl_group_coverage)
+//! * [`acl_group_matrix() -> [(acl group id, [topic rights])]`](DshApiClient::acl_group_matrix)
+//! * [`acl_groups() -> [(acl group id, acl group)]`](DshApiClient::acl_groups)
+//!
+//! # Functions
+//!
+//! * [`acl_group_coverage_from_configurations(acl groups, applications, topic ids) -> coverage`](acl_group_coverage_from_configurations)
+//! * [`acl_group_matrix_from_acl_groups(acl groups) -> [(acl group id, [topic rights])]`](acl_group_matrix_from_acl_groups)
+//! * [`acl_group_topic_rights(acl group) -> [topic rights]`](acl_group_topic_rights)
+
+use crate::dsh_api_client::DshApiClient;
+use crate::topic::topics_from_applications;
+use crate::types::{Application, GetAclgroupConfigurationByTenantByIdId, KafkaAclGroup, KafkaAclGroupTopicKind};
+use crate::{DshApiError, DshApiResult};
+use futures::future::try_join_all;
+use futures::try_join;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+/// # Describes the rights of an ACL group on a topic or stream
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct AclGroupTopicRights {
+  /// Kind of the topic or stream
+  pub kind: KafkaAclGroupTopicKind,
+  /// Name of the topic or stream
+  pub name: String,
+  /// Whether the ACL group grants read access
+  pub read: bool,
+  /// Whether the ACL group grants write access
+  pub write: bool,
+}
+
+impl Display for AclGroupTopicRights {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let rights = match (self.read, self.write) {
+      (true, true) => "read/write",
+      (true, false) => "read",
+      (false, true) => "write",
+      (false, false) => "none",
+    };
+    write!(f, "{}:{} ({})", self.kind.to_string(), self.name, rights)
+  }
+}
+
+/// # Describes the access that an application requires
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum AclAccess {
+  /// Read access, required for readable streams
+  #[serde(rename = "read")]
+  Read,
+  /// Write access, required for writable streams
+  #[serde(rename = "write")]
+  Write,
+  /// Read or write access, required for scratch topics
+  #[serde(rename = "read-or-write")]
+  ReadOrWrite,
+}
+
+impl Display for AclAccess {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      AclAccess::Read => write!(f, "read"),
+      AclAccess::Write => write!(f, "write"),
+      AclAccess::ReadOrWrite => write!(f, "read or write"),
+    }
+  }
+}
+
+/// # Describes a topic or stream that is not covered by any ACL group
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct UncoveredTopic {
+  /// Identifies the application that uses the topic or stream
+  pub application_id: String,
+  /// Name of the topic or stream, as used in the application
+  pub name: String,
+  /// Access that the application requires
+  pub access: AclAccess,
+}
+
+/// # Describes the coverage of the topics and streams by the ACL groups
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct AclGroupCoverage {
+  /// Topics and streams used by the applications for which no ACL group grants the required access,
+  /// sorted by application id, name and access
+  pub uncovered_topics: Vec<UncoveredTopic>,
+  /// ACL groups that refer to topics that do not exist, as pairs of ACL group id and topic name,
+  /// sorted by ACL group id and topic name
+  pub missing_topics: Vec<(String, String)>,
+}
+
+impl AclGroupCoverage {
+  /// # Check if there are any coverage issues
+  ///
+  /// # Returns
+  /// * `true` - all topics and streams are covered and all ACL groups refer to existing topics
+  /// * `false` - there are uncovered topics or missing topics
+  pub fn is_empty(&self) -> bool {
+    self.uncovered_topics.is_empty() && self.missing_topics.is_empty()
+  }
+}
+
+/// # Additional methods to manage Kafka ACL groups
+///
+/// Module that contains methods to manage Kafka ACL groups.
+/// * Derived methods - DshApiClient methods that add extra capabilities
+///   but depend on the API methods.
+///
+/// # Derived methods
+///
+/// [`DshApiClient`] methods that add extra capabilities but do not directly call the
+/// DSH resource management API. These derived methods depend on the API methods for this.
+///
+/// * [`acl_group_coverage() -> coverage`](DshApiClient::acl_group_coverage)
+/// * [`acl_group_matrix() -> [(acl group id, [topic rights])]`](DshApiClient::acl_group_matrix)
+/// * [`acl_groups() -> [(acl group id, acl group)]`](DshApiClient::acl_groups)
+impl DshApiClient {
+  /// # Returns the coverage of the topics and streams by the ACL groups
+  ///
+  /// See [`acl_group_coverage_from_configurations`] for a description of the checks.
+  ///
+  /// # Returns
+  /// * `Ok<`[`AclGroupCoverage`]`>` - the uncovered topics and streams,
+  ///   and the ACL groups that refer to missing topics
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn acl_group_coverage(&self) -> DshApiResult<AclGroupCoverage> {
+    let (acl_groups, applications, topic_ids) = try_join!(self.acl_groups(), self.get_application_configuration_map(), self.get_topic_ids())?;
+    Ok(acl_group_coverage_from_configurations(
+      &acl_groups.into_iter().collect::<HashMap<_, _>>(),
+      &applications,
+      &topic_ids,
+    ))
+  }
+
+  /// # Returns the rights matrix of all ACL groups
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(String, Vec<`[`AclGroupTopicRights`]`>)>>` - list of all ACL group ids,
+  ///   sorted by ACL group id, together with the rights that they grant on topics and streams
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn acl_group_matrix(&self) -> DshApiResult<Vec<(String, Vec<AclGroupTopicRights>)>> {
+    Ok(acl_group_matrix_from_acl_groups(&self.acl_groups().await?.into_iter().collect::<HashMap<_, _>>()))
+  }
+
+  /// # Returns all ACL groups
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(String, `[`KafkaAclGroup`]`)>>` - list of all ACL group ids and configurations,
+  ///   sorted by ACL group id
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn acl_groups(&self) -> DshApiResult<Vec<(String, KafkaAclGroup)>> {
+    let mut acl_group_ids: Vec<String> = self.get_aclgroup_ids().await?;
+    acl_group_ids.sort();
+    let ids = acl_group_ids
+      .iter()
+      .map(|acl_group_id| acl_group_id.parse::<GetAclgroupConfigurationByTenantByIdId>())
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(|error| DshApiError::Unexpected(format!("illegal acl group id ({})", error), None))?;
+    let acl_groups = try_join_all(ids.iter().map(|id| self.get_aclgroup_configuration(id))).await?;
+    Ok(acl_group_ids.into_iter().zip(acl_groups).collect_vec())
+  }
+}
+
+/// # Get the coverage of the topics and streams by the ACL groups
+///
+/// The following topics and streams are reported as uncovered:
+/// * scratch topics of applications (see [`topics_from_applications`]) for which no ACL group
+///   of kind `topic` grants read or write access,
+/// * readable streams of applications for which no ACL group of kind `internal` or `public`
+///   grants read access,
+/// * writable streams of applications for which no ACL group of kind `internal` or `public`
+///   grants write access.
+///
+/// Streams are matched by name, with an optional `internal.` or `stream.` prefix removed.
+/// Only ACL group topics of kind `topic` can be checked for existence, since the internal
+/// and public streams are managed outside the tenant.
+///
+/// # Parameters
+/// * `acl_groups` - hashmap containing id/ACL group pairs
+/// * `applications` - hashmap containing id/application pairs
+/// * `topic_ids` - ids of all existing topics of the tenant
+///
+/// # Returns
+/// [`AclGroupCoverage`] - the uncovered topics and streams,
+/// and the ACL groups that refer to missing topics
+pub fn acl_group_coverage_from_configurations(acl_groups: &HashMap<String, KafkaAclGroup>, applications: &HashMap<String, Application>, topic_ids: &[String]) -> AclGroupCoverage {
+  let mut topic_rights = HashMap::<&str, (bool, bool)>::new();
+  let mut stream_rights = HashMap::<&str, (bool, bool)>::new();
+  for acl_group in acl_groups.values() {
+    for (acl_group_topics, access) in [(&acl_group.readable_streams, AclAccess::Read), (&acl_group.writable_streams, AclAccess::Write)] {
+      for acl_group_topic in acl_group_topics {
+        let rights = match acl_group_topic.kind {
+          KafkaAclGroupTopicKind::Topic => topic_rights.entry(acl_group_topic.name.as_str()).or_default(),
+          KafkaAclGroupTopicKind::Internal | KafkaAclGroupTopicKind::Public => stream_rights.entry(stream_name(&acl_group_topic.name)).or_default(),
+        };
+        if access == AclAccess::Read {
+          rights.0 = true;
+        } else {
+          rights.1 = true;
+        }
+      }
+    }
+  }
+  let is_covered = |rights_map: &HashMap<&str, (bool, bool)>, name: &str, access: AclAccess| {
+    rights_map.get(name).is_some_and(|(read, write)| match access {
+      AclAccess::Read => *read,
+      AclAccess::Write => *write,
+      AclAccess::ReadOrWrite => *read || *write,
+    })
+  };
+  let mut uncovered_topics = Vec::<UncoveredTopic>::new();
+  for application_values in topics_from_applications(applications) {
+    for topic in application_values.values {
+      if !is_covered(&topic_rights, topic, AclAccess::ReadOrWrite) {
+        uncovered_topics.push(UncoveredTopic { application_id: application_values.id.to_string(), name: topic.to_string(), access: AclAccess::ReadOrWrite });
+      }
+    }
+  }
+  for (application_id, application) in applications {
+    for (streams, access) in [(&application.readable_streams, AclAccess::Read), (&application.writable_streams, AclAccess::Write)] {
+      for stream in streams {
+        if !is_covered(&stream_rights, stream_name(stream), access) {
+          uncovered_topics.push(UncoveredTopic { application_id: application_id.to_string(), name: stream.to_string(), access });
+        }
+      }
+    }
+  }
+  uncovered_topics.sort();
+  uncovered_topics.dedup();
+  let existing_topic_ids = topic_ids.iter().map(String::as_str).collect::<HashSet<_>>();
+  let mut missing_topics = acl_groups
+    .iter()
+    .flat_map(|(acl_group_id, acl_group)| {
+      acl_group
+        .readable_streams
+        .iter()
+        .chain(acl_group.writable_streams.iter())
+        .filter(|acl_group_topic| acl_group_topic.kind == KafkaAclGroupTopicKind::Topic && !existing_topic_ids.contains(acl_group_topic.name.as_str()))
+        .map(|acl_group_topic| (acl_group_id.to_string(), acl_group_topic.name.to_string()))
+    })
+    .collect_vec();
+  missing_topics.sort();
+  missing_topics.dedup();
+  AclGroupCoverage { uncovered_topics, missing_topics }
+}
+
+/// # Get the rights matrix from ACL groups
+///
+/// # Parameters
+/// * `acl_groups` - hashmap containing id/ACL group pairs
+///
+/// # Returns
+/// `Vec<(String, Vec<AclGroupTopicRights>)>` - list of all ACL group ids, sorted by ACL group id,
+/// together with the rights that they grant on topics and streams
+pub fn acl_group_matrix_from_acl_groups(acl_groups: &HashMap<String, KafkaAclGroup>) -> Vec<(String, Vec<AclGroupTopicRights>)> {
+  acl_groups
+    .iter()
+    .map(|(acl_group_id, acl_group)| (acl_group_id.to_string(), acl_group_topic_rights(acl_group)))
+    .sorted_by(|(acl_group_id_a, _), (acl_group_id_b, _)| acl_group_id_a.cmp(acl_group_id_b))
+    .collect_vec()
+}
+
+/// # Get the rights of an ACL group
+///
+/// Combines the readable and writable streams of the ACL group into one list
+/// with the read and write rights per topic or stream.
+///
+/// # Parameters
+/// * `acl_group` - reference to the `KafkaAclGroup`
+///
+/// # Returns
+/// `Vec<AclGroupTopicRights>` - list of the rights of the ACL group, sorted by kind and name
+pub fn acl_group_topic_rights(acl_group: &KafkaAclGroup) -> Vec<AclGroupTopicRights> {
+  let mut rights = BTreeMap::<(KafkaAclGroupTopicKind, &str), (bool, bool)>::new();
+  for readable_stream in &acl_group.readable_streams {
+    rights.entry((readable_stream.kind, readable_stream.name.as_str())).or_default().0 = true;
+  }
+  for writable_stream in &acl_group.writable_streams {
+    rights.entry((writable_stream.kind, writable_stream.name.as_str())).or_default().1 = true;
+  }
+  rights
+    .into_iter()
+    .map(|((kind, name), (read, write))| AclGroupTopicRights { kind, name: name.to_string(), read, write })
+    .collect_vec()
+}
+
+// Removes the optional `internal.` or `stream.` prefix from a stream name
+fn stream_name(name: &str) -> &str {
+  name.strip_prefix("internal.").or_else(|| name.strip_prefix("stream.")).unwrap_or(name)
+}
@@ -0,0 +1,486 @@
+//! # Traits that describe the DSH resource management API
+//!
+//! Module that contains a small set of per-domain traits that describe the read and write
+//! surface of the DSH resource management API, which are implemented by the [`DshApiClient`].
+//! Code that only depends on these traits, instead of on the concrete [`DshApiClient`],
+//! can be used with alternative implementations, like the in-memory `FakeDshApiClient`
+//! from the `fake` module, that is available when the `testing` feature is enabled.
+//!
+//! | trait              | resources                  |
+//! | ------------------ | -------------------------- |
+//! | [`AppCatalogApi`]  | app catalog apps           |
+//! | [`ApplicationApi`] | applications               |
+//! | [`BucketApi`]      | buckets                    |
+//! | [`CertificateApi`] | certificates               |
+//! | [`SecretApi`]      | secrets                    |
+//! | [`TopicApi`]       | topics                     |
+//! | [`VolumeApi`]      | volumes                    |
+//! | [`DshApi`]         | all of the above           |
+//!
+//! The methods of the traits have the same names and signatures as the corresponding
+//! methods of the [`DshApiClient`]. The trait [`DshApi`] is implemented for all types that
+//! implement all per-domain traits, and provides derived methods that find the dependants
+//! of resources. The [`DshApiClient`] methods with the same names delegate to these methods.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dsh_api::api::DshApi;
+//! use dsh_api::DshApiResult;
+//!
+//! async fn unused_secrets(api: &impl DshApi) -> DshApiResult<Vec<String>> {
+//!   Ok(
+//!     api
+//!       .secrets_with_dependants()
+//!       .await?
+//!       .into_iter()
+//!       .filter(|(_, dependants)| dependants.is_empty())
+//!       .map(|(secret_id, _)| secret_id)
+//!       .collect(),
+//!   )
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # use dsh_api::dsh_api_client_factory::DshApiClientFactory;
+//! let client = DshApiClientFactory::default().client().await?;
+//! println!("{:?}", unused_secrets(&client).await?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::app::apps_that_use_secret;
+use crate::dsh_api_client::DshApiClient;
+use crate::secret::{secret_dependants_from_configurations, secret_env_vars_from_applications, SecretInjection};
+use crate::topic::{topic_dependants_from_configurations, TopicInjection};
+use crate::types::{AllocationStatus, AppCatalogApp, Application, Bucket, Certificate, Secret, Topic, Volume};
+use crate::volume::{volume_dependants_from_configurations, volume_paths_from_applications, VolumeInjection};
+use crate::{Dependant, DependantApp, DependantApplication, DshApiResult};
+use futures::try_join;
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::future::Future;
+
+/// # Access to the app catalog apps
+pub trait AppCatalogApi: Sync {
+  /// # Returns the configuration of an app catalog app
+  fn get_appcatalogapp_configuration(&self, appcatalogappid: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<AppCatalogApp>> + Send;
+
+  /// # Returns the configurations of all app catalog apps, keyed by app id
+  fn get_appcatalogapp_configuration_map(&self) -> impl Future<Output = DshApiResult<HashMap<String, AppCatalogApp>>> + Send;
+}
+
+/// # Access to the applications
+pub trait ApplicationApi: Sync {
+  /// # Deletes an application
+  fn delete_application_configuration(&self, appid: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<()>> + Send;
+
+  /// # Returns the configuration of an application
+  fn get_application_configuration(&self, appid: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<Application>> + Send;
+
+  /// # Returns the configurations of all applications, keyed by application id
+  fn get_application_configuration_map(&self) -> impl Future<Output = DshApiResult<HashMap<String, Application>>> + Send;
+
+  /// # Returns the allocation status of an application
+  fn get_application_status(&self, appid: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<AllocationStatus>> + Send;
+
+  /// # Creates or updates an application
+  fn put_application_configuration(&self, appid: impl AsRef<str> + Send, body: &Application) -> impl Future<Output = DshApiResult<()>> + Send;
+}
+
+/// # Access to the buckets
+pub trait BucketApi: Sync {
+  /// # Deletes a bucket
+  fn delete_bucket_configuration(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<()>> + Send;
+
+  /// # Returns the configuration of a bucket
+  fn get_bucket_configuration(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<Bucket>> + Send;
+
+  /// # Returns the ids of all buckets
+  fn get_bucket_ids(&self) -> impl Future<Output = DshApiResult<Vec<String>>> + Send;
+
+  /// # Returns the allocation status of a bucket
+  fn get_bucket_status(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<AllocationStatus>> + Send;
+
+  /// # Creates or updates a bucket
+  fn put_bucket_configuration(&self, id: impl AsRef<str> + Send, body: &Bucket) -> impl Future<Output = DshApiResult<()>> + Send;
+}
+
+/// # Access to the certificates
+pub trait CertificateApi: Sync {
+  /// # Deletes a certificate
+  fn delete_certificate_configuration(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<()>> + Send;
+
+  /// # Returns the configuration of a certificate
+  fn get_certificate_configuration(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<Certificate>> + Send;
+
+  /// # Returns the ids of all certificates
+  fn get_certificate_ids(&self) -> impl Future<Output = DshApiResult<Vec<String>>> + Send;
+
+  /// # Returns the allocation status of a certificate
+  fn get_certificate_status(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<AllocationStatus>> + Send;
+
+  /// # Creates or updates a certificate
+  fn put_certificate_configuration(&self, id: impl AsRef<str> + Send, body: &Certificate) -> impl Future<Output = DshApiResult<()>> + Send;
+}
+
+/// # Access to the secrets
+pub trait SecretApi: Sync {
+  /// # Deletes a secret
+  fn delete_secret_configuration(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<()>> + Send;
+
+  /// # Returns the value of a secret
+  fn get_secret(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<String>> + Send;
+
+  /// # Returns the ids of all secrets
+  fn get_secret_ids(&self) -> impl Future<Output = DshApiResult<Vec<String>>> + Send;
+
+  /// # Returns the allocation status of a secret
+  fn get_secret_status(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<AllocationStatus>> + Send;
+
+  /// # Creates a secret
+  fn post_secret(&self, body: &Secret) -> impl Future<Output = DshApiResult<()>> + Send;
+
+  /// # Updates the value of a secret
+  fn put_secret(&self, id: impl AsRef<str> + Send, body: String) -> impl Future<Output = DshApiResult<()>> + Send;
+}
+
+/// # Access to the topics
+pub trait TopicApi: Sync {
+  /// # Deletes a topic
+  fn delete_topic_configuration(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<()>> + Send;
+
+  /// # Returns the configuration of a topic
+  fn get_topic_configuration(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<Topic>> + Send;
+
+  /// # Returns the ids of all topics
+  fn get_topic_ids(&self) -> impl Future<Output = DshApiResult<Vec<String>>> + Send;
+
+  /// # Returns the allocation status of a topic
+  fn get_topic_status(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<AllocationStatus>> + Send;
+
+  /// # Creates or updates a topic
+  fn put_topic_configuration(&self, id: impl AsRef<str> + Send, body: &Topic) -> impl Future<Output = DshApiResult<()>> + Send;
+}
+
+/// # Access to the volumes
+pub trait VolumeApi: Sync {
+  /// # Deletes a volume
+  fn delete_volume_configuration(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<()>> + Send;
+
+  /// # Returns the configuration of a volume
+  fn get_volume_configuration(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<Volume>> + Send;
+
+  /// # Returns the ids of all volumes
+  fn get_volume_ids(&self) -> impl Future<Output = DshApiResult<Vec<String>>> + Send;
+
+  /// # Returns the allocation status of a volume
+  fn get_volume_status(&self, id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<AllocationStatus>> + Send;
+
+  /// # Creates or updates a volume
+  fn put_volume_configuration(&self, id: impl AsRef<str> + Send, body: &Volume) -> impl Future<Output = DshApiResult<()>> + Send;
+}
+
+/// # Access to all resources, with derived methods
+///
+/// This trait is implemented for all types that implement all per-domain traits.
+/// The derived methods only use the methods of the per-domain traits.
+pub trait DshApi: AppCatalogApi + ApplicationApi + BucketApi + CertificateApi + SecretApi + TopicApi + VolumeApi {
+  /// # List all application ids
+  ///
+  /// # Returns
+  /// * `Ok<Vec<String>>` - vector containing the sorted application ids
+  /// * `Err<`[`DshApiError`](crate::DshApiError)`>` - when the request could not be processed by the DSH
+  fn application_ids(&self) -> impl Future<Output = DshApiResult<Vec<String>>> + Send {
+    async move { Ok(self.get_application_configuration_map().await?.into_keys().sorted().collect_vec()) }
+  }
+
+  /// # Get all applications that depend on a given secret
+  ///
+  /// # Parameters
+  /// * `secret_id` - id of the requested secret
+  ///
+  /// # Returns
+  /// * `Ok<Vec<DependantApplication>>` - applications that use the secret
+  /// * `Err<`[`DshApiError`](crate::DshApiError)`>` - when the request could not be processed by the DSH
+  fn applications_dependant_on_secret(&self, secret_id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<Vec<DependantApplication<SecretInjection>>>> + Send {
+    async move {
+      let applications = self.get_application_configuration_map().await?;
+      Ok(
+        secret_env_vars_from_applications(secret_id.as_ref(), &applications)
+          .iter()
+          .map(|application_values| {
+            DependantApplication::new(
+              application_values.id.to_string(),
+              application_values.application.instances,
+              application_values
+                .values
+                .iter()
+                .map(|env_var| SecretInjection::EnvVar(env_var.to_string()))
+                .collect_vec(),
+            )
+          })
+          .collect_vec(),
+      )
+    }
+  }
+
+  /// # Get all applications that depend on a given volume
+  ///
+  /// # Parameters
+  /// * `volume` - id of the requested volume
+  ///
+  /// # Returns
+  /// * `Ok<Vec<DependantApplication>>` - applications that mount the volume
+  /// * `Err<`[`DshApiError`](crate::DshApiError)`>` - when the request could not be processed by the DSH
+  fn applications_dependant_on_volume(&self, volume: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<Vec<DependantApplication<VolumeInjection>>>> + Send {
+    async move {
+      let applications = self.get_application_configuration_map().await?;
+      Ok(
+        volume_paths_from_applications(volume.as_ref(), &applications)
+          .iter()
+          .map(|application_values| {
+            DependantApplication::new(
+              application_values.id.to_string(),
+              application_values.application.instances,
+              application_values.values.iter().map(|path| VolumeInjection::Volume(path.to_string())).collect_vec(),
+            )
+          })
+          .collect_vec(),
+      )
+    }
+  }
+
+  /// # Get all apps that depend on a secret
+  ///
+  /// # Parameters
+  /// * `secret_id` - Identifier of the secret.
+  ///
+  /// # Returns
+  /// * `Ok<Vec<DependantApp>>` - Apps that depend on the secret.
+  /// * `Err<`[`DshApiError`](crate::DshApiError)`>` - When the request could not be processed by the DSH.
+  fn apps_dependant_on_secret(&self, secret_id: impl AsRef<str> + Send) -> impl Future<Output = DshApiResult<Vec<DependantApp>>> + Send {
+    async move {
+      Ok(
+        apps_that_use_secret(secret_id.as_ref(), &self.get_appcatalogapp_configuration_map().await?)
+          .into_iter()
+          .map(|(app_id, _, resource_ids)| DependantApp::new(app_id.to_string(), resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec()))
+          .collect_vec(),
+      )
+    }
+  }
+
+  /// # Returns all secrets with dependant applications and apps
+  ///
+  /// Returns a sorted list of all secrets together with the applications and apps that use them.
+  fn secrets_with_dependants(&self) -> impl Future<Output = DshApiResult<Vec<(String, Vec<Dependant<SecretInjection>>)>>> + Send {
+    async move {
+      let (secret_ids, applications, apps) = try_join!(
+        self.get_secret_ids(),
+        self.get_application_configuration_map(),
+        self.get_appcatalogapp_configuration_map()
+      )?;
+      Ok(
+        secret_ids
+          .into_iter()
+          .map(|secret_id| {
+            let dependants = secret_dependants_from_configurations(secret_id.as_str(), &applications, &apps);
+            (secret_id, dependants)
+          })
+          .collect_vec(),
+      )
+    }
+  }
+
+  /// # Returns all topics with dependant applications and apps
+  ///
+  /// Returns a sorted list of all topics together with the applications and apps that use them.
+  fn topics_with_dependants(&self) -> impl Future<Output = DshApiResult<Vec<(String, Vec<Dependant<TopicInjection>>)>>> + Send {
+    async move {
+      let (topic_ids, applications, apps) = try_join!(
+        self.get_topic_ids(),
+        self.get_application_configuration_map(),
+        self.get_appcatalogapp_configuration_map()
+      )?;
+      Ok(
+        topic_ids
+          .into_iter()
+          .map(|topic_id| {
+            let dependants = topic_dependants_from_configurations(topic_id.as_str(), &applications, &apps);
+            (topic_id, dependants)
+          })
+          .collect_vec(),
+      )
+    }
+  }
+
+  /// # Returns all volumes with dependant applications and apps
+  ///
+  /// Returns a sorted list of all volumes together with the applications and apps that use them.
+  fn volumes_with_dependants(&self) -> impl Future<Output = DshApiResult<Vec<(String, Vec<Dependant<VolumeInjection>>)>>> + Send {
+    async move {
+      let (volume_ids, applications, apps) = try_join!(
+        self.get_volume_ids(),
+        self.get_application_configuration_map(),
+        self.get_appcatalogapp_configuration_map()
+      )?;
+      Ok(
+        volume_ids
+          .into_iter()
+          .map(|volume_id| {
+            let dependants = volume_dependants_from_configurations(volume_id.as_str(), &applications, &apps);
+            (volume_id, dependants)
+          })
+          .collect_vec(),
+      )
+    }
+  }
+}
+
+impl<T> DshApi for T where T: AppCatalogApi + ApplicationApi + BucketApi + CertificateApi + SecretApi + TopicApi + VolumeApi {}
+
+impl AppCatalogApi for DshApiClient {
+  async fn get_appcatalogapp_configuration(&self, appcatalogappid: impl AsRef<str> + Send) -> DshApiResult<AppCatalogApp> {
+    DshApiClient::get_appcatalogapp_configuration(self, appcatalogappid).await
+  }
+
+  async fn get_appcatalogapp_configuration_map(&self) -> DshApiResult<HashMap<String, AppCatalogApp>> {
+    DshApiClient::get_appcatalogapp_configuration_map(self).await
+  }
+}
+
+impl ApplicationApi for DshApiClient {
+  async fn delete_application_configuration(&self, appid: impl AsRef<str> + Send) -> DshApiResult<()> {
+    DshApiClient::delete_application_configuration(self, appid).await
+  }
+
+  async fn get_application_configuration(&self, appid: impl AsRef<str> + Send) -> DshApiResult<Application> {
+    DshApiClient::get_application_configuration(self, appid).await
+  }
+
+  async fn get_application_configuration_map(&self) -> DshApiResult<HashMap<String, Application>> {
+    DshApiClient::get_application_configuration_map(self).await
+  }
+
+  async fn get_application_status(&self, appid: impl AsRef<str> + Send) -> DshApiResult<AllocationStatus> {
+    DshApiClient::get_application_status(self, appid).await
+  }
+
+  async fn put_application_configuration(&self, appid: impl AsRef<str> + Send, body: &Application) -> DshApiResult<()> {
+    DshApiClient::put_application_configuration(self, appid, body).await
+  }
+}
+
+impl BucketApi for DshApiClient {
+  async fn delete_bucket_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<()> {
+    DshApiClient::delete_bucket_configuration(self, id).await
+  }
+
+  async fn get_bucket_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<Bucket> {
+    DshApiClient::get_bucket_configuration(self, id).await
+  }
+
+  async fn get_bucket_ids(&self) -> DshApiResult<Vec<String>> {
+    DshApiClient::get_bucket_ids(self).await
+  }
+
+  async fn get_bucket_status(&self, id: impl AsRef<str> + Send) -> DshApiResult<AllocationStatus> {
+    DshApiClient::get_bucket_status(self, id).await
+  }
+
+  async fn put_bucket_configuration(&self, id: impl AsRef<str> + Send, body: &Bucket) -> DshApiResult<()> {
+    DshApiClient::put_bucket_configuration(self, id, body).await
+  }
+}
+
+impl CertificateApi for DshApiClient {
+  async fn delete_certificate_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<()> {
+    DshApiClient::delete_certificate_configuration(self, id).await
+  }
+
+  async fn get_certificate_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<Certificate> {
+    DshApiClient::get_certificate_configuration(self, id).await
+  }
+
+  async fn get_certificate_ids(&self) -> DshApiResult<Vec<String>> {
+    DshApiClient::get_certificate_ids(self).await
+  }
+
+  async fn get_certificate_status(&self, id: impl AsRef<str> + Send) -> DshApiResult<AllocationStatus> {
+    DshApiClient::get_certificate_status(self, id).await
+  }
+
+  async fn put_certificate_configuration(&self, id: impl AsRef<str> + Send, body: &Certificate) -> DshApiResult<()> {
+    DshApiClient::put_certificate_configuration(self, id, body).await
+  }
+}
+
+impl SecretApi for DshApiClient {
+  async fn delete_secret_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<()> {
+    DshApiClient::delete_secret_configuration(self, id).await
+  }
+
+  async fn get_secret(&self, id: impl AsRef<str> + Send) -> DshApiResult<String> {
+    DshApiClient::get_secret(self, id).await
+  }
+
+  async fn get_secret_ids(&self) -> DshApiResult<Vec<String>> {
+    DshApiClient::get_secret_ids(self).await
+  }
+
+  async fn get_secret_status(&self, id: impl AsRef<str> + Send) -> DshApiResult<AllocationStatus> {
+    DshApiClient::get_secret_status(self, id).await
+  }
+
+  async fn post_secret(&self, body: &Secret) -> DshApiResult<()> {
+    DshApiClient::post_secret(self, body).await
+  }
+
+  async fn put_secret(&self, id: impl AsRef<str> + Send, body: String) -> DshApiResult<()> {
+    DshApiClient::put_secret(self, id, body).await
+  }
+}
+
+impl TopicApi for DshApiClient {
+  async fn delete_topic_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<()> {
+    DshApiClient::delete_topic_configuration(self, id).await
+  }
+
+  async fn get_topic_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<Topic> {
+    DshApiClient::get_topic_configuration(self, id).await
+  }
+
+  async fn get_topic_ids(&self) -> DshApiResult<Vec<String>> {
+    DshApiClient::get_topic_ids(self).await
+  }
+
+  async fn get_topic_status(&self, id: impl AsRef<str> + Send) -> DshApiResult<AllocationStatus> {
+    DshApiClient::get_topic_status(self, id).await
+  }
+
+  async fn put_topic_configuration(&self, id: impl AsRef<str> + Send, body: &Topic) -> DshApiResult<()> {
+    DshApiClient::put_topic_configuration(self, id, body).await
+  }
+}
+
+impl VolumeApi for DshApiClient {
+  async fn delete_volume_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<()> {
+    DshApiClient::delete_volume_configuration(self, id).await
+  }
+
+  async fn get_volume_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<Volume> {
+    DshApiClient::get_volume_configuration(self, id).await
+  }
+
+  async fn get_volume_ids(&self) -> DshApiResult<Vec<String>> {
+    DshApiClient::get_volume_ids(self).await
+  }
+
+  async fn get_volume_status(&self, id: impl AsRef<str> + Send) -> DshApiResult<AllocationStatus> {
+    DshApiClient::get_volume_status(self, id).await
+  }
+
+  async fn put_volume_configuration(&self, id: impl AsRef<str> + Send, body: &Volume) -> DshApiResult<()> {
+    DshApiClient::put_volume_configuration(self, id, body).await
+  }
+}
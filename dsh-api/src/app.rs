@@ -30,6 +30,7 @@
 //! * [`apps_dependant_on_vhost(vhost_id) -> [app]`](DshApiClient::apps_dependant_on_vhost)
 //! * [`apps_dependant_on_volume(volume_id) -> [app]`](DshApiClient::apps_dependant_on_volume)
 
+use crate::api::DshApi;
 use crate::application::application_resources_from_app;
 use crate::bucket::bucket_resources_from_app;
 use crate::certificate::certificate_resources_from_app;
@@ -78,7 +79,8 @@ impl DshApiClient {
   ///   and parsed configuration hashmap.
   /// * `Err<`[`DshApiError::NotFound`]`>` - When the app could not be found.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed.
-  pub async fn app_configuration(&self, app_id: &str) -> DshApiResult<(AppCatalogApp, Option<HashMap<String, String>>)> {
+  pub async fn app_configuration(&self, app_id: impl AsRef<str>) -> DshApiResult<(AppCatalogApp, Option<HashMap<String, String>>)> {
+    let app_id = app_id.as_ref();
     match self.get_appcatalogapp_configuration(app_id).await {
       Ok(app_catalog_app) => {
         let configuration = app_catalog_app
@@ -146,7 +148,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<Vec<DependantApp>>` - Apps that depend on the application.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed by the DSH.
-  pub async fn apps_dependant_on_application(&self, application_id: &str) -> DshApiResult<Vec<DependantApp>> {
+  pub async fn apps_dependant_on_application(&self, application_id: impl AsRef<str>) -> DshApiResult<Vec<DependantApp>> {
+    let application_id = application_id.as_ref();
     Ok(
       apps_that_use_application(application_id, &self.get_appcatalogapp_configuration_map().await?)
         .into_iter()
@@ -163,7 +166,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<Vec<DependantApp>>` - Apps that depend on the bucket.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed by the DSH.
-  pub async fn apps_dependant_on_bucket(&self, bucket_id: &str) -> DshApiResult<Vec<DependantApp>> {
+  pub async fn apps_dependant_on_bucket(&self, bucket_id: impl AsRef<str>) -> DshApiResult<Vec<DependantApp>> {
+    let bucket_id = bucket_id.as_ref();
     Ok(
       apps_that_use_resource(bucket_id, &self.get_appcatalogapp_configuration_map().await?, &bucket_resources_from_app)
         .into_iter()
@@ -180,7 +184,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<Vec<DependantApp>>` - Apps that depend on the certificate.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed by the DSH.
-  pub async fn apps_dependant_on_certificate(&self, certificate_id: &str) -> DshApiResult<Vec<DependantApp>> {
+  pub async fn apps_dependant_on_certificate(&self, certificate_id: impl AsRef<str>) -> DshApiResult<Vec<DependantApp>> {
+    let certificate_id = certificate_id.as_ref();
     Ok(
       apps_that_use_certificate(certificate_id, &self.get_appcatalogapp_configuration_map().await?)
         .into_iter()
@@ -197,13 +202,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<Vec<DependantApp>>` - Apps that depend on the secret.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed by the DSH.
-  pub async fn apps_dependant_on_secret(&self, secret_id: &str) -> DshApiResult<Vec<DependantApp>> {
-    Ok(
-      apps_that_use_secret(secret_id, &self.get_appcatalogapp_configuration_map().await?)
-        .into_iter()
-        .map(|(app_id, _, resource_ids)| DependantApp::new(app_id.to_string(), resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec()))
-        .collect_vec(),
-    )
+  pub async fn apps_dependant_on_secret(&self, secret_id: impl AsRef<str>) -> DshApiResult<Vec<DependantApp>> {
+    DshApi::apps_dependant_on_secret(self, secret_id.as_ref()).await
   }
 
   /// # Get all apps that depend on a topic
@@ -214,7 +214,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<Vec<DependantApp>>` - Apps that depend on the topic.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed by the DSH.
-  pub async fn apps_dependant_on_topic(&self, topic_id: &str) -> DshApiResult<Vec<DependantApp>> {
+  pub async fn apps_dependant_on_topic(&self, topic_id: impl AsRef<str>) -> DshApiResult<Vec<DependantApp>> {
+    let topic_id = topic_id.as_ref();
     Ok(
       apps_that_use_topic(topic_id, &self.get_appcatalogapp_configuration_map().await?)
         .into_iter()
@@ -231,7 +232,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<Vec<DependantApp>>` - Apps that depend on the vhost.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed by the DSH.
-  pub async fn apps_dependant_on_vhost(&self, vhost_id: &str) -> DshApiResult<Vec<DependantApp>> {
+  pub async fn apps_dependant_on_vhost(&self, vhost_id: impl AsRef<str>) -> DshApiResult<Vec<DependantApp>> {
+    let vhost_id = vhost_id.as_ref();
     Ok(
       apps_that_use_vhost(vhost_id, &self.get_appcatalogapp_configuration_map().await?)
         .into_iter()
@@ -248,7 +250,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<Vec<DependantApp>>` - Apps that depend on the volume.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed by the DSH.
-  pub async fn apps_dependant_on_volume(&self, volume_id: &str) -> DshApiResult<Vec<DependantApp>> {
+  pub async fn apps_dependant_on_volume(&self, volume_id: impl AsRef<str>) -> DshApiResult<Vec<DependantApp>> {
+    let volume_id = volume_id.as_ref();
     Ok(
       apps_that_use_volume(volume_id, &self.get_appcatalogapp_configuration_map().await?)
         .into_iter()
@@ -367,7 +370,7 @@ pub(crate) fn app_resources<'a, T>(app: &'a AppCatalogApp, get_resource_variant:
       resources.push((resource_id, resource))
     }
   }
-  resources.sort_by(|(resource_id_a, _), (resource_id_b, _)| resource_id_a.cmp(resource_id_b));
+  resources.sort_by_key(|(resource_id_a, _)| *resource_id_a);
   resources
 }
 
@@ -396,6 +399,6 @@ pub(crate) fn apps_that_use_resource<'a, T: 'a>(
       }
     }
   }
-  tuples.sort_by(|(app_id_a, _, _), (app_id_b, _, _)| app_id_a.cmp(app_id_b));
+  tuples.sort_by_key(|(app_id_a, _, _)| *app_id_a);
   tuples
 }
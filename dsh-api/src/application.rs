@@ -36,6 +36,7 @@
 //! * [`applications_that_use_env_value(query) -> [(id, app, [env])]`](DshApiClient::applications_that_use_env_value)
 //! * [`guid() -> (gid, uid)`](DshApiClient::guid)
 
+use crate::api::DshApi;
 use crate::app::app_resources;
 use crate::application_types::ApplicationValues;
 use crate::bucket::BucketInjection;
@@ -50,7 +51,7 @@ use crate::volume::VolumeInjection;
 #[allow(unused_imports)]
 use crate::DshApiError;
 use crate::DshApiError::Unexpected;
-use crate::{bucket, secret, topic, vhost, volume, DependantApplication, DshApiResult};
+use crate::{bucket, topic, vhost, DependantApplication, DshApiResult};
 use futures::future::{join, try_join_all};
 use itertools::Itertools;
 use lazy_static::lazy_static;
@@ -89,14 +90,7 @@ impl DshApiClient {
   /// * `Ok<Vec<String>>` - vector containing the sorted application ids
   /// * `Err<\[DshApiError\]>` - when the request could not be processed by the DSH
   pub async fn application_ids(&self) -> DshApiResult<Vec<String>> {
-    let mut application_ids: Vec<String> = self
-      .get_application_configuration_map()
-      .await?
-      .keys()
-      .map(|application_id| application_id.to_string())
-      .collect();
-    application_ids.sort();
-    Ok(application_ids)
+    DshApi::application_ids(self).await
   }
 
   /// # List application ids with the corresponding allocation status
@@ -127,7 +121,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<Vec<DependantApplication>>` - usage.
   /// * `Err<\[DshApiError\]>` - when the request could not be processed by the DSH
-  pub async fn applications_dependant_on_bucket(&self, bucket_id: &str) -> DshApiResult<Vec<DependantApplication<BucketInjection>>> {
+  pub async fn applications_dependant_on_bucket(&self, bucket_id: impl AsRef<str>) -> DshApiResult<Vec<DependantApplication<BucketInjection>>> {
+    let bucket_id = bucket_id.as_ref();
     let (applications, bucket_name) = match self.platform().cloud_provider() {
       CloudProvider::AWS => (self.get_application_configuration_map().await?, None),
       CloudProvider::Azure => {
@@ -157,24 +152,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<Vec<DependantApplication>>` - usage.
   /// * `Err<\[DshApiError\]>` - when the request could not be processed by the DSH
-  pub async fn applications_dependant_on_secret(&self, secret_id: &str) -> DshApiResult<Vec<DependantApplication<SecretInjection>>> {
-    let applications = self.get_application_configuration_map().await?;
-    Ok(
-      secret::secret_env_vars_from_applications(secret_id, &applications)
-        .iter()
-        .map(|application_values| {
-          DependantApplication::new(
-            application_values.id.to_string(),
-            application_values.application.instances,
-            application_values
-              .values
-              .iter()
-              .map(|env_var| SecretInjection::EnvVar(env_var.to_string()))
-              .collect_vec(),
-          )
-        })
-        .collect_vec(),
-    )
+  pub async fn applications_dependant_on_secret(&self, secret_id: impl AsRef<str>) -> DshApiResult<Vec<DependantApplication<SecretInjection>>> {
+    DshApi::applications_dependant_on_secret(self, secret_id.as_ref()).await
   }
 
   /// # Get all application that depend on a given vhost
@@ -185,7 +164,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<Vec<DependantApplication>>` - usage.
   /// * `Err<\[DshApiError\]>` - when the request could not be processed by the DSH
-  pub async fn applications_dependant_on_scratch_topic(&self, topic: &str) -> DshApiResult<Vec<DependantApplication<TopicInjection>>> {
+  pub async fn applications_dependant_on_scratch_topic(&self, topic: impl AsRef<str>) -> DshApiResult<Vec<DependantApplication<TopicInjection>>> {
+    let topic = topic.as_ref();
     let applications = self.get_application_configuration_map().await?;
     Ok(
       topic::topic_used_in_applications(topic, &applications)
@@ -203,7 +183,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<Vec<DependantApplication>>` - usage.
   /// * `Err<\[DshApiError\]>` - when the request could not be processed by the DSH
-  pub async fn applications_dependant_on_vhost(&self, vhost: &str) -> DshApiResult<Vec<DependantApplication<VhostInjection>>> {
+  pub async fn applications_dependant_on_vhost(&self, vhost: impl AsRef<str>) -> DshApiResult<Vec<DependantApplication<VhostInjection>>> {
+    let vhost = vhost.as_ref();
     let applications = self.get_application_configuration_map().await?;
     Ok(
       vhost::vhost_port_mappings_from_applications(vhost, &applications)
@@ -231,20 +212,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<Vec<DependantApplication>>` - usage.
   /// * `Err<\[DshApiError\]>` - when the request could not be processed by the DSH
-  pub async fn applications_dependant_on_volume(&self, volume: &str) -> DshApiResult<Vec<DependantApplication<VolumeInjection>>> {
-    let applications = self.get_application_configuration_map().await?;
-    Ok(
-      volume::volume_paths_from_applications(volume, &applications)
-        .iter()
-        .map(|application_values| {
-          DependantApplication::new(
-            application_values.id.to_string(),
-            application_values.application.instances,
-            application_values.values.iter().map(|path| VolumeInjection::Volume(path.to_string())).collect_vec(),
-          )
-        })
-        .collect_vec(),
-    )
+  pub async fn applications_dependant_on_volume(&self, volume: impl AsRef<str>) -> DshApiResult<Vec<DependantApplication<VolumeInjection>>> {
+    DshApi::applications_dependant_on_volume(self, volume.as_ref()).await
   }
 
   /// # Find all applications that match a predicate
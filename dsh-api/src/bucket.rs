@@ -41,21 +41,27 @@
 //! [`DshApiClient`] methods that add extra capabilities but do not directly call the
 //! DSH resource management API. These derived methods depend on the API methods for this.
 //!
+//! * [`bucket_accesses(bucket id) -> [(access name, access status)]`](DshApiClient::bucket_accesses)
 //! * [`bucket_ids_with_dependants() -> [(bucket id, [dependant])]`](DshApiClient::bucket_ids_with_dependants)
 //! * [`bucket_map() -> map(bucket id -> bucket)`](DshApiClient::bucket_map)
 //! * [`bucket_name(bucket id) -> bucket name`](DshApiClient::bucket_name)
-//! * [`bucket_with_dependants(bucket id) -> (bucket id, [dependant])]`](DshApiClient::bucket_with_dependants)
+//! * [`bucket_watch(bucket id) -> Option<watch status>`](DshApiClient::bucket_watch)
+//! * [`bucket_with_dependants(bucket id) -> bucket with watch, accesses and dependants`](DshApiClient::bucket_with_dependants)
 //! * [`buckets() -> [(bucket id, bucket)]`](DshApiClient::buckets)
 //! * [`buckets_with_dependant_applications() -> [(bucket_id, bucket, [dependant application])]`](DshApiClient::buckets_with_dependant_applications)
 //! * [`buckets_with_dependant_apps() -> [(bucket id, bucket, [dependant app])]`](DshApiClient::buckets_with_dependant_apps)
 //! * [`buckets_with_dependants() -> [(bucket_id, [dependant])]`](DshApiClient::bucket_with_dependants)
+//! * [`disable_bucket_watch(bucket id)`](DshApiClient::disable_bucket_watch)
+//! * [`enable_bucket_watch(bucket id)`](DshApiClient::enable_bucket_watch)
+//! * [`grant_bucket_access(bucket id, access name, readable, writable)`](DshApiClient::grant_bucket_access)
+//! * [`revoke_bucket_access(bucket id, access name)`](DshApiClient::revoke_bucket_access)
 
 use crate::app::{app_resources, apps_that_use_resource};
 use crate::application_types::{ApplicationValues, EnvVarInjection};
 use crate::dsh_api_client::DshApiClient;
 use crate::parse::parse_function1;
 use crate::platform::CloudProvider;
-use crate::types::{AppCatalogApp, AppCatalogAppResourcesValue, Application, Bucket, BucketStatus};
+use crate::types::{AppCatalogApp, AppCatalogAppResourcesValue, Application, Bucket, BucketAccessConfiguration, BucketAccessStatus, BucketStatus, BucketWatchStatus};
 #[allow(unused_imports)]
 use crate::DshApiError;
 use crate::{Dependant, DependantApp, DependantApplication, DshApiResult};
@@ -91,6 +97,19 @@ impl Display for BucketInjection {
   }
 }
 
+/// # Describes a bucket with its watch, access grants and dependants
+#[derive(Debug, PartialEq, Serialize)]
+pub struct BucketWithDependants {
+  /// Configuration, actual state and status of the bucket
+  pub bucket: BucketStatus,
+  /// Watch of the bucket, `None` when the bucket is not watched
+  pub watch: Option<BucketWatchStatus>,
+  /// Names and statuses of the access grants of the bucket, sorted by name
+  pub accesses: Vec<(String, BucketAccessStatus)>,
+  /// Applications and apps that use the bucket
+  pub dependants: Vec<Dependant<BucketInjection>>,
+}
+
 /// # Additional methods to manage buckets
 ///
 /// Module that contains methods and functions to manage buckets.
@@ -102,15 +121,38 @@ impl Display for BucketInjection {
 /// [`DshApiClient`] methods that add extra capabilities but do not directly call the
 /// DSH resource management API. These derived methods depend on the API methods for this.
 ///
+/// * [`bucket_accesses(bucket id) -> [(access name, access status)]`](DshApiClient::bucket_accesses)
 /// * [`bucket_ids_with_dependants() -> [(bucket id, [dependant])]`](DshApiClient::bucket_ids_with_dependants)
 /// * [`bucket_map() -> map(bucket id -> bucket)`](DshApiClient::bucket_map)
 /// * [`bucket_name(bucket id) -> bucket name`](DshApiClient::bucket_name)
-/// * [`bucket_with_dependants(bucket id) -> (bucket id, [dependant])]`](DshApiClient::bucket_with_dependants)
+/// * [`bucket_watch(bucket id) -> Option<watch status>`](DshApiClient::bucket_watch)
+/// * [`bucket_with_dependants(bucket id) -> bucket with watch, accesses and dependants`](DshApiClient::bucket_with_dependants)
 /// * [`buckets() -> [(bucket id, bucket)]`](DshApiClient::buckets)
 /// * [`buckets_with_dependant_applications() -> [(bucket_id, bucket, [dependant application])]`](DshApiClient::buckets_with_dependant_applications)
 /// * [`buckets_with_dependant_apps() -> [(bucket id, bucket, [dependant app])]`](DshApiClient::buckets_with_dependant_apps)
 /// * [`buckets_with_dependants() -> [(bucket_id, [dependant])]`](DshApiClient::bucket_with_dependants)
+/// * [`disable_bucket_watch(bucket id)`](DshApiClient::disable_bucket_watch)
+/// * [`enable_bucket_watch(bucket id)`](DshApiClient::enable_bucket_watch)
+/// * [`grant_bucket_access(bucket id, access name, readable, writable)`](DshApiClient::grant_bucket_access)
+/// * [`revoke_bucket_access(bucket id, access name)`](DshApiClient::revoke_bucket_access)
 impl DshApiClient {
+  /// # Returns all access grants of a bucket
+  ///
+  /// # Parameters
+  /// * `bucket_id` - Identifier of the bucket.
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(String, `[`BucketAccessStatus`]`)>>` - list of the names and statuses
+  ///   of all access grants of the bucket, sorted by name
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn bucket_accesses(&self, bucket_id: impl AsRef<str>) -> DshApiResult<Vec<(String, BucketAccessStatus)>> {
+    let bucket_id = bucket_id.as_ref();
+    let mut access_names = self.get_bucket_access_ids(bucket_id).await?;
+    access_names.sort();
+    let access_statuses = try_join_all(access_names.iter().map(|access_name| self.get_bucket_access(bucket_id, access_name))).await?;
+    Ok(access_names.into_iter().zip(access_statuses).collect_vec())
+  }
+
   /// # Returns all bucket identifiers with dependant applications and apps
   ///
   /// Returns a sorted list of all bucket ids together with the applications and apps that use them.
@@ -123,21 +165,8 @@ impl DshApiClient {
     )?;
     let mut buckets = Vec::<(String, Vec<Dependant<BucketInjection>>)>::new();
     for bucket_id in &bucket_ids {
-      let mut dependants: Vec<Dependant<BucketInjection>> = vec![];
       let bucket_name = self.platform().bucket_name(self.tenant_name(), bucket_id, access_key_id.as_deref()).ok();
-      for application_injections in bucket_injections_from_applications(bucket_id, bucket_name.as_deref(), &applications) {
-        dependants.push(Dependant::application(
-          application_injections.id.to_string(),
-          application_injections.application.instances,
-          application_injections.values,
-        ));
-      }
-      for (app_id, _, resource_ids) in apps_that_use_resource(bucket_id.as_str(), &apps, &bucket_resources_from_app) {
-        dependants.push(Dependant::app(
-          app_id.to_string(),
-          resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec(),
-        ));
-      }
+      let dependants = bucket_dependants_from_configurations(bucket_id, bucket_name.as_deref(), &applications, &apps);
       buckets.push((bucket_id.to_string(), dependants));
     }
     Ok(buckets)
@@ -158,7 +187,8 @@ impl DshApiClient {
   /// * `Ok<String>` - When the key was successfully craeted.
   /// * `Err<`[`DshApiError::NotFound`]`>` - When on Azure the bucket secret is not set.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed.
-  pub async fn bucket_name(&self, bucket_id: &str) -> DshApiResult<String> {
+  pub async fn bucket_name(&self, bucket_id: impl AsRef<str>) -> DshApiResult<String> {
+    let bucket_id = bucket_id.as_ref();
     match self.platform().cloud_provider() {
       CloudProvider::Azure => match self.object_store_access_key_id_if_required().await {
         Ok(Some(access_key_id)) => Ok(self.platform().bucket_name(self.tenant_name(), bucket_id, Some(access_key_id))?),
@@ -171,34 +201,44 @@ impl DshApiClient {
     }
   }
 
-  /// # Returns all buckets with dependant applications and apps
+  /// # Returns the watch of a bucket
+  ///
+  /// # Parameters
+  /// * `bucket_id` - Identifier of the bucket.
+  ///
+  /// # Returns
+  /// * `Ok<Some<`[`BucketWatchStatus`]`>>` - when the bucket is watched
+  /// * `Ok<None>` - when the bucket is not watched
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn bucket_watch(&self, bucket_id: impl AsRef<str>) -> DshApiResult<Option<BucketWatchStatus>> {
+    match self.get_bucket_watch(bucket_id).await {
+      Ok(bucket_watch_status) => Ok(Some(bucket_watch_status)),
+      Err(DshApiError::NotFound(_)) => Ok(None),
+      Err(error) => Err(error),
+    }
+  }
+
+  /// # Returns a bucket with its watch, access grants and dependant applications and apps
   ///
   /// # Parameters
   /// * `bucket_id` - Identifier of the requested bucket.
   ///
-  /// Returns a bucket with the applications and apps that use it.
-  pub async fn bucket_with_dependants(&self, bucket_id: &str) -> DshApiResult<(BucketStatus, Vec<Dependant<BucketInjection>>)> {
-    let (bucket_status, application_configuration_map, appcatalogapp_configuration_map) = try_join!(
+  /// # Returns
+  /// * `Ok<`[`BucketWithDependants`]`>` - the bucket, its watch and access grants
+  ///   and the applications and apps that use it
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn bucket_with_dependants(&self, bucket_id: impl AsRef<str>) -> DshApiResult<BucketWithDependants> {
+    let bucket_id = bucket_id.as_ref();
+    let (bucket_status, watch, accesses, application_configuration_map, appcatalogapp_configuration_map) = try_join!(
       self.get_bucket(bucket_id),
+      self.bucket_watch(bucket_id),
+      self.bucket_accesses(bucket_id),
       self.get_application_configuration_map(),
       self.get_appcatalogapp_configuration_map()
     )?;
-    let mut dependants: Vec<Dependant<BucketInjection>> = vec![];
     let bucket_name = self.bucket_name(bucket_id).await.ok();
-    for application in bucket_injections_from_applications(bucket_id, bucket_name.as_deref(), &application_configuration_map) {
-      dependants.push(Dependant::application(
-        application.id.to_string(),
-        application.application.instances,
-        application.values,
-      ));
-    }
-    for (app_id, _, resource_ids) in apps_that_use_resource(bucket_id, &appcatalogapp_configuration_map, &bucket_resources_from_app) {
-      dependants.push(Dependant::app(
-        app_id.to_string(),
-        resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec(),
-      ));
-    }
-    Ok((bucket_status, dependants))
+    let dependants = bucket_dependants_from_configurations(bucket_id, bucket_name.as_deref(), &application_configuration_map, &appcatalogapp_configuration_map);
+    Ok(BucketWithDependants { bucket: bucket_status, watch, accesses, dependants })
   }
 
   /// Return a list of all buckets
@@ -273,26 +313,69 @@ impl DshApiClient {
     )?;
     let mut buckets_with_dependants = Vec::<(String, BucketStatus, Vec<Dependant<BucketInjection>>)>::new();
     for (ref bucket_id, bucket_status) in buckets {
-      let mut dependants: Vec<Dependant<BucketInjection>> = vec![];
       let bucket_name = self.platform().bucket_name(self.tenant_name(), bucket_id, access_key_id.as_deref()).ok();
-      for application in bucket_injections_from_applications(bucket_id.as_str(), bucket_name.as_deref(), &application_configuration_map) {
-        dependants.push(Dependant::application(
-          application.id.to_string(),
-          application.application.instances,
-          application.values,
-        ));
-      }
-      for (app_id, _, resource_ids) in apps_that_use_resource(bucket_id.as_str(), &apps, &bucket_resources_from_app) {
-        dependants.push(Dependant::app(
-          app_id.to_string(),
-          resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec(),
-        ));
-      }
+      let dependants = bucket_dependants_from_configurations(bucket_id, bucket_name.as_deref(), &application_configuration_map, &apps);
       buckets_with_dependants.push((bucket_id.to_string(), bucket_status, dependants));
     }
     Ok(buckets_with_dependants)
   }
 
+  /// # Stop watching a bucket
+  ///
+  /// # Parameters
+  /// * `bucket_id` - Identifier of the bucket.
+  ///
+  /// # Returns
+  /// * `Ok(())` - when the bucket watch was removed
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn disable_bucket_watch(&self, bucket_id: impl AsRef<str>) -> DshApiResult<()> {
+    self.delete_bucket_watch_configuration(bucket_id).await
+  }
+
+  /// # Start watching a bucket
+  ///
+  /// # Parameters
+  /// * `bucket_id` - Identifier of the bucket.
+  ///
+  /// # Returns
+  /// * `Ok(())` - when the bucket watch was created
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn enable_bucket_watch(&self, bucket_id: impl AsRef<str>) -> DshApiResult<()> {
+    self.put_bucket_watch_configuration(bucket_id).await
+  }
+
+  /// # Grant named access to a bucket
+  ///
+  /// Creates or replaces the access grant `access_name` for the bucket.
+  ///
+  /// # Parameters
+  /// * `bucket_id` - Identifier of the bucket.
+  /// * `access_name` - Name of the access grant.
+  /// * `readable` - Whether the access grant allows reading from the bucket.
+  /// * `writable` - Whether the access grant allows writing to the bucket.
+  ///
+  /// # Returns
+  /// * `Ok(())` - when the access grant was created or replaced
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn grant_bucket_access(&self, bucket_id: impl AsRef<str>, access_name: impl AsRef<str>, readable: bool, writable: bool) -> DshApiResult<()> {
+    let (bucket_id, access_name) = (bucket_id.as_ref(), access_name.as_ref());
+    let bucket_access_configuration = BucketAccessConfiguration { bucket: bucket_id.to_string(), name: access_name.to_string(), readable, writable };
+    self.put_bucket_access_configuration(bucket_id, access_name, &bucket_access_configuration).await
+  }
+
+  /// # Revoke named access to a bucket
+  ///
+  /// # Parameters
+  /// * `bucket_id` - Identifier of the bucket.
+  /// * `access_name` - Name of the access grant.
+  ///
+  /// # Returns
+  /// * `Ok(())` - when the access grant was removed
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn revoke_bucket_access(&self, bucket_id: impl AsRef<str>, access_name: impl AsRef<str>) -> DshApiResult<()> {
+    self.delete_bucket_access_configuration(bucket_id, access_name).await
+  }
+
   /// # Returns the object store secrets
   ///
   /// Returns the object store `access_key_id` and `secret_access_key`.
@@ -304,7 +387,7 @@ impl DshApiClient {
   }
 
   /// Get object store access key
-  async fn object_store_access_key_id_if_required(&self) -> DshApiResult<Option<String>> {
+  pub(crate) async fn object_store_access_key_id_if_required(&self) -> DshApiResult<Option<String>> {
     match self.platform().cloud_provider() {
       CloudProvider::AWS => Ok(None),
       CloudProvider::Azure => self.get_secret(OBJECT_STORE_ACCESS_KEY_ID).await.map(Some),
@@ -312,6 +395,30 @@ impl DshApiClient {
   }
 }
 
+// Returns the applications and apps that use a bucket, from the application and app configurations
+pub(crate) fn bucket_dependants_from_configurations(
+  bucket_id: &str,
+  bucket_name: Option<&str>,
+  applications: &HashMap<String, Application>,
+  apps: &HashMap<String, AppCatalogApp>,
+) -> Vec<Dependant<BucketInjection>> {
+  let mut dependants: Vec<Dependant<BucketInjection>> = vec![];
+  for application in bucket_injections_from_applications(bucket_id, bucket_name, applications) {
+    dependants.push(Dependant::application(
+      application.id.to_string(),
+      application.application.instances,
+      application.values,
+    ));
+  }
+  for (app_id, _, resource_ids) in apps_that_use_resource(bucket_id, apps, &bucket_resources_from_app) {
+    dependants.push(Dependant::app(
+      app_id.to_string(),
+      resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec(),
+    ));
+  }
+  dependants
+}
+
 /// # Get applications environment variables referencing bucket
 ///
 /// Get all environment variables referencing bucket `bucket_id` or (if available) `bucket_name`
@@ -418,7 +525,7 @@ pub fn bucket_resources_from_app(app: &AppCatalogApp) -> Vec<(&str, &Bucket)> {
 /// * list of environment variables referencing the bucket
 ///
 /// The list is sorted by bucket id.
-pub fn buckets_from_application(application: &Application) -> Vec<EnvVarInjection> {
+pub fn buckets_from_application(application: &Application) -> Vec<EnvVarInjection<'_>> {
   let mut buckets = HashMap::<&str, Vec<&str>>::new();
   for (env_key, env_value) in &application.env {
     if let Ok(bucket_id) = parse_function1(env_value, "bucket_name") {
@@ -446,7 +553,7 @@ pub fn buckets_from_application(application: &Application) -> Vec<EnvVarInjectio
 ///   sorted by bucket id
 ///
 /// The list is sorted by application id.
-pub fn buckets_from_applications(applications: &HashMap<String, Application>) -> Vec<ApplicationValues<EnvVarInjection>> {
+pub fn buckets_from_applications(applications: &HashMap<String, Application>) -> Vec<ApplicationValues<'_, EnvVarInjection<'_>>> {
   let mut application_tuples: Vec<ApplicationValues<EnvVarInjection>> = vec![];
   for (application_id, application) in applications {
     let injections: Vec<EnvVarInjection> = buckets_from_application(application);
@@ -0,0 +1,67 @@
+//! # Builders for request body types
+//!
+//! Module that contains fluent builders for all types that are used as a request body
+//! in the openapi specification, like [`Application`](crate::types::Application),
+//! [`Topic`](crate::types::Topic), [`Secret`](crate::types::Secret) and, when the `manage`
+//! feature is enabled, [`ManagedTenant`](crate::types::ManagedTenant) and
+//! [`PublicManagedStream`](crate::types::PublicManagedStream).
+//! The builders are generated from the openapi specification at build time.
+//!
+//! A builder is created with the `builder()` function of the type
+//! (e.g. [`Application::builder()`](crate::types::Application::builder)).
+//! The builder has a setter method for each field. Fields that contain a map with `String` keys
+//! have an extra method that adds one entry to the map (e.g. `env_entry(key, value)`).
+//! The `build()` method creates the value, where
+//! * required fields that have not been set will result in an error,
+//! * optional fields that have not been set will be `None` or get their default value
+//!   from the openapi specification,
+//! * the created value is validated against the openapi specification
+//!   (see the [`validation`](crate::validation) module).
+//!
+//! All errors are reported as [`DshApiError::Parameter`], with a message that names the
+//! missing or invalid field.
+//!
+//! # Examples
+//!
+//! ```
+//! use dsh_api::types::Application;
+//!
+//! let application = Application::builder()
+//!   .image("registry.cp.kpn-dsh.com/my-tenant/my-image:0.0.1")
+//!   .cpus(0.5)
+//!   .mem(512)
+//!   .user("1234:1234")
+//!   .env_entry("LOG_LEVEL", "info")
+//!   .build()
+//!   .unwrap();
+//! assert_eq!(application.instances, 1);
+//! assert!(application.needs_token);
+//!
+//! let error = Application::builder().cpus(0.5).build().unwrap_err();
+//! assert_eq!(error.to_string(), "missing required field 'image' for Application");
+//! ```
+
+use crate::validation::validate_schema;
+use crate::{DshApiError, DshApiResult};
+use itertools::Itertools;
+use serde::Serialize;
+
+include!(concat!(env!("OUT_DIR"), "/builders.rs"));
+
+fn missing_field(type_name: &str, field_name: &str) -> DshApiError {
+  DshApiError::Parameter(format!("missing required field '{}' for {}", field_name, type_name))
+}
+
+fn validate_built<T: Serialize>(schema_name: &str, value: &T) -> DshApiResult<()> {
+  let json_value = serde_json::to_value(value).map_err(|error| DshApiError::Parameter(format!("could not serialize {} ({})", schema_name, error)))?;
+  let validation_errors = validate_schema(schema_name, &json_value)?;
+  if validation_errors.is_empty() {
+    Ok(())
+  } else {
+    Err(DshApiError::Parameter(format!(
+      "invalid {} ({})",
+      schema_name,
+      validation_errors.iter().map(|validation_error| validation_error.to_string()).join(", ")
+    )))
+  }
+}
@@ -8,7 +8,7 @@
 //!
 //! * [`delete_certificate_configuration(id)`](DshApiClient::delete_certificate_configuration)
 //! * [`get_certificate(id) -> CertificateStatus`](DshApiClient::get_certificate)
-//! * [`get_certificate_actual(id) -> Certificate`](DshApiClient::get_certificate_actual)
+//! * [`get_certificate_actual(id) -> ActualCertificate`](DshApiClient::get_certificate_actual)
 //! * [`get_certificate_configuration(id) -> Certificate`](DshApiClient::get_certificate_configuration)
 //! * [`get_certificate_ids() -> [id]`](DshApiClient::get_certificate_ids)
 //! * [`get_certificate_status(id) -> AllocationStatus`](DshApiClient::get_certificate_status)
@@ -19,19 +19,254 @@
 //! [`DshApiClient`] methods that add extra capabilities but do not directly call the
 //! DSH resource management API. These derived methods depend on the API methods for this.
 //!
+//! * [`certificate_chain(certificate id) -> [CertificateInfo]`](DshApiClient::certificate_chain)
 //! * [`certificate_with_dependant_apps(certificate id) -> (certificate status, [app])`](DshApiClient::certificate_with_dependant_apps)
+//! * [`certificates_expiring_within(days, concurrency) -> [CertificateExpiry]`](DshApiClient::certificates_expiring_within)
 //! * [`certificates_with_dependant_apps() -> [(certificate id, certificate status, [app])]`](DshApiClient::certificates_with_dependant_apps)
+//! * [`upload_certificate(certificate id, certificate pem) -> Certificate`](DshApiClient::upload_certificate)
+//! * [`vhosts_without_certificate(concurrency) -> [(vhost id, [application])]`](DshApiClient::vhosts_without_certificate)
+//!
+//! # Functions
+//!
+//! * [`certificate_resources_from_app(app) -> [(resource id, certificate)]`](certificate_resources_from_app)
+//! * [`certificate_secret_names(certificate id, suffix) -> (cert chain secret, key secret)`](certificate_secret_names)
+//! * [`dns_name_matches(dns name, host) -> bool`](dns_name_matches)
+//! * [`expiring_certificates(certificates, moment, days) -> [CertificateExpiry]`](expiring_certificates)
+//! * [`parse_pem_certificates(pem) -> [CertificateInfo]`](parse_pem_certificates)
+//! * [`vhost_domains(vhost, platform, tenant name) -> [domain]`](vhost_domains)
+//! * [`vhosts_without_certificate_from_applications(applications, dns names, platform, tenant name) -> [(vhost id, [application])]`](vhosts_without_certificate_from_applications)
 
 use crate::app::app_resources;
 use crate::dsh_api_client::DshApiClient;
+use crate::platform::DshPlatform;
 use crate::secret::secrets_resources_from_apps;
-use crate::types::{AppCatalogApp, AppCatalogAppResourcesValue, Certificate, CertificateStatus};
-#[allow(unused_imports)]
-use crate::DshApiError;
-use crate::{DependantApp, DshApiResult};
-use futures::future::try_join_all;
-use futures::try_join;
+use crate::types::{ActualCertificate, AppCatalogApp, AppCatalogAppResourcesValue, Application, Certificate, CertificateStatus, Secret};
+use crate::vhost::{VhostInjection, VhostString};
+use crate::{now, DependantApp, DependantApplication, DshApiError, DshApiResult};
+use chrono::{DateTime, Duration, Utc};
+use futures::future::{self, try_join_all};
+use futures::{try_join, TryStreamExt};
+use itertools::Itertools;
+use log::warn;
+use serde::Serialize;
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use x509_parser::certificate::X509Certificate;
+use x509_parser::der_parser::asn1_rs::Class;
+use x509_parser::der_parser::ber::{BerObject, BerObjectContent};
+use x509_parser::der_parser::der::parse_der;
+use x509_parser::extensions::GeneralName;
+use x509_parser::objects::{oid2abbrev, oid_registry};
+use x509_parser::oid_registry::{OID_KEY_TYPE_EC_PUBLIC_KEY, OID_PKCS1_RSAENCRYPTION, OID_SIG_ED25519};
+use x509_parser::pem::Pem;
+use x509_parser::public_key::PublicKey;
+use x509_parser::x509::X509Name;
+
+/// # Describes the type and size of the public key of a certificate
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub enum CertificateKeyType {
+  /// Elliptic curve key, where the value is the key size in bits
+  Ec(usize),
+  /// Ed25519 key
+  Ed25519,
+  /// RSA key, where the value is the key size in bits
+  Rsa(usize),
+  /// Other key type, where the value is the object identifier of the key algorithm
+  Other(String),
+}
+
+impl Display for CertificateKeyType {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      CertificateKeyType::Ec(key_size) => write!(f, "ec-{}", key_size),
+      CertificateKeyType::Ed25519 => write!(f, "ed25519"),
+      CertificateKeyType::Rsa(key_size) => write!(f, "rsa-{}", key_size),
+      CertificateKeyType::Other(algorithm) => write!(f, "{}", algorithm),
+    }
+  }
+}
+
+/// # Describes the decoded content of a certificate
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct CertificateInfo {
+  /// Distinguished name of the subject
+  pub subject: String,
+  /// Distinguished name of the issuer
+  pub issuer: String,
+  /// Serial number, as colon separated hexadecimal bytes
+  pub serial_number: String,
+  /// Dns names from the subject alternative names extension
+  pub subject_alternative_names: Vec<String>,
+  /// Start of the validity period
+  pub not_before: DateTime<Utc>,
+  /// End of the validity period
+  pub not_after: DateTime<Utc>,
+  /// Type and size of the public key
+  pub key_type: CertificateKeyType,
+  /// Whether the certificate is a certificate authority
+  pub is_ca: bool,
+}
+
+impl CertificateInfo {
+  /// # Check if the certificate covers a host
+  ///
+  /// # Parameters
+  /// * `host` - fully qualified domain name of the host
+  ///
+  /// # Returns
+  /// * `true` - when one of the subject alternative names matches `host`
+  /// * `false` - otherwise
+  pub fn covers(&self, host: &str) -> bool {
+    self.subject_alternative_names.iter().any(|dns_name| dns_name_matches(dns_name, host))
+  }
+}
+
+impl Display for CertificateInfo {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} (issuer {}, {}, valid until {})", self.subject, self.issuer, self.key_type, self.not_after)
+  }
+}
+
+/// # Describes the expiry of a certificate
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct CertificateExpiry {
+  /// Identifies the certificate
+  pub certificate_id: String,
+  /// End of the validity period
+  pub not_after: DateTime<Utc>,
+  /// Number of whole days until the certificate expires, negative when it already expired
+  pub days_remaining: i64,
+}
+
+impl CertificateExpiry {
+  /// # Check if the certificate already expired
+  pub fn is_expired(&self) -> bool {
+    self.days_remaining < 0
+  }
+}
+
+impl Display for CertificateExpiry {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    if self.is_expired() {
+      write!(f, "{} expired at {}", self.certificate_id, self.not_after)
+    } else {
+      write!(f, "{} expires at {} ({} days)", self.certificate_id, self.not_after, self.days_remaining)
+    }
+  }
+}
+
+/// # Describes the pem material of a certificate
+///
+/// Contains the certificate, the chain of certificate authorities that issued it
+/// and the private key, all in pem format. Encrypted private keys are not supported.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificatePem {
+  /// Pem encoded certificate
+  pub certificate: String,
+  /// Pem encoded certificate authorities that issued the certificate, ordered from the issuer
+  /// of the certificate towards the root, which can be empty when the certificate is self-signed
+  pub chain: String,
+  /// Pem encoded private key, in either pkcs#8, pkcs#1 (rsa) or sec1 (ec) format
+  pub private_key: String,
+  /// Subjects of the root certificate authorities that are allowed to be omitted from the chain
+  /// (e.g. `CN=My Root CA, O=My Organization`), since they are already trusted by the clients,
+  /// see [`with_omitted_root`](CertificatePem::with_omitted_root) for the format
+  pub omitted_roots: Vec<String>,
+}
+
+impl CertificatePem {
+  /// # Create a `CertificatePem`
+  ///
+  /// # Parameters
+  /// * `certificate` - pem encoded certificate
+  /// * `chain` - pem encoded certificate chain, ordered from the issuer towards the root
+  /// * `private_key` - pem encoded private key
+  pub fn new(certificate: impl Into<String>, chain: impl Into<String>, private_key: impl Into<String>) -> Self {
+    Self { certificate: certificate.into(), chain: chain.into(), private_key: private_key.into(), omitted_roots: vec![] }
+  }
+
+  /// # Allow a root certificate authority to be omitted from the chain
+  ///
+  /// The subject is a distinguished name, formatted as comma separated `attribute=value` pairs,
+  /// e.g. `CN=My Root CA, O=My Organization`, like [`CertificateInfo::issuer`].
+  /// The attributes are identified by their usual abbreviations (e.g. `CN`, `O`, `OU`, `L`, `ST` or `C`),
+  /// which are compared case-insensitively. The order of the attributes and the whitespace
+  /// around the attributes and values are not significant. A comma in a value must be escaped
+  /// with a backslash (e.g. `O=My Organization\, Inc.`).
+  ///
+  /// # Parameters
+  /// * `subject` - subject of the root certificate authority
+  pub fn with_omitted_root(mut self, subject: impl Into<String>) -> Self {
+    self.omitted_roots.push(subject.into());
+    self
+  }
+
+  /// # Create a `CertificatePem` from files
+  ///
+  /// # Parameters
+  /// * `certificate_file` - file that contains the pem encoded certificate
+  /// * `chain_file` - file that contains the pem encoded certificate chain
+  /// * `private_key_file` - file that contains the pem encoded private key
+  ///
+  /// # Returns
+  /// * `Ok<CertificatePem>` - the pem material read from the files
+  /// * `Err<`[`DshApiError::Parameter`]`>` - when one of the files could not be read
+  pub fn from_files(certificate_file: impl AsRef<Path>, chain_file: impl AsRef<Path>, private_key_file: impl AsRef<Path>) -> DshApiResult<Self> {
+    Ok(Self::new(
+      read_pem_file(certificate_file.as_ref())?,
+      read_pem_file(chain_file.as_ref())?,
+      read_pem_file(private_key_file.as_ref())?,
+    ))
+  }
+
+  /// # Returns the certificate chain, including the certificate itself
+  ///
+  /// This is the value that is stored in the certificate chain secret.
+  pub fn cert_chain(&self) -> String {
+    let certificate = self.certificate.trim_end();
+    let chain = self.chain.trim();
+    if chain.is_empty() {
+      format!("{}\n", certificate)
+    } else {
+      format!("{}\n{}\n", certificate, chain)
+    }
+  }
+
+  /// # Validate the pem material
+  ///
+  /// Checks that the private key matches the public key of the certificate,
+  /// that each certificate in the chain is a certificate authority that issued
+  /// and signed its predecessor, and that the chain is complete. The chain is complete
+  /// when its last certificate, which is the certificate itself when the chain is empty,
+  /// is either self-signed, with a valid signature, or issued by one of the root certificate authorities
+  /// that are explicitly allowed to be omitted (see [`with_omitted_root`](CertificatePem::with_omitted_root)).
+  ///
+  /// # Returns
+  /// * `Ok<Vec<`[`CertificateInfo`]`>>` - decoded certificates, starting with the certificate itself
+  /// * `Err<`[`DshApiError::Parameter`]`>` - when the pem material is not valid
+  pub fn validate(&self) -> DshApiResult<Vec<CertificateInfo>> {
+    let cert_chain = self.cert_chain();
+    let blocks = pem_blocks(cert_chain.as_str(), "CERTIFICATE")?;
+    let certificates = blocks
+      .iter()
+      .map(|pem_block| {
+        pem_block
+          .parse_x509()
+          .map_err(|error| DshApiError::Parameter(format!("invalid certificate ({})", error)))
+      })
+      .collect::<DshApiResult<Vec<_>>>()?;
+    if pem_blocks(self.certificate.as_str(), "CERTIFICATE")?.len() != 1 {
+      return Err(DshApiError::Parameter("certificate must contain exactly one certificate".to_string()));
+    }
+    if !private_key_matches(self.private_key.as_str(), &certificates[0])? {
+      return Err(DshApiError::Parameter("private key does not match the certificate".to_string()));
+    }
+    validate_chain(&certificates, &self.omitted_roots)?;
+    certificates.iter().map(certificate_info).collect()
+  }
+}
 
 /// # Additional methods to manage certificates
 ///
@@ -44,9 +279,31 @@ use std::collections::HashMap;
 /// [`DshApiClient`] methods that add extra capabilities but do not directly call the
 /// DSH resource management API. These derived methods depend on the API methods for this.
 ///
+/// * [`certificate_chain(certificate id) -> [CertificateInfo]`](DshApiClient::certificate_chain)
 /// * [`certificate_with_dependant_apps(certificate id) -> (certificate status, [app])`](DshApiClient::certificate_with_dependant_apps)
+/// * [`certificates_expiring_within(days, concurrency) -> [CertificateExpiry]`](DshApiClient::certificates_expiring_within)
 /// * [`certificates_with_dependant_apps() -> [(certificate id, certificate status, [app])]`](DshApiClient::certificates_with_dependant_apps)
+/// * [`upload_certificate(certificate id, certificate pem) -> Certificate`](DshApiClient::upload_certificate)
+/// * [`vhosts_without_certificate(concurrency) -> [(vhost id, [application])]`](DshApiClient::vhosts_without_certificate)
 impl DshApiClient {
+  /// # Returns the decoded certificate chain of a certificate
+  ///
+  /// Reads the secret that contains the certificate chain of the actual certificate
+  /// and decodes the certificates in it.
+  ///
+  /// # Parameters
+  /// * `certificate_id` - id of the requested certificate
+  ///
+  /// # Returns
+  /// * `Ok<Vec<`[`CertificateInfo`]`>>` - decoded certificates, in the order of the chain
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH,
+  ///   or when the certificate chain could not be decoded
+  pub async fn certificate_chain(&self, certificate_id: impl AsRef<str>) -> DshApiResult<Vec<CertificateInfo>> {
+    let actual_certificate = self.get_certificate_actual(certificate_id.as_ref()).await?;
+    let cert_chain = self.get_secret(actual_certificate.cert_chain_secret.as_str()).await?;
+    parse_pem_certificates(cert_chain.as_str())
+  }
+
   /// # Return certificate with usage
   ///
   /// Returns the certificate configuration for the provided certificate id,
@@ -59,22 +316,32 @@ impl DshApiClient {
   /// * `Ok<(CertificateStatus, Vec<UsedBy>>` - tuple containing the certificate configuration
   ///   and a vector of usages, which can be empty.
   /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
-  pub async fn certificate_with_dependant_apps(&self, certificate_id: &str) -> DshApiResult<(CertificateStatus, Vec<DependantApp>)> {
+  pub async fn certificate_with_dependant_apps(&self, certificate_id: impl AsRef<str>) -> DshApiResult<(CertificateStatus, Vec<DependantApp>)> {
+    let certificate_id = certificate_id.as_ref();
     let (certificate_status, appcatalogapp_configuration_map): (CertificateStatus, HashMap<String, AppCatalogApp>) =
       try_join!(self.get_certificate(certificate_id), self.get_appcatalogapp_configuration_map())?;
-    let mut dependants: Vec<DependantApp> = vec![];
-    if let Some(ref configuration) = certificate_status.configuration {
-      let secrets = match configuration.passphrase_secret {
-        Some(ref passphrase_secret) => vec![configuration.cert_chain_secret.clone(), configuration.key_secret.clone(), passphrase_secret.clone()],
-        None => vec![configuration.cert_chain_secret.clone(), configuration.key_secret.clone()],
-      };
-      for (app_id, _, secret_resources) in secrets_resources_from_apps(&secrets, &appcatalogapp_configuration_map) {
-        dependants.push(DependantApp::new(app_id.clone(), secret_resources));
-      }
-    }
+    let dependants = certificate_dependant_apps_from_configurations(&certificate_status, &appcatalogapp_configuration_map);
     Ok((certificate_status, dependants))
   }
 
+  /// # Returns all certificates that expire within a number of days
+  ///
+  /// The expiry is taken from the actual certificates. Certificates that are not deployed
+  /// are ignored and already expired certificates are included.
+  ///
+  /// # Parameters
+  /// * `days` - number of days from now
+  /// * `concurrency` - maximum number of concurrent certificate requests,
+  ///   at least one request is executed
+  ///
+  /// # Returns
+  /// * `Ok<Vec<`[`CertificateExpiry`]`>>` - expiring certificates, sorted by expiry moment
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn certificates_expiring_within(&self, days: u32, concurrency: usize) -> DshApiResult<Vec<CertificateExpiry>> {
+    let actual_certificates = self.actual_certificates(concurrency).await?;
+    Ok(expiring_certificates(&actual_certificates, now(), days))
+  }
+
   /// # List all certificates with usage
   ///
   /// Returns a list of all certificate configurations,
@@ -91,20 +358,103 @@ impl DshApiClient {
     let apps = self.get_appcatalogapp_configuration_map().await?;
     let mut certificates_with_usage: Vec<(String, CertificateStatus, Vec<DependantApp>)> = vec![];
     for (certificate_id, certificate_status) in certificate_ids.iter().zip(certificates) {
-      let mut dependant_apps: Vec<DependantApp> = vec![];
-      if let Some(ref configuration) = certificate_status.configuration {
-        let secrets = match configuration.passphrase_secret {
-          Some(ref passphrase_secret) => vec![configuration.cert_chain_secret.clone(), configuration.key_secret.clone(), passphrase_secret.clone()],
-          None => vec![configuration.cert_chain_secret.clone(), configuration.key_secret.clone()],
-        };
-        for (app_id, _, secret_resources) in secrets_resources_from_apps(&secrets, &apps) {
-          dependant_apps.push(DependantApp::new(app_id.clone(), secret_resources));
-        }
-      }
+      let dependant_apps = certificate_dependant_apps_from_configurations(&certificate_status, &apps);
       certificates_with_usage.push((certificate_id.clone(), certificate_status, dependant_apps));
     }
     Ok(certificates_with_usage)
   }
+
+  /// # Upload a certificate from pem material
+  ///
+  /// Validates the pem material, stores the private key and the certificate chain as secrets
+  /// with generated names and creates or replaces the certificate configuration
+  /// that refers to these secrets. When one of the steps fails, the secrets that were already
+  /// created will be deleted again. The secrets of a replaced certificate configuration
+  /// are not deleted, since they might still be used.
+  ///
+  /// # Parameters
+  /// * `certificate_id` - id of the certificate to create or replace
+  /// * `certificate_pem` - pem material of the certificate
+  ///
+  /// # Returns
+  /// * `Ok<`[`Certificate`]`>` - the created certificate configuration
+  /// * `Err<`[`DshApiError`]`>` - when the pem material is not valid,
+  ///   or when the request could not be processed by the DSH
+  pub async fn upload_certificate(&self, certificate_id: impl AsRef<str>, certificate_pem: &CertificatePem) -> DshApiResult<Certificate> {
+    let certificate_id = certificate_id.as_ref();
+    certificate_pem.validate()?;
+    let (cert_chain_secret, key_secret) = certificate_secret_names(certificate_id, now().timestamp());
+    let secret_ids = self.get_secret_ids().await?;
+    if let Some(existing_secret) = [&cert_chain_secret, &key_secret].into_iter().find(|secret| secret_ids.contains(secret)) {
+      return Err(DshApiError::Parameter(format!("secret '{}' already exists", existing_secret)));
+    }
+    self.post_secret(&Secret::new(key_secret.as_str(), certificate_pem.private_key.as_str())).await?;
+    if let Err(error) = self.post_secret(&Secret::new(cert_chain_secret.as_str(), certificate_pem.cert_chain())).await {
+      self.delete_secrets_after_failure(&[key_secret.as_str()]).await;
+      return Err(error);
+    }
+    let certificate = Certificate::builder()
+      .cert_chain_secret(cert_chain_secret.as_str())
+      .key_secret(key_secret.as_str())
+      .build()?;
+    if let Err(error) = self.put_certificate_configuration(certificate_id, &certificate).await {
+      self.delete_secrets_after_failure(&[key_secret.as_str(), cert_chain_secret.as_str()]).await;
+      return Err(error);
+    }
+    Ok(certificate)
+  }
+
+  /// # Returns all vhosts that are not covered by a certificate
+  ///
+  /// Returns the vhosts referenced in the `exposedPorts` sections of the applications
+  /// for which none of the domains is matched by the dns names of the actual certificates.
+  ///
+  /// # Parameters
+  /// * `concurrency` - maximum number of concurrent certificate requests,
+  ///   at least one request is executed
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(String, Vec<DependantApplication<VhostInjection>>)>>` - list of tuples
+  ///   containing the vhost ids and the applications that expose them, sorted by vhost id
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn vhosts_without_certificate(&self, concurrency: usize) -> DshApiResult<Vec<(String, Vec<DependantApplication<VhostInjection>>)>> {
+    let (applications, actual_certificates) = try_join!(self.get_application_configuration_map(), self.actual_certificates(concurrency))?;
+    let dns_names = actual_certificates
+      .iter()
+      .flat_map(|(_, actual_certificate)| actual_certificate.dns_names.iter().map(String::as_str))
+      .collect_vec();
+    Ok(vhosts_without_certificate_from_applications(
+      &applications,
+      &dns_names,
+      self.platform(),
+      self.tenant_name(),
+    ))
+  }
+
+  // Deletes secrets that were created before a failure, failures are logged but not reported
+  async fn delete_secrets_after_failure(&self, secret_ids: &[&str]) {
+    for secret_id in secret_ids {
+      if let Err(error) = self.delete_secret_configuration(secret_id).await {
+        warn!("could not delete secret '{}' after failure ({})", secret_id, error);
+      }
+    }
+  }
+
+  // Returns the ids and actual certificates of all deployed certificates, with at most `concurrency` requests in flight
+  async fn actual_certificates(&self, concurrency: usize) -> DshApiResult<Vec<(String, ActualCertificate)>> {
+    self
+      .stream_per_id(
+        self.get_certificate_ids(),
+        |certificate_id| async move {
+          let certificate_status = self.get_certificate(certificate_id.as_str()).await?;
+          Ok(certificate_status.actual.map(|actual_certificate| (certificate_id, actual_certificate)))
+        },
+        concurrency,
+      )
+      .try_filter_map(|actual_certificate| future::ready(Ok(actual_certificate)))
+      .try_collect()
+      .await
+  }
 }
 
 /// Get certificate resources from `AppCatalogApp`
@@ -123,3 +473,389 @@ pub fn certificate_resources_from_app(app: &AppCatalogApp) -> Vec<(&str, &Certif
     _ => None,
   })
 }
+
+/// # Generate the names of the secrets of a certificate
+///
+/// # Example
+///
+/// ```
+/// # use dsh_api::certificate::certificate_secret_names;
+/// assert_eq!(
+///   certificate_secret_names("my-certificate", 1735689600),
+///   ("my-certificate-cert-chain-1735689600".to_string(), "my-certificate-key-1735689600".to_string())
+/// );
+/// ```
+///
+/// # Parameters
+/// * `certificate_id` - id of the certificate
+/// * `suffix` - suffix that makes the names unique, typically a timestamp
+///
+/// # Returns
+/// `(String, String)` - tuple containing the names of the certificate chain secret and the key secret
+pub fn certificate_secret_names(certificate_id: &str, suffix: impl Display) -> (String, String) {
+  (format!("{}-cert-chain-{}", certificate_id, suffix), format!("{}-key-{}", certificate_id, suffix))
+}
+
+/// # Check if a dns name matches a host
+///
+/// The dns name can contain a wildcard as its left-most label,
+/// which matches exactly one label of the host. The comparison is case-insensitive.
+///
+/// # Example
+///
+/// ```
+/// # use dsh_api::certificate::dns_name_matches;
+/// assert!(dns_name_matches("*.my-tenant.dsh-dev.dsh.np.aws.kpn.com", "my-vhost.my-tenant.dsh-dev.dsh.np.aws.kpn.com"));
+/// assert!(!dns_name_matches("*.dsh-dev.dsh.np.aws.kpn.com", "my-vhost.my-tenant.dsh-dev.dsh.np.aws.kpn.com"));
+/// ```
+///
+/// # Parameters
+/// * `dns_name` - dns name from a certificate, possibly with a wildcard
+/// * `host` - fully qualified domain name of the host
+///
+/// # Returns
+/// * `true` - when `dns_name` matches `host`
+/// * `false` - otherwise
+pub fn dns_name_matches(dns_name: &str, host: &str) -> bool {
+  let (dns_name, host) = (dns_name.to_lowercase(), host.to_lowercase());
+  match dns_name.strip_prefix("*.") {
+    Some(dns_name_domain) => host
+      .split_once('.')
+      .is_some_and(|(host_label, host_domain)| !host_label.is_empty() && host_domain == dns_name_domain),
+    None => dns_name == host,
+  }
+}
+
+/// # Get the certificates that expire within a number of days
+///
+/// # Parameters
+/// * `certificates` - list of certificate ids and actual certificates
+/// * `moment` - moment from which the number of days is counted, typically now
+/// * `days` - number of days from `moment`
+///
+/// # Returns
+/// `Vec<`[`CertificateExpiry`]`>` - certificates that expire before `moment` plus `days`,
+/// including the already expired certificates, sorted by expiry moment
+pub fn expiring_certificates(certificates: &[(String, ActualCertificate)], moment: DateTime<Utc>, days: u32) -> Vec<CertificateExpiry> {
+  let deadline = moment + Duration::days(days as i64);
+  certificates
+    .iter()
+    .filter(|(_, actual_certificate)| actual_certificate.not_after < deadline)
+    .map(|(certificate_id, actual_certificate)| CertificateExpiry {
+      certificate_id: certificate_id.to_string(),
+      not_after: actual_certificate.not_after,
+      days_remaining: (actual_certificate.not_after - moment).num_days(),
+    })
+    .sorted_by(|expiry_a, expiry_b| (expiry_a.not_after, &expiry_a.certificate_id).cmp(&(expiry_b.not_after, &expiry_b.certificate_id)))
+    .collect_vec()
+}
+
+/// # Decode certificates in pem format
+///
+/// Blocks with another label than `CERTIFICATE` are ignored.
+///
+/// # Parameters
+/// * `pem` - one or more pem encoded certificates
+///
+/// # Returns
+/// * `Ok<Vec<`[`CertificateInfo`]`>>` - decoded certificates, in the order of `pem`
+/// * `Err<`[`DshApiError::Parameter`]`>` - when `pem` could not be decoded
+///   or does not contain any certificate
+pub fn parse_pem_certificates(pem: &str) -> DshApiResult<Vec<CertificateInfo>> {
+  let mut certificates = vec![];
+  for pem_block in Pem::iter_from_buffer(pem.as_bytes()) {
+    let pem_block = pem_block.map_err(|error| DshApiError::Parameter(format!("invalid pem ({})", error)))?;
+    if pem_block.label == "CERTIFICATE" {
+      let certificate = pem_block
+        .parse_x509()
+        .map_err(|error| DshApiError::Parameter(format!("invalid certificate ({})", error)))?;
+      certificates.push(certificate_info(&certificate)?);
+    }
+  }
+  if certificates.is_empty() {
+    Err(DshApiError::Parameter("pem does not contain a certificate".to_string()))
+  } else {
+    Ok(certificates)
+  }
+}
+
+/// # Get the domains of a vhost
+///
+/// Private vhosts map to the private domain of the tenant, when the platform has one.
+/// Other vhosts map to the public vhost domain of the platform and the public domain of the tenant.
+///
+/// # Parameters
+/// * `vhost` - parsed vhost string
+/// * `platform` - platform that the vhost is exposed on
+/// * `tenant_name` - tenant that exposes the vhost, unless the vhost string specifies a tenant
+///
+/// # Returns
+/// `Vec<String>` - fully qualified domain names that the vhost can be reached on
+pub fn vhost_domains(vhost: &VhostString, platform: &DshPlatform, tenant_name: &str) -> Vec<String> {
+  let tenant_name = vhost.tenant_name.as_deref().unwrap_or(tenant_name);
+  match vhost.zone.as_deref() {
+    Some("private") => platform.tenant_private_vhost_domain(tenant_name, &vhost.vhost_name).into_iter().collect_vec(),
+    _ => vec![platform.public_vhost_domain(&vhost.vhost_name), platform.tenant_public_app_domain(tenant_name, &vhost.vhost_name)],
+  }
+}
+
+/// # Get the vhosts from applications that are not covered by a certificate
+///
+/// # Parameters
+/// * `applications` - hashmap containing id/application pairs
+/// * `dns_names` - dns names of the available certificates, possibly with wildcards
+/// * `platform` - platform that the applications run on
+/// * `tenant_name` - tenant that the applications belong to
+///
+/// # Returns
+/// `Vec<(String, Vec<DependantApplication<VhostInjection>>)>` - list of tuples containing
+/// the vhost ids that are not covered and the applications that expose them, sorted by vhost id
+pub fn vhosts_without_certificate_from_applications(
+  applications: &HashMap<String, Application>,
+  dns_names: &[&str],
+  platform: &DshPlatform,
+  tenant_name: &str,
+) -> Vec<(String, Vec<DependantApplication<VhostInjection>>)> {
+  let mut vhosts_map = HashMap::<String, Vec<DependantApplication<VhostInjection>>>::new();
+  for (application_id, application) in applications.iter().sorted_by_key(|(application_id, _)| *application_id) {
+    for (port, port_mapping) in application.exposed_ports.iter().sorted_by_key(|(port, _)| *port) {
+      if let Ok(vhost) = VhostString::try_from(port_mapping) {
+        let covered = vhost_domains(&vhost, platform, tenant_name)
+          .iter()
+          .any(|domain| dns_names.iter().any(|dns_name| dns_name_matches(dns_name, domain)));
+        if !covered {
+          vhosts_map.entry(vhost.vhost_name.clone()).or_default().push(DependantApplication::new(
+            application_id.to_string(),
+            application.instances,
+            vec![VhostInjection::Vhost(port.to_string(), vhost.zone.clone())],
+          ));
+        }
+      }
+    }
+  }
+  vhosts_map
+    .into_iter()
+    .sorted_by(|(vhost_id_a, _), (vhost_id_b, _)| vhost_id_a.cmp(vhost_id_b))
+    .collect_vec()
+}
+
+// Decodes the relevant fields of a parsed certificate
+fn certificate_info(certificate: &X509Certificate) -> DshApiResult<CertificateInfo> {
+  let subject_alternative_names = match certificate.subject_alternative_name() {
+    Ok(Some(extension)) => extension
+      .value
+      .general_names
+      .iter()
+      .filter_map(|general_name| match general_name {
+        GeneralName::DNSName(dns_name) => Some(dns_name.to_string()),
+        _ => None,
+      })
+      .collect_vec(),
+    Ok(None) => vec![],
+    Err(error) => return Err(DshApiError::Parameter(format!("invalid subject alternative names ({})", error))),
+  };
+  let public_key_info = certificate.public_key();
+  let key_type = if public_key_info.algorithm.algorithm == OID_SIG_ED25519 {
+    CertificateKeyType::Ed25519
+  } else {
+    match public_key_info.parsed() {
+      Ok(PublicKey::EC(ec_point)) => CertificateKeyType::Ec(ec_point.key_size()),
+      Ok(PublicKey::RSA(rsa_public_key)) => CertificateKeyType::Rsa(rsa_public_key.key_size()),
+      _ => CertificateKeyType::Other(public_key_info.algorithm.algorithm.to_id_string()),
+    }
+  };
+  Ok(CertificateInfo {
+    subject: certificate.subject().to_string(),
+    issuer: certificate.issuer().to_string(),
+    serial_number: certificate.raw_serial_as_string(),
+    subject_alternative_names,
+    not_before: timestamp_to_date_time(certificate.validity().not_before.timestamp())?,
+    not_after: timestamp_to_date_time(certificate.validity().not_after.timestamp())?,
+    key_type,
+    is_ca: certificate.is_ca(),
+  })
+}
+
+// Returns the pem blocks with the given label
+fn pem_blocks(pem: &str, label: &str) -> DshApiResult<Vec<Pem>> {
+  let mut pem_blocks = vec![];
+  for pem_block in Pem::iter_from_buffer(pem.as_bytes()) {
+    let pem_block = pem_block.map_err(|error| DshApiError::Parameter(format!("invalid pem ({})", error)))?;
+    if pem_block.label == label {
+      pem_blocks.push(pem_block);
+    }
+  }
+  Ok(pem_blocks)
+}
+
+// Reads a pem file
+fn read_pem_file(file: &Path) -> DshApiResult<String> {
+  fs::read_to_string(file).map_err(|error| DshApiError::Parameter(format!("could not read file '{}' ({})", file.display(), error)))
+}
+
+// Checks whether the private key belongs to the public key of the certificate
+fn private_key_matches(private_key: &str, certificate: &X509Certificate) -> DshApiResult<bool> {
+  // skip leading blocks, e.g. the `EC PARAMETERS` block that is written by `openssl ecparam -genkey`
+  let pem_block = Pem::iter_from_buffer(private_key.as_bytes())
+    .map(|pem_block| pem_block.map_err(|error| DshApiError::Parameter(format!("invalid private key ({})", error))))
+    .find(|pem_block| pem_block.as_ref().map_or(true, |pem_block| pem_block.label.ends_with("PRIVATE KEY")))
+    .ok_or(DshApiError::Parameter("private key does not contain a private key pem block".to_string()))??;
+  let (is_rsa, key) = match pem_block.label.as_str() {
+    "RSA PRIVATE KEY" => (true, pem_block.contents.clone()),
+    "EC PRIVATE KEY" => (false, pem_block.contents.clone()),
+    "PRIVATE KEY" => {
+      let (_, private_key_info) = parse_der(&pem_block.contents).map_err(invalid_private_key)?;
+      let private_key_info = private_key_info.as_sequence().map_err(invalid_private_key)?;
+      let algorithm = private_key_info
+        .get(1)
+        .and_then(|algorithm| algorithm.as_sequence().ok())
+        .and_then(|algorithm| algorithm.first())
+        .and_then(|algorithm| algorithm.as_oid().ok())
+        .ok_or_else(|| invalid_private_key(()))?;
+      let key = private_key_info.get(2).and_then(|key| key.as_slice().ok()).ok_or_else(|| invalid_private_key(()))?;
+      if *algorithm == OID_PKCS1_RSAENCRYPTION {
+        (true, key.to_vec())
+      } else if *algorithm == OID_KEY_TYPE_EC_PUBLIC_KEY {
+        (false, key.to_vec())
+      } else {
+        return Err(DshApiError::Parameter(format!("unsupported private key algorithm ({})", algorithm.to_id_string())));
+      }
+    }
+    label => return Err(DshApiError::Parameter(format!("unsupported private key type ({})", label.to_lowercase()))),
+  };
+  let (_, key) = parse_der(&key).map_err(invalid_private_key)?;
+  let key = key.as_sequence().map_err(invalid_private_key)?;
+  let public_key = certificate.public_key();
+  match public_key.parsed() {
+    Ok(PublicKey::RSA(rsa_public_key)) if is_rsa => {
+      let (modulus, exponent) = match (key.get(1), key.get(2)) {
+        (Some(modulus), Some(exponent)) => (modulus.as_slice().map_err(invalid_private_key)?, exponent.as_slice().map_err(invalid_private_key)?),
+        _ => return Err(invalid_private_key(())),
+      };
+      Ok(trim_leading_zeros(modulus) == trim_leading_zeros(rsa_public_key.modulus) && trim_leading_zeros(exponent) == trim_leading_zeros(rsa_public_key.exponent))
+    }
+    Ok(PublicKey::EC(ec_point)) if !is_rsa => {
+      let ec_public_key = key
+        .iter()
+        .find(|element| element.header.class() == Class::ContextSpecific && element.header.tag().0 == 1)
+        .and_then(|element| element.as_slice().ok())
+        .and_then(|element| parse_der(element).ok())
+        .and_then(|(_, element): (_, BerObject)| match element.content {
+          BerObjectContent::BitString(_, bit_string) => Some(bit_string.data.to_vec()),
+          _ => None,
+        })
+        .ok_or(DshApiError::Parameter("private key does not contain the public key".to_string()))?;
+      Ok(ec_public_key.as_slice() == ec_point.data())
+    }
+    _ => Ok(false),
+  }
+}
+
+fn invalid_private_key<E>(_: E) -> DshApiError {
+  DshApiError::Parameter("invalid private key".to_string())
+}
+
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+  let leading_zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+  &bytes[leading_zeros..]
+}
+
+// Checks that each certificate is issued and signed by its successor and that the chain is complete,
+// i.e. that the last certificate is self-signed or issued by one of the omitted roots
+fn validate_chain(certificates: &[X509Certificate], omitted_roots: &[String]) -> DshApiResult<()> {
+  for (certificate, issuer) in certificates.iter().tuple_windows() {
+    if certificate.issuer().as_raw() != issuer.subject().as_raw() {
+      return if certificates.iter().any(|other| other.subject().as_raw() == certificate.issuer().as_raw()) {
+        Err(DshApiError::Parameter(format!(
+          "certificate chain is not ordered, '{}' is not issued by '{}'",
+          certificate.subject(),
+          issuer.subject()
+        )))
+      } else {
+        Err(DshApiError::Parameter(format!(
+          "certificate chain is not complete, issuer '{}' is missing",
+          certificate.issuer()
+        )))
+      };
+    }
+    if !issuer.is_ca() {
+      return Err(DshApiError::Parameter(format!(
+        "'{}' in certificate chain is not a certificate authority",
+        issuer.subject()
+      )));
+    }
+    verify_signature(certificate, issuer)?;
+  }
+  match certificates.last() {
+    Some(last) if last.issuer().as_raw() == last.subject().as_raw() => verify_signature(last, last),
+    Some(last) if !omitted_roots.iter().any(|omitted_root| is_distinguished_name(omitted_root, last.issuer())) => Err(DshApiError::Parameter(format!(
+      "certificate chain is not complete, issuer '{}' is missing",
+      last.issuer()
+    ))),
+    _ => Ok(()),
+  }
+}
+
+// Checks that the certificate is signed with the key of the issuer
+fn verify_signature(certificate: &X509Certificate, issuer: &X509Certificate) -> DshApiResult<()> {
+  certificate.verify_signature(Some(issuer.public_key())).map_err(|error| {
+    DshApiError::Parameter(format!(
+      "signature of '{}' is not valid for issuer '{}' ({})",
+      certificate.subject(),
+      issuer.subject(),
+      error
+    ))
+  })
+}
+
+// Checks whether a distinguished name, formatted as described in `CertificatePem::with_omitted_root`,
+// denotes the same name as `name`
+fn is_distinguished_name(distinguished_name: &str, name: &X509Name) -> bool {
+  let mut attributes = vec![];
+  let mut attribute = String::new();
+  let mut characters = distinguished_name.chars();
+  while let Some(character) = characters.next() {
+    match character {
+      '\\' => attribute.extend(characters.next()),
+      ',' => attributes.push(std::mem::take(&mut attribute)),
+      _ => attribute.push(character),
+    }
+  }
+  attributes.push(attribute);
+  let attributes = attributes
+    .iter()
+    .map(|attribute| attribute.split_once('=').map(|(key, value)| (key.trim().to_uppercase(), value.trim().to_string())))
+    .collect::<Option<Vec<_>>>();
+  let name_attributes = name
+    .iter_attributes()
+    .map(|attribute| {
+      let key = oid2abbrev(attribute.attr_type(), oid_registry()).map_or_else(|_| attribute.attr_type().to_id_string(), str::to_string);
+      let value = match attribute.as_str() {
+        Ok(value) => value.trim().to_string(),
+        Err(_) => format!("#{}", attribute.as_slice().iter().map(|byte| format!("{:02x}", byte)).collect::<String>()),
+      };
+      (key.to_uppercase(), value)
+    })
+    .sorted()
+    .collect_vec();
+  attributes.is_some_and(|attributes| attributes.into_iter().sorted().collect_vec() == name_attributes)
+}
+
+fn timestamp_to_date_time(timestamp: i64) -> DshApiResult<DateTime<Utc>> {
+  DateTime::<Utc>::from_timestamp(timestamp, 0).ok_or(DshApiError::Parameter(format!("invalid timestamp in certificate ({})", timestamp)))
+}
+
+// Returns the apps that use the secrets of a certificate, from the app configurations
+pub(crate) fn certificate_dependant_apps_from_configurations(certificate_status: &CertificateStatus, apps: &HashMap<String, AppCatalogApp>) -> Vec<DependantApp> {
+  let mut dependant_apps: Vec<DependantApp> = vec![];
+  if let Some(ref configuration) = certificate_status.configuration {
+    let secrets = match configuration.passphrase_secret {
+      Some(ref passphrase_secret) => vec![configuration.cert_chain_secret.clone(), configuration.key_secret.clone(), passphrase_secret.clone()],
+      None => vec![configuration.cert_chain_secret.clone(), configuration.key_secret.clone()],
+    };
+    for (app_id, _, secret_resources) in secrets_resources_from_apps(&secrets, apps) {
+      dependant_apps.push(DependantApp::new(app_id.clone(), secret_resources));
+    }
+  }
+  dependant_apps
+}
@@ -0,0 +1,258 @@
+//! # Additional methods to manage the data catalog
+//!
+//! Module that contains methods and functions to manage the assets in the data catalog.
+//! The data catalog supports assets of two kinds, buckets and writable streams.
+//! Assets of kind `writablestream` refer to the topics of the tenant.
+//!
+//! # Generated methods
+//!
+//! [`DshApiClient`] methods that are generated from the `openapi` specification.
+//!
+//! * [`delete_datacatalog_asset_configuration(kind, name)`](DshApiClient::delete_datacatalog_asset_configuration)
+//! * [`get_datacatalog_asset(kind, name) -> DataCatalogAssetStatus`](DshApiClient::get_datacatalog_asset)
+//! * [`get_datacatalog_asset_configuration(kind, name) -> DataCatalogAsset`](DshApiClient::get_datacatalog_asset_configuration)
+//! * [`get_datacatalog_asset_ids(kind) -> [name]`](DshApiClient::get_datacatalog_asset_ids)
+//! * [`put_datacatalog_asset_configuration(kind, name, body)`](DshApiClient::put_datacatalog_asset_configuration)
+//!
+//! # Derived methods
+//!
+//! [`DshApiClient`] methods that add extra capabilities but do not directly call the
+//! DSH resource management API. These derived methods depend on the API methods for this.
+//!
+//! * [`data_catalog_asset_ids() -> [(kind, name)]`](DshApiClient::data_catalog_asset_ids)
+//! * [`data_catalog_assets() -> [(kind, name, asset status)]`](DshApiClient::data_catalog_assets)
+//! * [`data_catalog_discrepancies() -> discrepancies`](DshApiClient::data_catalog_discrepancies)
+//! * [`register_data_catalog_asset(kind, name)`](DshApiClient::register_data_catalog_asset)
+//! * [`unregister_data_catalog_asset(kind, name)`](DshApiClient::unregister_data_catalog_asset)
+//!
+//! # Functions
+//!
+//! * [`data_catalog_discrepancies_from_ids(asset ids, bucket ids, topic ids) -> discrepancies`](data_catalog_discrepancies_from_ids)
+
+use crate::dsh_api_client::DshApiClient;
+use crate::types::{
+  DataCatalogAsset, DataCatalogAssetStatus, DeleteDatacatalogAssetConfigurationByTenantByKindByNameKind, GetDatacatalogAssetByTenantByKindByNameKind,
+  GetDatacatalogAssetByTenantByKindKind, GetDatacatalogAssetConfigurationByTenantByKindByNameKind, PutDatacatalogAssetConfigurationByTenantByKindByNameKind,
+};
+use crate::{DshApiError, DshApiResult};
+use futures::future::try_join_all;
+use futures::try_join;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// # Kind of data catalog asset
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum DataCatalogAssetKind {
+  /// Bucket asset, where the name of the asset is the bucket id
+  #[serde(rename = "bucket")]
+  Bucket,
+  /// Writable stream asset, where the name of the asset is the topic id
+  #[serde(rename = "writablestream")]
+  WritableStream,
+}
+
+impl DataCatalogAssetKind {
+  /// All data catalog asset kinds
+  pub const ALL: [DataCatalogAssetKind; 2] = [DataCatalogAssetKind::Bucket, DataCatalogAssetKind::WritableStream];
+
+  /// # Returns the kind as used in the DSH resource management API
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      DataCatalogAssetKind::Bucket => "bucket",
+      DataCatalogAssetKind::WritableStream => "writablestream",
+    }
+  }
+}
+
+impl Display for DataCatalogAssetKind {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
+
+impl FromStr for DataCatalogAssetKind {
+  type Err = DshApiError;
+
+  fn from_str(kind: &str) -> Result<Self, Self::Err> {
+    match kind {
+      "bucket" => Ok(DataCatalogAssetKind::Bucket),
+      "writablestream" => Ok(DataCatalogAssetKind::WritableStream),
+      _ => Err(DshApiError::Parameter(format!("illegal data catalog asset kind '{}'", kind))),
+    }
+  }
+}
+
+macro_rules! from_data_catalog_asset_kind {
+  ($kind_type:ident) => {
+    impl From<DataCatalogAssetKind> for $kind_type {
+      fn from(kind: DataCatalogAssetKind) -> Self {
+        match kind {
+          DataCatalogAssetKind::Bucket => $kind_type::Bucket,
+          DataCatalogAssetKind::WritableStream => $kind_type::Writablestream,
+        }
+      }
+    }
+  };
+}
+
+from_data_catalog_asset_kind!(DeleteDatacatalogAssetConfigurationByTenantByKindByNameKind);
+from_data_catalog_asset_kind!(GetDatacatalogAssetByTenantByKindByNameKind);
+from_data_catalog_asset_kind!(GetDatacatalogAssetByTenantByKindKind);
+from_data_catalog_asset_kind!(GetDatacatalogAssetConfigurationByTenantByKindByNameKind);
+from_data_catalog_asset_kind!(PutDatacatalogAssetConfigurationByTenantByKindByNameKind);
+
+/// # Describes the differences between the data catalog and the resources
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct DataCatalogDiscrepancies {
+  /// Assets in the data catalog whose bucket or topic does not exist, sorted by kind and name
+  pub orphan_assets: Vec<(DataCatalogAssetKind, String)>,
+  /// Buckets and topics that are not registered in the data catalog, sorted by kind and name
+  pub unregistered_resources: Vec<(DataCatalogAssetKind, String)>,
+}
+
+impl DataCatalogDiscrepancies {
+  /// # Check if there are any discrepancies
+  ///
+  /// # Returns
+  /// * `true` - the data catalog and the resources are consistent
+  /// * `false` - there are orphan assets or unregistered resources
+  pub fn is_empty(&self) -> bool {
+    self.orphan_assets.is_empty() && self.unregistered_resources.is_empty()
+  }
+}
+
+/// # Additional methods to manage the data catalog
+///
+/// Module that contains methods to manage the assets in the data catalog.
+/// * Derived methods - DshApiClient methods that add extra capabilities
+///   but depend on the API methods.
+///
+/// # Derived methods
+///
+/// [`DshApiClient`] methods that add extra capabilities but do not directly call the
+/// DSH resource management API. These derived methods depend on the API methods for this.
+///
+/// * [`data_catalog_asset_ids() -> [(kind, name)]`](DshApiClient::data_catalog_asset_ids)
+/// * [`data_catalog_assets() -> [(kind, name, asset status)]`](DshApiClient::data_catalog_assets)
+/// * [`data_catalog_discrepancies() -> discrepancies`](DshApiClient::data_catalog_discrepancies)
+/// * [`register_data_catalog_asset(kind, name)`](DshApiClient::register_data_catalog_asset)
+/// * [`unregister_data_catalog_asset(kind, name)`](DshApiClient::unregister_data_catalog_asset)
+impl DshApiClient {
+  /// # Returns the ids of all data catalog assets
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(`[`DataCatalogAssetKind`]`, String)>>` - list of the kinds and names
+  ///   of all assets, sorted by kind and name
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn data_catalog_asset_ids(&self) -> DshApiResult<Vec<(DataCatalogAssetKind, String)>> {
+    let names_per_kind = try_join_all(DataCatalogAssetKind::ALL.iter().map(|kind| self.get_datacatalog_asset_ids((*kind).into()))).await?;
+    Ok(
+      DataCatalogAssetKind::ALL
+        .into_iter()
+        .zip(names_per_kind)
+        .flat_map(|(kind, names)| names.into_iter().sorted().map(move |name| (kind, name)))
+        .collect_vec(),
+    )
+  }
+
+  /// # Returns all data catalog assets with their status
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(`[`DataCatalogAssetKind`]`, String, `[`DataCatalogAssetStatus`]`)>>` - list of the kinds,
+  ///   names and statuses of all assets, sorted by kind and name
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn data_catalog_assets(&self) -> DshApiResult<Vec<(DataCatalogAssetKind, String, DataCatalogAssetStatus)>> {
+    let asset_ids = self.data_catalog_asset_ids().await?;
+    let asset_statuses = try_join_all(asset_ids.iter().map(|(kind, name)| self.get_datacatalog_asset((*kind).into(), name))).await?;
+    Ok(
+      asset_ids
+        .into_iter()
+        .zip(asset_statuses)
+        .map(|((kind, name), asset_status)| (kind, name, asset_status))
+        .collect_vec(),
+    )
+  }
+
+  /// # Returns the differences between the data catalog and the resources
+  ///
+  /// See [`data_catalog_discrepancies_from_ids`] for a description of the checks.
+  ///
+  /// # Returns
+  /// * `Ok<`[`DataCatalogDiscrepancies`]`>` - the orphan assets and the unregistered resources
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn data_catalog_discrepancies(&self) -> DshApiResult<DataCatalogDiscrepancies> {
+    let (asset_ids, bucket_ids, topic_ids) = try_join!(self.data_catalog_asset_ids(), self.get_bucket_ids(), self.get_topic_ids())?;
+    Ok(data_catalog_discrepancies_from_ids(&asset_ids, &bucket_ids, &topic_ids))
+  }
+
+  /// # Register a bucket or topic in the data catalog
+  ///
+  /// # Parameters
+  /// * `kind` - kind of the asset
+  /// * `name` - id of the bucket or topic to register
+  ///
+  /// # Returns
+  /// * `Ok(())` - when the asset was registered
+  /// * `Err<`[`DshApiError::NotFound`]`>` - when the bucket or topic does not exist
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn register_data_catalog_asset(&self, kind: DataCatalogAssetKind, name: impl AsRef<str>) -> DshApiResult<()> {
+    let name = name.as_ref();
+    let resource_ids = match kind {
+      DataCatalogAssetKind::Bucket => self.get_bucket_ids().await?,
+      DataCatalogAssetKind::WritableStream => self.get_topic_ids().await?,
+    };
+    if !resource_ids.iter().any(|resource_id| resource_id == name) {
+      let resource_kind = match kind {
+        DataCatalogAssetKind::Bucket => "bucket",
+        DataCatalogAssetKind::WritableStream => "topic",
+      };
+      return Err(DshApiError::NotFound(Some(format!("{} '{}' does not exist", resource_kind, name))));
+    }
+    let asset = DataCatalogAsset { kind: kind.to_string(), name: name.to_string() };
+    self.put_datacatalog_asset_configuration(kind.into(), name, &asset).await
+  }
+
+  /// # Unregister an asset from the data catalog
+  ///
+  /// The underlying bucket or topic is not affected.
+  ///
+  /// # Parameters
+  /// * `kind` - kind of the asset
+  /// * `name` - name of the asset to unregister
+  ///
+  /// # Returns
+  /// * `Ok(())` - when the asset was unregistered
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn unregister_data_catalog_asset(&self, kind: DataCatalogAssetKind, name: impl AsRef<str>) -> DshApiResult<()> {
+    self.delete_datacatalog_asset_configuration(kind.into(), name).await
+  }
+}
+
+/// # Get the differences between the data catalog and the resources
+///
+/// Assets of kind `bucket` are compared with the bucket ids and assets of kind
+/// `writablestream` are compared with the topic ids.
+///
+/// # Parameters
+/// * `asset_ids` - kinds and names of all data catalog assets
+/// * `bucket_ids` - ids of all buckets of the tenant
+/// * `topic_ids` - ids of all topics of the tenant
+///
+/// # Returns
+/// [`DataCatalogDiscrepancies`] - the assets whose bucket or topic does not exist,
+/// and the buckets and topics that are not registered in the data catalog
+pub fn data_catalog_discrepancies_from_ids(asset_ids: &[(DataCatalogAssetKind, String)], bucket_ids: &[String], topic_ids: &[String]) -> DataCatalogDiscrepancies {
+  let assets = asset_ids.iter().map(|(kind, name)| (*kind, name.as_str())).collect::<HashSet<_>>();
+  let resources = bucket_ids
+    .iter()
+    .map(|bucket_id| (DataCatalogAssetKind::Bucket, bucket_id.as_str()))
+    .chain(topic_ids.iter().map(|topic_id| (DataCatalogAssetKind::WritableStream, topic_id.as_str())))
+    .collect::<HashSet<_>>();
+  DataCatalogDiscrepancies {
+    orphan_assets: assets.difference(&resources).map(|(kind, name)| (*kind, name.to_string())).sorted().collect_vec(),
+    unregistered_resources: resources.difference(&assets).map(|(kind, name)| (*kind, name.to_string())).sorted().collect_vec(),
+  }
+}
@@ -1,6 +1,6 @@
-//! # Additional methods to manage buckets
+//! # Additional methods to manage databases
 //!
-//! Module that contains methods and functions to manage buckets.
+//! Module that contains methods and functions to manage databases.
 //!
 //! # Generated methods
 //!
@@ -13,5 +13,512 @@
 //! * [`get_database_ids() -> [id]`](DshApiClient::get_database_ids)
 //! * [`get_database_status(id) -> AllocationStatus`](DshApiClient::get_database_status)
 //! * [`put_database_configuration(id, body)`](DshApiClient::put_database_configuration)
-#[allow(unused_imports)]
+//!
+//! # Derived methods
+//!
+//! [`DshApiClient`] methods that add extra capabilities but do not directly call the
+//! DSH resource management API. These derived methods depend on the API methods for this.
+//!
+//! * [`database_connection(database id) -> DatabaseConnection`](DshApiClient::database_connection)
+//! * [`database_diff(database id) -> Option<DatabaseDiff>`](DshApiClient::database_diff)
+//! * [`database_status_summaries() -> [DatabaseStatusSummary]`](DshApiClient::database_status_summaries)
+//! * [`database_with_dependants(database id) -> [database status, [(id, [injection|resource])]]`](DshApiClient::database_with_dependants)
+//! * [`databases_with_dependants() -> [database id, [(id, [injection|resource])]]`](DshApiClient::databases_with_dependants)
+//!
+//! # Functions
+//!
+//! * [`apps_that_use_database(database id, apps, manifests) -> [(app id, app, [resource id])]`](apps_that_use_database)
+//! * [`database_env_vars_from_application(database host, application) -> [env var]`](database_env_vars_from_application)
+//! * [`database_env_vars_from_applications(database host, applications) -> [(application id, application, [env var])]`](database_env_vars_from_applications)
+//! * [`database_resources_from_manifest(manifest) -> [(resource id, database resource)]`](database_resources_from_manifest)
+
+use crate::application_types::ApplicationValues;
 use crate::dsh_api_client::DshApiClient;
+use crate::manifest::{DatabaseResource, Manifest, Resource};
+use crate::platform::DshPlatform;
+use crate::types::{AppCatalogApp, Application, Database, DatabaseStatus};
+use crate::{Dependant, DshApiError, DshApiResult};
+use futures::future::try_join_all;
+use futures::try_join;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// Port on which the databases accept connections
+const DATABASE_PORT: u16 = 5432;
+
+/// # Describes an injection of a database in an application
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum DatabaseInjection {
+  /// Environment variable injection, where the value is the name of the environment variable.
+  #[serde(rename = "env")]
+  EnvVar(String),
+}
+
+impl Display for DatabaseInjection {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      DatabaseInjection::EnvVar(env_var) => write!(f, "{}", env_var),
+    }
+  }
+}
+
+/// # Describes how applications can connect to a database
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DatabaseConnection {
+  /// Identifies the database
+  pub database_id: String,
+  /// Host name of the database, within the tenant's internal domain
+  pub host: String,
+  /// Port on which the database accepts connections
+  pub port: u16,
+  /// Version of the database, when configured
+  pub version: Option<String>,
+}
+
+impl DatabaseConnection {
+  /// # Create a database connection descriptor
+  ///
+  /// # Parameters
+  /// * `platform` - platform that the database is deployed on
+  /// * `tenant_name` - name of the tenant that owns the database
+  /// * `database_id` - id of the database
+  /// * `database` - configuration of the database
+  pub fn new(platform: &DshPlatform, tenant_name: &str, database_id: &str, database: &Database) -> Self {
+    DatabaseConnection {
+      database_id: database_id.to_string(),
+      host: platform.internal_service_domain(tenant_name, database_id),
+      port: DATABASE_PORT,
+      version: database.version.clone(),
+    }
+  }
+
+  /// # Returns the environment variables for the database connection
+  ///
+  /// # Returns
+  /// `Vec<(String, String)>` - list of environment variable keys and values,
+  /// following the conventions of the PostgreSQL client libraries
+  pub fn environment_variables(&self) -> Vec<(String, String)> {
+    vec![("PGDATABASE".to_string(), self.database_id.clone()), ("PGHOST".to_string(), self.host.clone()), ("PGPORT".to_string(), self.port.to_string())]
+  }
+
+  /// # Returns the connection url for the database
+  pub fn url(&self) -> String {
+    format!("postgresql://{}:{}/{}", self.host, self.port, self.database_id)
+  }
+}
+
+impl Display for DatabaseConnection {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.url())
+  }
+}
+
+/// Structure that contains the differences between two `Database`s
+#[derive(Debug)]
+pub struct DatabaseDiff {
+  pub cpus: Option<(f64, f64)>,
+  pub extensions: Option<(Vec<String>, Vec<String>)>,
+  pub instances: Option<(i64, i64)>,
+  pub mem: Option<(i64, i64)>,
+  pub snapshot_interval: Option<(Option<i64>, Option<i64>)>,
+  pub version: Option<(Option<String>, Option<String>)>,
+  pub volume_size: Option<(i64, i64)>,
+}
+
+impl DatabaseDiff {
+  /// # Compare databases
+  ///
+  /// # Parameters
+  /// * `baseline` - baseline database to compare against
+  /// * `sample` - sample database that will be compared against the baseline
+  ///
+  /// # Returns
+  /// * [`DatabaseDiff`] - struct that describes the differences between the two [`Database`]s
+  pub fn differences_between_databases(baseline: &Database, sample: &Database) -> DatabaseDiff {
+    DatabaseDiff {
+      cpus: if baseline.cpus == sample.cpus { None } else { Some((baseline.cpus, sample.cpus)) },
+      extensions: if baseline.extensions.iter().sorted().eq(sample.extensions.iter().sorted()) { None } else { Some((baseline.extensions.clone(), sample.extensions.clone())) },
+      instances: if baseline.instances == sample.instances { None } else { Some((baseline.instances, sample.instances)) },
+      mem: if baseline.mem == sample.mem { None } else { Some((baseline.mem, sample.mem)) },
+      snapshot_interval: if baseline.snapshot_interval == sample.snapshot_interval { None } else { Some((baseline.snapshot_interval, sample.snapshot_interval)) },
+      version: if baseline.version == sample.version { None } else { Some((baseline.version.clone(), sample.version.clone())) },
+      volume_size: if baseline.volume_size == sample.volume_size { None } else { Some((baseline.volume_size, sample.volume_size)) },
+    }
+  }
+
+  /// # Check if there are any differences
+  ///
+  /// # Returns
+  /// * `true` - struct does not contain any differences
+  /// * `false` - struct does contain differences
+  pub fn is_empty(&self) -> bool {
+    self.cpus.is_none()
+      && self.extensions.is_none()
+      && self.instances.is_none()
+      && self.mem.is_none()
+      && self.snapshot_interval.is_none()
+      && self.version.is_none()
+      && self.volume_size.is_none()
+  }
+
+  /// # List the differences
+  ///
+  /// If there are no differences, an empty list will be returned.
+  ///
+  /// # Returns
+  /// * `Vec<(String, String)>` - list of key/value pairs describing all differences
+  pub fn differences(&self) -> Vec<(String, String)> {
+    vec![
+      self.cpus.as_ref().map(|value| ("cpus".to_string(), format!("{:?} / {:?}", value.0, value.1))),
+      self
+        .extensions
+        .as_ref()
+        .map(|value| ("extensions".to_string(), format!("{:?} / {:?}", value.0, value.1))),
+      self
+        .instances
+        .as_ref()
+        .map(|value| ("instances".to_string(), format!("{:?} / {:?}", value.0, value.1))),
+      self.mem.as_ref().map(|value| ("mem".to_string(), format!("{:?} / {:?}", value.0, value.1))),
+      self
+        .snapshot_interval
+        .as_ref()
+        .map(|value| ("snapshot interval".to_string(), format!("{:?} / {:?}", value.0, value.1))),
+      self.version.as_ref().map(|value| ("version".to_string(), format!("{:?} / {:?}", value.0, value.1))),
+      self
+        .volume_size
+        .as_ref()
+        .map(|value| ("volume size".to_string(), format!("{:?} / {:?}", value.0, value.1))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect_vec()
+  }
+}
+
+/// # Summarizes the status of a database
+#[derive(Debug)]
+pub struct DatabaseStatusSummary {
+  /// Identifies the database
+  pub database_id: String,
+  /// Whether the database is configured
+  pub configured: bool,
+  /// Whether the database is deployed
+  pub deployed: bool,
+  /// Whether the configuration and actual state of the database match
+  pub provisioned: bool,
+  /// Messages of the notifications reported for the database
+  pub notifications: Vec<String>,
+  /// Differences between the configuration and the actual state of the database,
+  /// when both are available
+  pub diff: Option<DatabaseDiff>,
+}
+
+impl DatabaseStatusSummary {
+  /// # Create a database status summary
+  ///
+  /// # Parameters
+  /// * `database_id` - id of the database
+  /// * `database_status` - status of the database, as returned by the DSH
+  pub fn new(database_id: &str, database_status: &DatabaseStatus) -> Self {
+    DatabaseStatusSummary {
+      database_id: database_id.to_string(),
+      configured: database_status.configuration.is_some(),
+      deployed: database_status.actual.is_some(),
+      provisioned: database_status.status.provisioned,
+      notifications: database_status
+        .status
+        .notifications
+        .iter()
+        .map(|notification| notification.message.to_string())
+        .collect_vec(),
+      diff: match (&database_status.configuration, &database_status.actual) {
+        (Some(configuration), Some(actual)) => Some(DatabaseDiff::differences_between_databases(configuration, actual)),
+        _ => None,
+      },
+    }
+  }
+
+  /// # Check if the database is healthy
+  ///
+  /// # Returns
+  /// * `true` - database is configured, deployed and provisioned,
+  ///   and the configuration matches the actual state
+  /// * `false` - otherwise
+  pub fn is_healthy(&self) -> bool {
+    self.configured && self.deployed && self.provisioned && self.diff.as_ref().is_some_and(|diff| diff.is_empty())
+  }
+}
+
+/// # Additional methods to manage databases
+///
+/// Module that contains methods to manage databases.
+/// * Derived methods - DshApiClient methods that add extra capabilities
+///   but depend on the API methods.
+///
+/// # Derived methods
+///
+/// [`DshApiClient`] methods that add extra capabilities but do not directly call the
+/// DSH resource management API. These derived methods depend on the API methods for this.
+///
+/// * [`database_connection(database id) -> DatabaseConnection`](DshApiClient::database_connection)
+/// * [`database_diff(database id) -> Option<DatabaseDiff>`](DshApiClient::database_diff)
+/// * [`database_status_summaries() -> [DatabaseStatusSummary]`](DshApiClient::database_status_summaries)
+/// * [`database_with_dependants(database id) -> [database status, [(id, [injection|resource])]]`](DshApiClient::database_with_dependants)
+/// * [`databases_with_dependants() -> [database id, [(id, [injection|resource])]]`](DshApiClient::databases_with_dependants)
+impl DshApiClient {
+  /// # Returns the connection descriptor for a database
+  ///
+  /// # Parameters
+  /// * `database_id` - id of the database
+  ///
+  /// # Returns
+  /// * `Ok<`[`DatabaseConnection`]`>` - describes how applications can connect to the database
+  /// * `Err<`[`DshApiError::NotFound`]`>` - when the database is not configured
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn database_connection(&self, database_id: impl AsRef<str>) -> DshApiResult<DatabaseConnection> {
+    let database_id = database_id.as_ref();
+    let database = self.get_database_configuration(database_id).await?;
+    Ok(DatabaseConnection::new(self.platform(), self.tenant_name(), database_id, &database))
+  }
+
+  /// # Compare the configuration and the actual state of a database
+  ///
+  /// # Parameters
+  /// * `database_id` - id of the database
+  ///
+  /// # Returns
+  /// * `Ok<Some<`[`DatabaseDiff`]`>>` - differences between the configuration (baseline)
+  ///   and the actual state (sample) of the database
+  /// * `Ok<None>` - when the database does not exist, or when it has no configuration or no actual state
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn database_diff(&self, database_id: impl AsRef<str>) -> DshApiResult<Option<DatabaseDiff>> {
+    match self.get_database(database_id).await {
+      Ok(database_status) => match (database_status.configuration, database_status.actual) {
+        (Some(configuration), Some(actual)) => Ok(Some(DatabaseDiff::differences_between_databases(&configuration, &actual))),
+        _ => Ok(None),
+      },
+      Err(DshApiError::NotFound(_)) => Ok(None),
+      Err(error) => Err(error),
+    }
+  }
+
+  /// # Returns the status summaries of all databases
+  ///
+  /// # Returns
+  /// * `Ok<Vec<`[`DatabaseStatusSummary`]`>>` - status summaries of all databases, sorted by database id
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn database_status_summaries(&self) -> DshApiResult<Vec<DatabaseStatusSummary>> {
+    let mut database_ids = self.get_database_ids().await?;
+    database_ids.sort();
+    let database_statuses = try_join_all(database_ids.iter().map(|database_id| self.get_database(database_id))).await?;
+    Ok(
+      database_ids
+        .iter()
+        .zip(database_statuses)
+        .map(|(database_id, database_status)| DatabaseStatusSummary::new(database_id, &database_status))
+        .collect_vec(),
+    )
+  }
+
+  /// # Get database with dependants
+  ///
+  /// Returns the status of a database, together with the applications and apps that use it.
+  ///
+  /// # Parameters
+  /// * `database_id` - id of the requested database
+  ///
+  /// # Returns
+  /// * `Ok<(DatabaseStatus, Vec<Dependant<DatabaseInjection>>)>` - database status and dependants
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn database_with_dependants(&self, database_id: impl AsRef<str>) -> DshApiResult<(DatabaseStatus, Vec<Dependant<DatabaseInjection>>)> {
+    let database_id = database_id.as_ref();
+    let (database_status, applications, apps, manifests) = try_join!(
+      self.get_database(database_id),
+      self.get_application_configuration_map(),
+      self.get_appcatalogapp_configuration_map(),
+      self.manifests()
+    )?;
+    let database_host = self.platform().internal_service_domain(self.tenant_name(), database_id);
+    let dependants = database_dependants_from_configurations(database_id, &database_host, &applications, &apps, &manifests);
+    Ok((database_status, dependants))
+  }
+
+  /// # Returns all databases with dependant applications and apps
+  ///
+  /// Returns a sorted list of all databases together with the applications and apps that use them.
+  pub async fn databases_with_dependants(&self) -> DshApiResult<Vec<(String, Vec<Dependant<DatabaseInjection>>)>> {
+    let (mut database_ids, applications, apps, manifests) = try_join!(
+      self.get_database_ids(),
+      self.get_application_configuration_map(),
+      self.get_appcatalogapp_configuration_map(),
+      self.manifests()
+    )?;
+    database_ids.sort();
+    Ok(
+      database_ids
+        .into_iter()
+        .map(|database_id| {
+          let database_host = self.platform().internal_service_domain(self.tenant_name(), &database_id);
+          let dependants = database_dependants_from_configurations(&database_id, &database_host, &applications, &apps, &manifests);
+          (database_id, dependants)
+        })
+        .collect_vec(),
+    )
+  }
+}
+
+// Returns the applications and apps that use a database, from the application, app and manifest configurations
+pub(crate) fn database_dependants_from_configurations(
+  database_id: &str,
+  database_host: &str,
+  applications: &HashMap<String, Application>,
+  apps: &HashMap<String, AppCatalogApp>,
+  manifests: &[Manifest],
+) -> Vec<Dependant<DatabaseInjection>> {
+  let mut dependants: Vec<Dependant<DatabaseInjection>> = vec![];
+  for ApplicationValues { id, application, values } in database_env_vars_from_applications(database_host, applications) {
+    dependants.push(Dependant::application(
+      id.to_string(),
+      application.instances,
+      values.iter().map(|env_var| DatabaseInjection::EnvVar(env_var.to_string())).collect_vec(),
+    ));
+  }
+  for (app_id, _, resource_ids) in apps_that_use_database(database_id, apps, manifests) {
+    dependants.push(Dependant::app(app_id.to_string(), resource_ids));
+  }
+  dependants
+}
+
+/// # Get application environment variables referencing a database
+///
+/// Get all environment variables from `application` whose value contains the host name of a database.
+/// The host name only matches as a whole, i.e. it must not be preceded by an alphanumeric character,
+/// `-` or `.`, and it must be followed by the end of the value, `:`, `/` or `?`.
+/// Hence `db.my-tenant.marathon.mesos` will not match `my-db.my-tenant.marathon.mesos`.
+///
+/// # Parameters
+/// * `database_host` - host name of the database to look for
+/// * `application` - reference to the `Application`
+///
+/// # Returns
+/// * `Vec<&str>` - list of all environment variables referencing the database
+///
+/// The list is sorted by environment variable key.
+pub fn database_env_vars_from_application<'a>(database_host: &str, application: &'a Application) -> Vec<&'a str> {
+  let mut env_vars = application
+    .env
+    .iter()
+    .filter_map(|(env_key, env_value)| if contains_host(env_value, database_host) { Some(env_key.as_str()) } else { None })
+    .collect_vec();
+  env_vars.sort();
+  env_vars
+}
+
+/// # Get applications environment variables referencing a database
+///
+/// Get all environment variables from multiple `Application`s referencing a database.
+/// Applications are only included if they reference the database at least once.
+///
+/// # Parameters
+/// * `database_host` - host name of the database to look for
+/// * `applications` - hashmap containing id/application pairs
+///
+/// # Returns
+/// `Vec<ApplicationValues<&str>>` - list of tuples containing:
+/// * application id
+/// * reference to application
+/// * list of environment variables referencing the database, sorted by environment variable key
+///
+/// The list is sorted by application id.
+pub fn database_env_vars_from_applications<'a>(database_host: &str, applications: &'a HashMap<String, Application>) -> Vec<ApplicationValues<'a, &'a str>> {
+  let mut application_tuples = applications
+    .iter()
+    .filter_map(|(application_id, application)| {
+      let injections = database_env_vars_from_application(database_host, application);
+      if !injections.is_empty() {
+        Some(ApplicationValues::new(application_id, application, injections))
+      } else {
+        None
+      }
+    })
+    .collect_vec();
+  application_tuples.sort();
+  application_tuples
+}
+
+/// Get database resources from `Manifest`
+///
+/// # Parameters
+/// * `manifest` - Reference to the manifest to get the database resources from.
+///
+/// # Returns
+/// List of tuples describing the database resources, sorted by resource id. Each tuple contains:
+/// * database resource id,
+/// * reference to the `DatabaseResource`.
+pub fn database_resources_from_manifest(manifest: &Manifest) -> Vec<(&str, &DatabaseResource)> {
+  let mut resources = manifest
+    .resources
+    .iter()
+    .filter_map(|(resource_id, resource)| match resource {
+      Resource::Database(database_resource) => Some((resource_id.as_str(), database_resource.as_ref())),
+      _ => None,
+    })
+    .collect_vec();
+  resources.sort_by_key(|(resource_id, _)| *resource_id);
+  resources
+}
+
+/// Find apps that use a given database
+///
+/// The app catalog app configurations do not contain the database resources.
+/// Therefore the database resources are taken from the manifest that the app was deployed from.
+/// The database id is the last element of the resource id, where `${@name}` is replaced by the app id.
+///
+/// # Parameters
+/// * `database_id` - Identifier of the database to look for.
+/// * `apps` - Hashmap of all apps.
+/// * `manifests` - All manifests from the app catalog.
+///
+/// # Returns
+/// `Vec<(app_id, app, resource_ids)>` - Vector of apps that use the database, sorted by app id:
+/// * `app_id` - App id of the app that uses the database,
+/// * `app` - Reference to the app,
+/// * `resource_ids` - Database resources of the database in the manifest of the app.
+pub fn apps_that_use_database<'a>(database_id: &str, apps: &'a HashMap<String, AppCatalogApp>, manifests: &[Manifest]) -> Vec<(&'a str, &'a AppCatalogApp, Vec<String>)> {
+  let mut tuples: Vec<(&str, &AppCatalogApp, Vec<String>)> = vec![];
+  for (app_id, app) in apps {
+    if let Some(manifest) = manifests
+      .iter()
+      .find(|manifest| app.manifest_urn == format!("appcatalog/manifest/{}/{}", manifest.id, manifest.version))
+    {
+      let resource_ids = database_resources_from_manifest(manifest)
+        .into_iter()
+        .filter(|(resource_id, _)| resource_id.rsplit('/').next().is_some_and(|id| id.replace("${@name}", app_id) == database_id))
+        .map(|(resource_id, _)| resource_id.to_string())
+        .collect_vec();
+      if !resource_ids.is_empty() {
+        tuples.push((app_id, app, resource_ids));
+      }
+    }
+  }
+  tuples.sort_by_key(|(app_id, _, _)| *app_id);
+  tuples
+}
+
+// Returns whether `value` contains `host` as a whole host name
+fn contains_host(value: &str, host: &str) -> bool {
+  !host.is_empty()
+    && value.match_indices(host).any(|(index, _)| {
+      let preceded_by_host_character = value[..index]
+        .chars()
+        .next_back()
+        .is_some_and(|preceding| preceding.is_ascii_alphanumeric() || preceding == '-' || preceding == '.');
+      let followed_by_host_end = value[index + host.len()..]
+        .chars()
+        .next()
+        .map_or(true, |following| following == ':' || following == '/' || following == '?');
+      !preceded_by_host_character && followed_by_host_end
+    })
+}
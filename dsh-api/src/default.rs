@@ -1,6 +1,12 @@
 //! # `Default` implementations for selected types
 //!
-//! This module provides implementations of the [`Default`] trait for selected types.
+//! This module provides implementations of the [`Default`] trait for the generated types.
+//! For most types the implementation is generated from the openapi specification at build time.
+//! Fields with a default value in the specification will get that value,
+//! optional fields will be `None` and all other fields will get their type's default value.
+//! No implementation is generated when a default value can not be determined for all fields,
+//! e.g. when a required field has an enumeration type with more than one value.
+//! The types listed below have a hand-written implementation.
 //!
 //! * [`ActualCertificate::default()`](ActualCertificate::default())
 //! * [`AllocationStatus::default()`](AllocationStatus::default())
@@ -46,13 +52,13 @@
 use crate::types::{
   ActualCertificate, AllocationStatus, AppCatalogApp, AppCatalogAppConfiguration, AppCatalogManifest, Application, ApplicationSecret, ApplicationVolumes, Bucket, BucketStatus,
   Certificate, CertificateStatus, Empty, HealthCheck, LimitValueCertificateCount, LimitValueCertificateCountName, LimitValueConsumerRate, LimitValueConsumerRateName,
-  LimitValueCpu, LimitValueCpuName, LimitValueKafkaAclGroupCount, LimitValueKafkaAclGroupCountName, LimitValueMem, LimitValueMemName, LimitValuePartitionCount,
-  LimitValuePartitionCountName, LimitValueProducerRate, LimitValueProducerRateName, LimitValueRequestRate, LimitValueRequestRateName, LimitValueSecretCount,
-  LimitValueSecretCountName, LimitValueTopicCount, LimitValueTopicCountName, ManagedStream, ManagedTenant, Metrics, Notification, PathSpec, PortMapping, PublicManagedStream,
-  PublicManagedStreamContract, PublicManagedStreamContractPartitioner, PublicManagedStreamKafkaDefaultPartitioner, PublicManagedStreamKafkaDefaultPartitionerKind, Secret, Task,
-  TaskState, TaskStatus, Topic, TopicStatus, Vhost, Volume, VolumeStatus,
+  LimitValueCpu, LimitValueCpuName, LimitValueMem, LimitValueMemName, LimitValuePartitionCount, LimitValuePartitionCountName, LimitValueProducerRate, LimitValueProducerRateName,
+  LimitValueRequestRate, LimitValueRequestRateName, LimitValueSecretCount, LimitValueSecretCountName, LimitValueTopicCount, LimitValueTopicCountName, ManagedTenant, Metrics,
+  Notification, PathSpec, PortMapping, PublicManagedStreamContract, PublicManagedStreamContractPartitioner, PublicManagedStreamKafkaDefaultPartitioner,
+  PublicManagedStreamKafkaDefaultPartitionerKind, Secret, Task, TaskState, TaskStatus, Topic, TopicStatus, Vhost, Volume, VolumeStatus,
 };
-
+#[cfg(not(dsh_api_1_8))]
+use crate::types::{LimitValueKafkaAclGroupCount, LimitValueKafkaAclGroupCountName, ManagedStream, PublicManagedStream};
 use std::net::Ipv4Addr;
 
 impl Default for ActualCertificate {
@@ -189,6 +195,7 @@ impl Default for LimitValueCpu {
   }
 }
 
+#[cfg(not(dsh_api_1_8))]
 impl Default for LimitValueKafkaAclGroupCount {
   fn default() -> Self {
     Self { name: LimitValueKafkaAclGroupCountName::KafkaAclGroupCount, value: 0 }
@@ -231,6 +238,7 @@ impl Default for LimitValueTopicCount {
   }
 }
 
+#[cfg(not(dsh_api_1_8))]
 #[allow(clippy::derivable_impls)]
 impl Default for ManagedStream {
   fn default() -> Self {
@@ -269,6 +277,7 @@ impl Default for PortMapping {
   }
 }
 
+#[cfg(not(dsh_api_1_8))]
 #[allow(clippy::derivable_impls)]
 impl Default for PublicManagedStream {
   fn default() -> Self {
@@ -356,3 +365,7 @@ impl Default for VolumeStatus {
     Self { actual: None, configuration: None, status: Default::default() }
   }
 }
+
+mod generated {
+  include!(concat!(env!("OUT_DIR"), "/generated_default.rs"));
+}
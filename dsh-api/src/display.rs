@@ -1,6 +1,10 @@
 //! # `Display` implementations for selected types
 //!
-//! This module provides implementations of the [`Display`] trait for selected types.
+//! This module provides implementations of the [`Display`] trait for the generated types.
+//! For most types the implementation is generated from the openapi specification at build time,
+//! in which case the fields of the value are listed as `name: value` pairs.
+//! When formatted with the alternate flag (`{:#}`), each field will be written on a separate line.
+//! The types listed below have a hand-written implementation.
 //! Note that if these implementations might change in a future version, this will not be
 //! considered a breaking change. Use these methods for documentation purposes only,
 //! not for business logic.
@@ -51,10 +55,14 @@
 use crate::types::{
   ActualCertificate, AllocationStatus, AppCatalogApp, AppCatalogAppConfiguration, AppCatalogAppResourcesValue, AppCatalogManifest, Application, ApplicationSecret,
   ApplicationVolumes, Bucket, BucketStatus, Certificate, CertificateStatus, Empty, HealthCheck, LimitValue, LimitValueCertificateCount, LimitValueConsumerRate, LimitValueCpu,
-  LimitValueKafkaAclGroupCount, LimitValueMem, LimitValuePartitionCount, LimitValueProducerRate, LimitValueRequestRate, LimitValueSecretCount, LimitValueTopicCount, ManagedStream,
-  ManagedStreamId, ManagedTenant, Metrics, Notification, PathSpec, PortMapping, PublicManagedStream, Secret, Task, TaskStatus, Topic, TopicStatus, Vhost, Volume, VolumeStatus,
+  LimitValueMem, LimitValuePartitionCount, LimitValueProducerRate, LimitValueRequestRate, LimitValueSecretCount, LimitValueTopicCount, ManagedStreamId, ManagedTenant, Metrics,
+  Notification, PathSpec, PortMapping, PublicManagedStream, Secret, Task, TaskStatus, Topic, TopicStatus, Vhost, Volume, VolumeStatus,
 };
+#[cfg(not(dsh_api_1_8))]
+use crate::types::{LimitValueKafkaAclGroupCount, ManagedStream};
 use itertools::Itertools;
+use serde::Serialize;
+use serde_json::Value;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
@@ -244,6 +252,7 @@ impl Display for LimitValue {
       LimitValue::CertificateCount(count) => write!(f, "{}", count),
       LimitValue::ConsumerRate(rate) => write!(f, "{}", rate),
       LimitValue::Cpu(cpu) => write!(f, "{}", cpu),
+      #[cfg(not(dsh_api_1_8))]
       LimitValue::KafkaAclGroupCount(count) => write!(f, "{}", count),
       LimitValue::Mem(mem) => write!(f, "{}", mem),
       LimitValue::PartitionCount(count) => write!(f, "{}", count),
@@ -273,6 +282,7 @@ impl Display for LimitValueCpu {
   }
 }
 
+#[cfg(not(dsh_api_1_8))]
 impl Display for LimitValueKafkaAclGroupCount {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     write!(f, "kafka acl groups: {}", self.value)
@@ -315,6 +325,7 @@ impl Display for LimitValueTopicCount {
   }
 }
 
+#[cfg(not(dsh_api_1_8))]
 impl Display for ManagedStream {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     write_topic(f, Some("internal"), self.partitions, self.replication_factor, &self.kafka_properties)?;
@@ -487,3 +498,37 @@ fn write_topic(f: &mut Formatter<'_>, kind: Option<&str>, partitions: i64, repli
   }
   Ok(())
 }
+
+/// # Write a serializable value as a list of fields
+///
+/// This function is used by the generated `Display` implementations.
+/// The value is serialized to json, after which the fields of the resulting json object
+/// are written as `name: value` pairs, separated by a comma or, when the alternate flag is set,
+/// by a new line. Fields without a value are skipped. Values that do not serialize to a json object
+/// are written as a string.
+pub(crate) fn write_serializable<T: Serialize>(f: &mut Formatter<'_>, value: &T) -> std::fmt::Result {
+  match serde_json::to_value(value).map_err(|_| std::fmt::Error)? {
+    Value::Object(fields) => {
+      let separator = if f.alternate() { "\n" } else { ", " };
+      let fields = fields
+        .iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(name, value)| format!("{}: {}", name, json_value_to_string(value)))
+        .join(separator);
+      write!(f, "{}", fields)
+    }
+    other => write!(f, "{}", json_value_to_string(&other)),
+  }
+}
+
+fn json_value_to_string(value: &Value) -> String {
+  match value {
+    Value::String(string) => string.to_string(),
+    other => other.to_string(),
+  }
+}
+
+mod generated {
+  use super::write_serializable;
+  include!(concat!(env!("OUT_DIR"), "/generated_display.rs"));
+}
@@ -47,13 +47,18 @@ use crate::dsh_jwt::DshJwt;
 use crate::generated::Client as GeneratedClient;
 use crate::platform::DshPlatform;
 use crate::token_fetcher::ManagementApiTokenFetcher;
+use crate::validation::{validate_response_body, ValidationError};
 use crate::{DshApiError, OPENAPI_SPEC};
 use bytes::Bytes;
 use futures::TryStreamExt;
-use log::{debug, log_enabled, trace, Level};
-use progenitor_client::{ByteStream, Error as ProgenitorError, ResponseValue as ProgenitorResponseValue};
+use log::{debug, log_enabled, trace, warn, Level};
+use progenitor_client::{encode_path, ByteStream, Error as ProgenitorError, ResponseValue as ProgenitorResponseValue};
+use reqwest::header::{HeaderValue, ACCEPT, AUTHORIZATION};
 use reqwest::StatusCode as ReqwestStatusCode;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
+use std::sync::Mutex;
 
 #[derive(Debug)]
 pub struct DshApiClient {
@@ -61,6 +66,8 @@ pub struct DshApiClient {
   token_fetcher: Option<ManagementApiTokenFetcher>,
   pub(crate) generated_client: GeneratedClient,
   tenant: DshApiTenant,
+  strict_mode: bool,
+  response_deviations: Mutex<HashMap<String, Vec<ValidationError>>>,
 }
 
 pub(crate) enum DshApiResponseStatus {
@@ -84,7 +91,7 @@ impl DshApiClient {
   /// # Returns
   /// * [DshApiClient] - The created dsh api client.
   pub(crate) fn from_static_token(static_token: String, generated_client: GeneratedClient, tenant: DshApiTenant) -> Self {
-    Self { static_token: Some(static_token), token_fetcher: None, generated_client, tenant }
+    Self { static_token: Some(static_token), token_fetcher: None, generated_client, tenant, strict_mode: false, response_deviations: Mutex::new(HashMap::new()) }
   }
 
   /// Create a `DshApiClient` from a token fetcher
@@ -97,7 +104,11 @@ impl DshApiClient {
   /// # Returns
   /// * [DshApiClient] - The created dsh api client.
   pub(crate) fn from_token_fetcher(token_fetcher: ManagementApiTokenFetcher, generated_client: GeneratedClient, tenant: DshApiTenant) -> Self {
-    Self { static_token: None, token_fetcher: Some(token_fetcher), generated_client, tenant }
+    Self { static_token: None, token_fetcher: Some(token_fetcher), generated_client, tenant, strict_mode: false, response_deviations: Mutex::new(HashMap::new()) }
+  }
+
+  pub(crate) fn with_strict_mode(self, strict_mode: bool) -> Self {
+    Self { strict_mode, ..self }
   }
 
   /// # Returns the openapi spec used to generate the client code
@@ -160,6 +171,98 @@ impl DshApiClient {
     }
   }
 
+  /// # Perform a `GET` request in strict mode
+  ///
+  /// Performs the request without the generated client, such that the raw response body
+  /// can be validated against the openapi specification before it is deserialized.
+  /// All deviations from the specification will be logged and collected.
+  /// Only path parameters are supported, the wrapper methods of operations with query
+  /// or header parameters always use the generated client.
+  ///
+  /// # Parameters
+  /// * `path` - path of the operation, the first path parameter will be substituted by the tenant
+  /// * `parameters` - names and values of the other path parameters
+  /// * `authorization` - value of the authorization header
+  pub(crate) async fn strict_get<T>(&self, path: &str, parameters: &[(&str, String)], authorization: &str) -> Result<ProgenitorResponseValue<T>, ProgenitorError>
+  where
+    T: DeserializeOwned,
+  {
+    let mut url_path = path.to_string();
+    if let Some(first_parameter) = path.split('/').find(|element| element.starts_with('{')) {
+      url_path = url_path.replacen(first_parameter, &encode_path(self.tenant_name()), 1);
+    }
+    for (parameter_name, parameter_value) in parameters {
+      url_path = url_path.replace(&format!("{{{}}}", parameter_name), &encode_path(parameter_value));
+    }
+    let request = self
+      .generated_client
+      .client()
+      .get(format!("{}{}", self.generated_client.baseurl(), url_path))
+      .header(ACCEPT, HeaderValue::from_static("application/json"))
+      .header(AUTHORIZATION, HeaderValue::try_from(authorization)?)
+      .build()?;
+    let response = self.generated_client.client().execute(request).await?;
+    let status = response.status();
+    if !status.is_success() {
+      return Err(ProgenitorError::UnexpectedResponse(response));
+    }
+    let headers = response.headers().clone();
+    let body = response.bytes().await.map_err(ProgenitorError::ResponseBodyError)?;
+    match serde_json::from_slice::<serde_json::Value>(&body) {
+      Ok(value) => match validate_response_body("get", path, status.as_u16(), &value) {
+        Ok(deviations) => self.add_response_deviations(format!("GET {}", path), deviations),
+        Err(error) => debug!("response for GET {} not validated ({})", path, error),
+      },
+      Err(error) => self.add_response_deviations(format!("GET {}", path), vec![ValidationError::new("$", format!("invalid json ({})", error))]),
+    }
+    match serde_json::from_slice::<T>(&body) {
+      Ok(inner) => Ok(ProgenitorResponseValue::new(inner, status, headers)),
+      Err(error) => Err(ProgenitorError::InvalidResponsePayload(body, error)),
+    }
+  }
+
+  fn add_response_deviations(&self, operation: String, deviations: Vec<ValidationError>) {
+    if !deviations.is_empty() {
+      let mut response_deviations = self.response_deviations.lock().unwrap();
+      let operation_deviations = response_deviations.entry(operation.clone()).or_default();
+      for deviation in deviations {
+        warn!("response for {} deviates from the openapi specification ({})", operation, deviation);
+        if !operation_deviations.contains(&deviation) {
+          operation_deviations.push(deviation);
+        }
+      }
+    }
+  }
+
+  /// # Returns whether strict mode is enabled
+  ///
+  /// In strict mode the response bodies of all `GET` operations are validated against the
+  /// openapi specification. Deviations are logged and can be retrieved via the
+  /// [`response_deviations()`](Self::response_deviations) method.
+  /// Strict mode can be enabled via the
+  /// [`DshApiClientFactory::with_strict_mode()`](crate::dsh_api_client_factory::DshApiClientFactory::with_strict_mode)
+  /// method.
+  pub fn strict_mode(&self) -> bool {
+    self.strict_mode
+  }
+
+  /// # Returns the collected response deviations
+  ///
+  /// # Returns
+  /// Map with all deviations from the openapi specification that were found in the responses
+  /// since the client was created or since the last call to
+  /// [`clear_response_deviations()`](Self::clear_response_deviations).
+  /// The keys of the map identify the operation (e.g. `GET /allocation/{tenant}/secret`).
+  /// The map will always be empty when strict mode is not enabled.
+  pub fn response_deviations(&self) -> HashMap<String, Vec<ValidationError>> {
+    self.response_deviations.lock().unwrap().clone()
+  }
+
+  /// # Clears the collected response deviations
+  pub fn clear_response_deviations(&self) {
+    self.response_deviations.lock().unwrap().clear()
+  }
+
   /// Returns the static token
   pub fn static_token(&self) -> &Option<String> {
     &self.static_token
@@ -64,6 +64,11 @@
 //! E.g. if the platform is `np-aws-lz-dsh` and the tenant name is
 //! `my-tenant`, the environment variable must be
 //! `DSH_API_PASSWORD_NP_AWS_LZ_DSH_MY_TENANT`.
+//!
+//! ## `DSH_API_STRICT_MODE`
+//! If this environment variable is set to `true`, the created clients will validate
+//! all responses against the openapi specification
+//! (see [`with_strict_mode()`](DshApiClientFactory::with_strict_mode)).
 use crate::dsh_api_client::DshApiClient;
 use crate::dsh_api_tenant::DshApiTenant;
 use crate::generated::Client as GeneratedClient;
@@ -80,6 +85,7 @@ pub struct DshApiClientFactory {
   tenant: DshApiTenant,
   access_token: Option<String>,
   robot_password: Option<String>,
+  strict_mode: bool,
 }
 
 impl DshApiClientFactory {
@@ -139,7 +145,13 @@ impl DshApiClientFactory {
   pub fn create_with_token_fetcher(tenant: DshApiTenant, robot_password: String) -> Self {
     let endpoint = tenant.platform().rest_api_endpoint();
     debug!("create dsh api client factory with token fetcher for '{}' at endpoint '{}'", tenant, endpoint);
-    DshApiClientFactory { generated_client: GeneratedClient::new(endpoint.as_str()), tenant, access_token: None, robot_password: Some(robot_password) }
+    DshApiClientFactory {
+      generated_client: GeneratedClient::new(endpoint.as_str()),
+      tenant,
+      access_token: None,
+      robot_password: Some(robot_password),
+      strict_mode: strict_mode_from_env(),
+    }
   }
 
   /// # Create factory for DSH API client with static access token
@@ -172,7 +184,13 @@ impl DshApiClientFactory {
   pub fn create_from_access_token(tenant: DshApiTenant, access_token: String) -> Self {
     let endpoint = tenant.platform().rest_api_endpoint();
     debug!("create dsh api client factory with static access token for '{}' at endpoint '{}'", tenant, endpoint);
-    DshApiClientFactory { generated_client: GeneratedClient::new(endpoint.as_str()), tenant, access_token: Some(access_token), robot_password: None }
+    DshApiClientFactory {
+      generated_client: GeneratedClient::new(endpoint.as_str()),
+      tenant,
+      access_token: Some(access_token),
+      robot_password: None,
+      strict_mode: strict_mode_from_env(),
+    }
   }
 
   /// # Create factory for DSH API client
@@ -276,6 +294,50 @@ impl DshApiClientFactory {
     }
   }
 
+  /// # Override the endpoint of the DSH resource management API
+  ///
+  /// By default the endpoint is derived from the platform
+  /// (see [`DshPlatform::rest_api_endpoint()`]).
+  /// This method can be used to target another endpoint, e.g. a proxy or a mock server.
+  ///
+  /// # Parameters
+  /// * `endpoint` - base url of the API, e.g. `http://localhost:8080/resources/v0`
+  pub fn with_endpoint(self, endpoint: &str) -> Self {
+    debug!("override dsh api client factory endpoint with '{}'", endpoint);
+    Self { generated_client: GeneratedClient::new(endpoint), ..self }
+  }
+
+  /// # Enable or disable strict mode
+  ///
+  /// In strict mode the created client will validate the response bodies of all `GET`
+  /// operations against the openapi specification. Deviations, like unknown fields,
+  /// missing required fields or wrong types, will be logged and collected per operation.
+  /// They can be retrieved via the
+  /// [`DshApiClient::response_deviations()`] method.
+  /// Strict mode is disabled by default, unless the environment variable
+  /// [`DSH_API_STRICT_MODE`](index.html#dsh_api_strict_mode) is set to `true`.
+  ///
+  /// # Parameters
+  /// * `strict_mode` - whether strict mode will be enabled
+  ///
+  /// # Examples
+  /// ```no_run
+  /// use dsh_api::dsh_api_client_factory::DshApiClientFactory;
+  ///
+  /// # use dsh_api::DshApiError;
+  /// # async fn hide() -> Result<(), DshApiError> {
+  /// let client = DshApiClientFactory::new().with_strict_mode(true).client().await?;
+  /// let _ = client.get_application_configuration_map().await?;
+  /// for (operation, deviations) in client.response_deviations() {
+  ///   println!("{}: {} deviations", operation, deviations.len());
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn with_strict_mode(self, strict_mode: bool) -> Self {
+    Self { strict_mode, ..self }
+  }
+
   /// # Returns the factories platform
   pub fn platform(&self) -> &DshPlatform {
     self.tenant.platform()
@@ -320,14 +382,14 @@ impl DshApiClientFactory {
         .client_secret(robot_password)
         .build()
       {
-        Ok(token_fetcher) => Ok(DshApiClient::from_token_fetcher(token_fetcher, self.generated_client, self.tenant.clone())),
+        Ok(token_fetcher) => Ok(DshApiClient::from_token_fetcher(token_fetcher, self.generated_client, self.tenant.clone()).with_strict_mode(self.strict_mode)),
         Err(rest_token_error) => Err(DshApiError::Unexpected(
           format!("could not create token fetcher ({})", rest_token_error),
           Some(rest_token_error.to_string()),
         )),
       }
     } else if let Some(access_token) = self.access_token {
-      Ok(DshApiClient::from_static_token(access_token, self.generated_client, self.tenant.clone()))
+      Ok(DshApiClient::from_static_token(access_token, self.generated_client, self.tenant.clone()).with_strict_mode(self.strict_mode))
     } else {
       unreachable!()
     }
@@ -353,6 +415,12 @@ impl Default for DshApiClientFactory {
   }
 }
 
+const ENV_VAR_STRICT_MODE: &str = "DSH_API_STRICT_MODE";
+
+fn strict_mode_from_env() -> bool {
+  env::var(ENV_VAR_STRICT_MODE).is_ok_and(|strict_mode| strict_mode.eq_ignore_ascii_case("true"))
+}
+
 const ENV_VAR_ACCESS_TOKEN_PREFIX: &str = "DSH_API_ACCESS_TOKEN";
 const ENV_VAR_ACCESS_TOKEN__FILE_PREFIX: &str = "DSH_API_ACCESS_TOKEN_FILE";
 
@@ -348,8 +348,8 @@ fn test_dsh_permission_from_str() {
   let dsh_permission = DshPermission::from_str("manage:dev-lz-dsh:greenbox-dev:view").unwrap();
   assert_eq!(dsh_permission.realm, "dev-lz-dsh");
   assert_eq!(dsh_permission.tenant, "greenbox-dev");
-  assert_eq!(dsh_permission.manage, false);
-  assert_eq!(dsh_permission.view, true);
+  assert!(!dsh_permission.manage);
+  assert!(dsh_permission.view);
 }
 
 #[test]
@@ -0,0 +1,380 @@
+//! # In-memory fake of the DSH resource management API
+//!
+//! Module that contains the [`FakeDshApiClient`], which implements the traits from the
+//! [`api`](crate::api) module on an in-memory store, without any network traffic.
+//! It can be used in unit tests of code that depends on these traits,
+//! including the derived methods of the [`DshApi`](crate::api::DshApi) trait.
+//!
+//! The fake models the state of the resources:
+//!
+//! * Creating or updating a resource stores its configuration and sets its allocation status
+//!   to provisioned.
+//! * Getting or deleting a resource that does not exist results in a
+//!   [`DshApiError::NotFound`] error.
+//! * Creating a secret that already exists results in a [`DshApiError::BadRequest`] error.
+//! * The allocation status of a resource can be changed with
+//!   [`set_status()`](FakeDshApiClient::set_status), e.g. to test how code handles resources
+//!   that are not provisioned.
+//!
+//! App catalog apps can not be created via the API, so they are inserted with
+//! [`insert_appcatalogapp()`](FakeDshApiClient::insert_appcatalogapp).
+//!
+//! This module is only available when the `testing` feature is enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use dsh_api::api::{DshApi, SecretApi, VolumeApi};
+//! use dsh_api::fake::FakeDshApiClient;
+//! use dsh_api::types::{Secret, Volume};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), dsh_api::DshApiError> {
+//! let fake = FakeDshApiClient::new();
+//! fake.post_secret(&Secret::new("my-secret", "value")).await?;
+//! fake.put_volume_configuration("my-volume", &Volume::new(10)).await?;
+//! assert_eq!(fake.get_secret("my-secret").await?, "value");
+//! assert!(fake.get_volume_status("my-volume").await?.provisioned);
+//! assert!(fake.secrets_with_dependants().await?[0].1.is_empty());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Methods
+//!
+//! * [`new() -> fake`](FakeDshApiClient::new)
+//! * [`insert_appcatalogapp(app id, app)`](FakeDshApiClient::insert_appcatalogapp)
+//! * [`set_status(kind, id, status)`](FakeDshApiClient::set_status)
+//!
+//! The kind of resource in [`set_status()`](FakeDshApiClient::set_status) is a [`FakeResourceKind`].
+
+use crate::api::{AppCatalogApi, ApplicationApi, BucketApi, CertificateApi, SecretApi, TopicApi, VolumeApi};
+use crate::types::{AllocationStatus, AppCatalogApp, Application, Bucket, Certificate, Secret, Topic, Volume};
+use crate::{DshApiError, DshApiResult};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::sync::Mutex;
+
+/// # Kind of resource with an allocation status in the fake
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum FakeResourceKind {
+  /// Application
+  Application,
+  /// Bucket
+  Bucket,
+  /// Certificate
+  Certificate,
+  /// Secret
+  Secret,
+  /// Topic
+  Topic,
+  /// Volume
+  Volume,
+}
+
+impl FakeResourceKind {
+  /// # Returns the kind as used in the error messages of the fake
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      FakeResourceKind::Application => "application",
+      FakeResourceKind::Bucket => "bucket",
+      FakeResourceKind::Certificate => "certificate",
+      FakeResourceKind::Secret => "secret",
+      FakeResourceKind::Topic => "topic",
+      FakeResourceKind::Volume => "volume",
+    }
+  }
+}
+
+impl Display for FakeResourceKind {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
+
+/// # In-memory fake of the DSH resource management API
+///
+/// See the [module documentation](self) for how the resource state is modelled.
+#[derive(Debug, Default)]
+pub struct FakeDshApiClient {
+  state: Mutex<FakeState>,
+}
+
+#[derive(Debug, Default)]
+struct FakeState {
+  applications: BTreeMap<String, Application>,
+  apps: BTreeMap<String, AppCatalogApp>,
+  buckets: BTreeMap<String, Bucket>,
+  certificates: BTreeMap<String, Certificate>,
+  secrets: BTreeMap<String, String>,
+  topics: BTreeMap<String, Topic>,
+  volumes: BTreeMap<String, Volume>,
+  statuses: HashMap<(&'static str, String), AllocationStatus>,
+}
+
+impl FakeDshApiClient {
+  /// # Create an empty fake
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// # Insert an app catalog app
+  ///
+  /// # Parameters
+  /// * `app_id` - id of the app
+  /// * `app` - configuration of the app
+  pub fn insert_appcatalogapp(&self, app_id: impl AsRef<str>, app: AppCatalogApp) {
+    self.state.lock().unwrap().apps.insert(app_id.as_ref().to_string(), app);
+  }
+
+  /// # Set the allocation status of a resource
+  ///
+  /// # Parameters
+  /// * `kind` - kind of the resource
+  /// * `id` - id of the resource
+  /// * `status` - new allocation status of the resource
+  ///
+  /// # Returns
+  /// * `Ok(())` - when the status was set
+  /// * `Err<`[`DshApiError::NotFound`]`>` - when the resource does not exist
+  pub fn set_status(&self, kind: FakeResourceKind, id: impl AsRef<str>, status: AllocationStatus) -> DshApiResult<()> {
+    let id = id.as_ref();
+    let mut state = self.state.lock().unwrap();
+    let exists = match kind {
+      FakeResourceKind::Application => state.applications.contains_key(id),
+      FakeResourceKind::Bucket => state.buckets.contains_key(id),
+      FakeResourceKind::Certificate => state.certificates.contains_key(id),
+      FakeResourceKind::Secret => state.secrets.contains_key(id),
+      FakeResourceKind::Topic => state.topics.contains_key(id),
+      FakeResourceKind::Volume => state.volumes.contains_key(id),
+    };
+    if exists {
+      state.statuses.insert((kind.as_str(), id.to_string()), status);
+      Ok(())
+    } else {
+      Err(not_found(kind.as_str(), id))
+    }
+  }
+}
+
+impl FakeState {
+  fn status(&self, kind: &'static str, id: &str) -> DshApiResult<AllocationStatus> {
+    self.statuses.get(&(kind, id.to_string())).cloned().ok_or_else(|| not_found(kind, id))
+  }
+
+  fn provision(&mut self, kind: &'static str, id: &str) {
+    self.statuses.insert(
+      (kind, id.to_string()),
+      AllocationStatus { derived_from: None, notifications: vec![], provisioned: true },
+    );
+  }
+
+  fn unprovision(&mut self, kind: &'static str, id: &str) {
+    self.statuses.remove(&(kind, id.to_string()));
+  }
+}
+
+impl AppCatalogApi for FakeDshApiClient {
+  async fn get_appcatalogapp_configuration(&self, appcatalogappid: impl AsRef<str> + Send) -> DshApiResult<AppCatalogApp> {
+    get(&self.state.lock().unwrap().apps, "app", appcatalogappid.as_ref())
+  }
+
+  async fn get_appcatalogapp_configuration_map(&self) -> DshApiResult<HashMap<String, AppCatalogApp>> {
+    Ok(self.state.lock().unwrap().apps.clone().into_iter().collect())
+  }
+}
+
+impl ApplicationApi for FakeDshApiClient {
+  async fn delete_application_configuration(&self, appid: impl AsRef<str> + Send) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    remove(&mut state.applications, "application", appid.as_ref())?;
+    state.unprovision("application", appid.as_ref());
+    Ok(())
+  }
+
+  async fn get_application_configuration(&self, appid: impl AsRef<str> + Send) -> DshApiResult<Application> {
+    get(&self.state.lock().unwrap().applications, "application", appid.as_ref())
+  }
+
+  async fn get_application_configuration_map(&self) -> DshApiResult<HashMap<String, Application>> {
+    Ok(self.state.lock().unwrap().applications.clone().into_iter().collect())
+  }
+
+  async fn get_application_status(&self, appid: impl AsRef<str> + Send) -> DshApiResult<AllocationStatus> {
+    self.state.lock().unwrap().status("application", appid.as_ref())
+  }
+
+  async fn put_application_configuration(&self, appid: impl AsRef<str> + Send, body: &Application) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    state.applications.insert(appid.as_ref().to_string(), body.clone());
+    state.provision("application", appid.as_ref());
+    Ok(())
+  }
+}
+
+impl BucketApi for FakeDshApiClient {
+  async fn delete_bucket_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    remove(&mut state.buckets, "bucket", id.as_ref())?;
+    state.unprovision("bucket", id.as_ref());
+    Ok(())
+  }
+
+  async fn get_bucket_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<Bucket> {
+    get(&self.state.lock().unwrap().buckets, "bucket", id.as_ref())
+  }
+
+  async fn get_bucket_ids(&self) -> DshApiResult<Vec<String>> {
+    Ok(self.state.lock().unwrap().buckets.keys().cloned().collect())
+  }
+
+  async fn get_bucket_status(&self, id: impl AsRef<str> + Send) -> DshApiResult<AllocationStatus> {
+    self.state.lock().unwrap().status("bucket", id.as_ref())
+  }
+
+  async fn put_bucket_configuration(&self, id: impl AsRef<str> + Send, body: &Bucket) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    state.buckets.insert(id.as_ref().to_string(), body.clone());
+    state.provision("bucket", id.as_ref());
+    Ok(())
+  }
+}
+
+impl CertificateApi for FakeDshApiClient {
+  async fn delete_certificate_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    remove(&mut state.certificates, "certificate", id.as_ref())?;
+    state.unprovision("certificate", id.as_ref());
+    Ok(())
+  }
+
+  async fn get_certificate_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<Certificate> {
+    get(&self.state.lock().unwrap().certificates, "certificate", id.as_ref())
+  }
+
+  async fn get_certificate_ids(&self) -> DshApiResult<Vec<String>> {
+    Ok(self.state.lock().unwrap().certificates.keys().cloned().collect())
+  }
+
+  async fn get_certificate_status(&self, id: impl AsRef<str> + Send) -> DshApiResult<AllocationStatus> {
+    self.state.lock().unwrap().status("certificate", id.as_ref())
+  }
+
+  async fn put_certificate_configuration(&self, id: impl AsRef<str> + Send, body: &Certificate) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    state.certificates.insert(id.as_ref().to_string(), body.clone());
+    state.provision("certificate", id.as_ref());
+    Ok(())
+  }
+}
+
+impl SecretApi for FakeDshApiClient {
+  async fn delete_secret_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    remove(&mut state.secrets, "secret", id.as_ref())?;
+    state.unprovision("secret", id.as_ref());
+    Ok(())
+  }
+
+  async fn get_secret(&self, id: impl AsRef<str> + Send) -> DshApiResult<String> {
+    get(&self.state.lock().unwrap().secrets, "secret", id.as_ref())
+  }
+
+  async fn get_secret_ids(&self) -> DshApiResult<Vec<String>> {
+    Ok(self.state.lock().unwrap().secrets.keys().cloned().collect())
+  }
+
+  async fn get_secret_status(&self, id: impl AsRef<str> + Send) -> DshApiResult<AllocationStatus> {
+    self.state.lock().unwrap().status("secret", id.as_ref())
+  }
+
+  async fn post_secret(&self, body: &Secret) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    if state.secrets.contains_key(&body.name) {
+      return Err(DshApiError::BadRequest(format!("secret '{}' already exists", body.name)));
+    }
+    state.secrets.insert(body.name.clone(), body.value.clone());
+    state.provision("secret", &body.name);
+    Ok(())
+  }
+
+  async fn put_secret(&self, id: impl AsRef<str> + Send, body: String) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    match state.secrets.get_mut(id.as_ref()) {
+      Some(value) => {
+        *value = body;
+        state.provision("secret", id.as_ref());
+        Ok(())
+      }
+      None => Err(not_found("secret", id.as_ref())),
+    }
+  }
+}
+
+impl TopicApi for FakeDshApiClient {
+  async fn delete_topic_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    remove(&mut state.topics, "topic", id.as_ref())?;
+    state.unprovision("topic", id.as_ref());
+    Ok(())
+  }
+
+  async fn get_topic_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<Topic> {
+    get(&self.state.lock().unwrap().topics, "topic", id.as_ref())
+  }
+
+  async fn get_topic_ids(&self) -> DshApiResult<Vec<String>> {
+    Ok(self.state.lock().unwrap().topics.keys().cloned().collect())
+  }
+
+  async fn get_topic_status(&self, id: impl AsRef<str> + Send) -> DshApiResult<AllocationStatus> {
+    self.state.lock().unwrap().status("topic", id.as_ref())
+  }
+
+  async fn put_topic_configuration(&self, id: impl AsRef<str> + Send, body: &Topic) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    state.topics.insert(id.as_ref().to_string(), body.clone());
+    state.provision("topic", id.as_ref());
+    Ok(())
+  }
+}
+
+impl VolumeApi for FakeDshApiClient {
+  async fn delete_volume_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    remove(&mut state.volumes, "volume", id.as_ref())?;
+    state.unprovision("volume", id.as_ref());
+    Ok(())
+  }
+
+  async fn get_volume_configuration(&self, id: impl AsRef<str> + Send) -> DshApiResult<Volume> {
+    get(&self.state.lock().unwrap().volumes, "volume", id.as_ref())
+  }
+
+  async fn get_volume_ids(&self) -> DshApiResult<Vec<String>> {
+    Ok(self.state.lock().unwrap().volumes.keys().cloned().collect())
+  }
+
+  async fn get_volume_status(&self, id: impl AsRef<str> + Send) -> DshApiResult<AllocationStatus> {
+    self.state.lock().unwrap().status("volume", id.as_ref())
+  }
+
+  async fn put_volume_configuration(&self, id: impl AsRef<str> + Send, body: &Volume) -> DshApiResult<()> {
+    let mut state = self.state.lock().unwrap();
+    state.volumes.insert(id.as_ref().to_string(), body.clone());
+    state.provision("volume", id.as_ref());
+    Ok(())
+  }
+}
+
+fn get<T: Clone>(resources: &BTreeMap<String, T>, kind: &str, id: &str) -> DshApiResult<T> {
+  resources.get(id).cloned().ok_or_else(|| not_found(kind, id))
+}
+
+fn remove<T>(resources: &mut BTreeMap<String, T>, kind: &str, id: &str) -> DshApiResult<T> {
+  resources.remove(id).ok_or_else(|| not_found(kind, id))
+}
+
+fn not_found(kind: &str, id: &str) -> DshApiError {
+  DshApiError::NotFound(Some(format!("{} '{}' does not exist", kind, id)))
+}
@@ -0,0 +1,283 @@
+//! # Additional methods to manage the Flink cluster
+//!
+//! Module that contains methods and functions to manage the Flink cluster of a tenant.
+//! A tenant has at most one Flink cluster.
+//!
+//! # Generated methods
+//!
+//! [`DshApiClient`] methods that are generated from the `openapi` specification.
+//!
+//! * [`delete_flinkcluster_configuration()`](DshApiClient::delete_flinkcluster_configuration)
+//! * [`get_flinkcluster() -> FlinkClusterStatus`](DshApiClient::get_flinkcluster)
+//! * [`get_flinkcluster_actual() -> FlinkCluster`](DshApiClient::get_flinkcluster_actual)
+//! * [`get_flinkcluster_configuration() -> FlinkCluster`](DshApiClient::get_flinkcluster_configuration)
+//! * [`get_flinkcluster_status() -> AllocationStatus`](DshApiClient::get_flinkcluster_status)
+//! * [`put_flinkcluster_configuration(body)`](DshApiClient::put_flinkcluster_configuration)
+//!
+//! # Derived methods
+//!
+//! [`DshApiClient`] methods that add extra capabilities but do not directly call the
+//! DSH resource management API. These derived methods depend on the API methods for this.
+//!
+//! * [`deploy_flink_cluster(configuration, cpu limit, mem limit)`](DshApiClient::deploy_flink_cluster)
+//! * [`flink_cluster() -> Option<(flink cluster, status)>`](DshApiClient::flink_cluster)
+//! * [`flink_cluster_diff() -> Option<diff>`](DshApiClient::flink_cluster_diff)
+//! * [`validate_flink_cluster(configuration, cpu limit, mem limit) -> [violation]`](DshApiClient::validate_flink_cluster)
+//!
+//! # Functions
+//!
+//! * [`flink_cluster_cpus(flink cluster) -> cpus`](flink_cluster_cpus)
+//! * [`flink_cluster_mem(flink cluster) -> mem`](flink_cluster_mem)
+//! * [`flink_cluster_violations(flink cluster, applications, cpu limit, mem limit) -> [violation]`](flink_cluster_violations)
+//!
+//! The tenant limits can only be retrieved via the manage methods (see
+//! `managed_tenant_limit()`, feature `manage`),
+//! hence the cpu and memory limits are parameters of the methods that validate a configuration.
+
+use crate::dsh_api_client::DshApiClient;
+use crate::types::{AllocationStatus, Application, FlinkCluster, FlinkJobManager, FlinkTaskManager};
+use crate::validation::validate_schema;
+use crate::{DshApiError, DshApiResult};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// Structure that contains the differences between two `FlinkCluster`s
+#[derive(Debug)]
+pub struct FlinkClusterDiff {
+  pub job_manager: Option<(Option<FlinkJobManager>, Option<FlinkJobManager>)>,
+  pub task_manager: Option<(Option<FlinkTaskManager>, Option<FlinkTaskManager>)>,
+  pub version: Option<(String, String)>,
+  pub zone: Option<(String, String)>,
+}
+
+impl FlinkClusterDiff {
+  /// # Compare Flink clusters
+  ///
+  /// # Parameters
+  /// * `baseline` - baseline Flink cluster to compare against
+  /// * `sample` - sample Flink cluster that will be compared against the baseline
+  ///
+  /// # Returns
+  /// * [`FlinkClusterDiff`] - struct that describes the differences between the two [`FlinkCluster`]s
+  pub fn differences_between_flink_clusters(baseline: &FlinkCluster, sample: &FlinkCluster) -> FlinkClusterDiff {
+    FlinkClusterDiff {
+      job_manager: if baseline.job_manager == sample.job_manager { None } else { Some((baseline.job_manager.clone(), sample.job_manager.clone())) },
+      task_manager: if baseline.task_manager == sample.task_manager { None } else { Some((baseline.task_manager.clone(), sample.task_manager.clone())) },
+      version: if baseline.version == sample.version { None } else { Some((baseline.version.clone(), sample.version.clone())) },
+      zone: if baseline.zone == sample.zone { None } else { Some((baseline.zone.clone(), sample.zone.clone())) },
+    }
+  }
+
+  /// # Check if there are any differences
+  ///
+  /// # Returns
+  /// * `true` - struct does not contain any differences
+  /// * `false` - struct does contain differences
+  pub fn is_empty(&self) -> bool {
+    self.job_manager.is_none() && self.task_manager.is_none() && self.version.is_none() && self.zone.is_none()
+  }
+
+  /// # List the differences
+  ///
+  /// If there are no differences, an empty list will be returned.
+  ///
+  /// # Returns
+  /// * `Vec<(String, String)>` - list of key/value pairs describing all differences
+  pub fn differences(&self) -> Vec<(String, String)> {
+    vec![
+      self
+        .job_manager
+        .as_ref()
+        .map(|value| ("job manager".to_string(), format!("{:?} / {:?}", value.0, value.1))),
+      self
+        .task_manager
+        .as_ref()
+        .map(|value| ("task manager".to_string(), format!("{:?} / {:?}", value.0, value.1))),
+      self.version.as_ref().map(|value| ("version".to_string(), format!("{:?} / {:?}", value.0, value.1))),
+      self.zone.as_ref().map(|value| ("zone".to_string(), format!("{:?} / {:?}", value.0, value.1))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect_vec()
+  }
+}
+
+/// # Additional methods to manage the Flink cluster
+///
+/// Module that contains methods to manage the Flink cluster.
+/// * Derived methods - DshApiClient methods that add extra capabilities
+///   but depend on the API methods.
+///
+/// # Derived methods
+///
+/// [`DshApiClient`] methods that add extra capabilities but do not directly call the
+/// DSH resource management API. These derived methods depend on the API methods for this.
+///
+/// * [`deploy_flink_cluster(configuration, cpu limit, mem limit)`](DshApiClient::deploy_flink_cluster)
+/// * [`flink_cluster() -> Option<(flink cluster, status)>`](DshApiClient::flink_cluster)
+/// * [`flink_cluster_diff() -> Option<diff>`](DshApiClient::flink_cluster_diff)
+/// * [`validate_flink_cluster(configuration, cpu limit, mem limit) -> [violation]`](DshApiClient::validate_flink_cluster)
+impl DshApiClient {
+  /// # Validate and deploy a Flink cluster configuration
+  ///
+  /// The configuration is first validated with
+  /// [`validate_flink_cluster()`](DshApiClient::validate_flink_cluster),
+  /// and is only deployed when there are no violations.
+  ///
+  /// # Parameters
+  /// * `configuration` - the new Flink cluster configuration
+  /// * `cpu_limit` - maximum number of cpus of the tenant
+  /// * `mem_limit` - maximum amount of memory of the tenant, in MB
+  ///
+  /// # Returns
+  /// * `Ok(())` - when the configuration was deployed
+  /// * `Err<`[`DshApiError::Parameter`]`>` - when the configuration is not valid,
+  ///   the message describes all violations
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn deploy_flink_cluster(&self, configuration: &FlinkCluster, cpu_limit: f64, mem_limit: i64) -> DshApiResult<()> {
+    let violations = self.validate_flink_cluster(configuration, cpu_limit, mem_limit).await?;
+    if violations.is_empty() {
+      self.put_flinkcluster_configuration(configuration).await
+    } else {
+      Err(DshApiError::Parameter(format!("invalid flink cluster configuration ({})", violations.join(", "))))
+    }
+  }
+
+  /// # Get the Flink cluster with its status
+  ///
+  /// # Returns
+  /// * `Ok<Some<(`[`FlinkCluster`]`, `[`AllocationStatus`]`)>>` - the configuration of the
+  ///   Flink cluster, or its actual state when there is no configuration, and its status
+  /// * `Ok<None>` - when the tenant has no Flink cluster
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn flink_cluster(&self) -> DshApiResult<Option<(FlinkCluster, AllocationStatus)>> {
+    match self.get_flinkcluster().await {
+      Ok(flink_cluster_status) => Ok(
+        flink_cluster_status
+          .configuration
+          .or(flink_cluster_status.actual)
+          .map(|flink_cluster| (flink_cluster, flink_cluster_status.status)),
+      ),
+      Err(DshApiError::NotFound(_)) => Ok(None),
+      Err(error) => Err(error),
+    }
+  }
+
+  /// # Compare the configuration of the Flink cluster with its actual state
+  ///
+  /// # Returns
+  /// * `Ok<Some<`[`FlinkClusterDiff`]`>>` - differences between the configuration (baseline)
+  ///   and the actual state (sample) of the Flink cluster
+  /// * `Ok<None>` - when the tenant has no Flink cluster, or when the Flink cluster
+  ///   has no configuration or no actual state
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn flink_cluster_diff(&self) -> DshApiResult<Option<FlinkClusterDiff>> {
+    match self.get_flinkcluster().await {
+      Ok(flink_cluster_status) => match (flink_cluster_status.configuration, flink_cluster_status.actual) {
+        (Some(configuration), Some(actual)) => Ok(Some(FlinkClusterDiff::differences_between_flink_clusters(&configuration, &actual))),
+        _ => Ok(None),
+      },
+      Err(DshApiError::NotFound(_)) => Ok(None),
+      Err(error) => Err(error),
+    }
+  }
+
+  /// # Validate a Flink cluster configuration
+  ///
+  /// Validates the configuration against the openapi specification,
+  /// and checks that the cpus and memory of the applications of the tenant
+  /// together with the cpus and memory of the new Flink cluster do not exceed the tenant limits.
+  /// The current Flink cluster, if any, is not taken into account, since it will be replaced.
+  ///
+  /// # Parameters
+  /// * `configuration` - the new Flink cluster configuration
+  /// * `cpu_limit` - maximum number of cpus of the tenant
+  /// * `mem_limit` - maximum amount of memory of the tenant, in MB
+  ///
+  /// # Returns
+  /// * `Ok<Vec<String>>` - descriptions of all violations, empty when the configuration is valid
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn validate_flink_cluster(&self, configuration: &FlinkCluster, cpu_limit: f64, mem_limit: i64) -> DshApiResult<Vec<String>> {
+    let applications = self.get_application_configuration_map().await?;
+    flink_cluster_violations(configuration, &applications, cpu_limit, mem_limit)
+  }
+}
+
+/// # Returns the total number of cpus of a Flink cluster
+///
+/// # Parameters
+/// * `flink_cluster` - the Flink cluster
+///
+/// # Returns
+/// Sum of the cpus of the job manager and the cpus of all task manager instances.
+pub fn flink_cluster_cpus(flink_cluster: &FlinkCluster) -> f64 {
+  flink_cluster.job_manager.as_ref().map(|job_manager| job_manager.cpus).unwrap_or_default()
+    + flink_cluster
+      .task_manager
+      .as_ref()
+      .map(|task_manager| task_manager.cpus * task_manager.instances.get() as f64)
+      .unwrap_or_default()
+}
+
+/// # Returns the total amount of memory of a Flink cluster
+///
+/// # Parameters
+/// * `flink_cluster` - the Flink cluster
+///
+/// # Returns
+/// Sum of the memory of the job manager and the memory of all task manager instances, in MB.
+pub fn flink_cluster_mem(flink_cluster: &FlinkCluster) -> i64 {
+  flink_cluster.job_manager.as_ref().map(|job_manager| job_manager.mem).unwrap_or_default()
+    + flink_cluster
+      .task_manager
+      .as_ref()
+      .map(|task_manager| task_manager.mem * task_manager.instances.get() as i64)
+      .unwrap_or_default()
+}
+
+/// # Returns the violations of a Flink cluster configuration
+///
+/// Validates the configuration against the openapi specification,
+/// and checks that the cpus and memory of the applications together with the cpus
+/// and memory of the Flink cluster do not exceed the limits.
+///
+/// # Parameters
+/// * `flink_cluster` - the Flink cluster configuration
+/// * `applications` - the applications of the tenant
+/// * `cpu_limit` - maximum number of cpus of the tenant
+/// * `mem_limit` - maximum amount of memory of the tenant, in MB
+///
+/// # Returns
+/// * `Ok<Vec<String>>` - descriptions of all violations, empty when the configuration is valid
+/// * `Err<`[`DshApiError`]`>` - when the configuration could not be serialized
+pub fn flink_cluster_violations(flink_cluster: &FlinkCluster, applications: &HashMap<String, Application>, cpu_limit: f64, mem_limit: i64) -> DshApiResult<Vec<String>> {
+  let mut violations = validate_schema(
+    "FlinkCluster",
+    &serde_json::to_value(flink_cluster).map_err(|error| DshApiError::Parameter(format!("could not serialize FlinkCluster ({})", error)))?,
+  )?
+  .iter()
+  .map(|validation_error| validation_error.to_string())
+  .collect_vec();
+  let application_cpus = applications
+    .values()
+    .map(|application| application.cpus * application.instances as f64)
+    .sum::<f64>();
+  let application_mem = applications
+    .values()
+    .map(|application| (application.mem * application.instances) as i64)
+    .sum::<i64>();
+  let (cpus, mem) = (flink_cluster_cpus(flink_cluster), flink_cluster_mem(flink_cluster));
+  if application_cpus + cpus > cpu_limit {
+    violations.push(format!(
+      "{} cpus for the flink cluster and {} cpus for the applications exceed the limit of {} cpus",
+      cpus, application_cpus, cpu_limit
+    ));
+  }
+  if application_mem + mem > mem_limit {
+    violations.push(format!(
+      "{} MB memory for the flink cluster and {} MB memory for the applications exceed the limit of {} MB",
+      mem, application_mem, mem_limit
+    ));
+  }
+  Ok(violations)
+}
@@ -0,0 +1,196 @@
+//! # Strongly typed resource identifiers
+//!
+//! Module that contains newtype identifiers for the resources of a tenant.
+//! An identifier can only be constructed from a value that complies with the DSH naming rules
+//! for the resource kind, which prevents that an invalid name is sent to the DSH resource
+//! management API, or that a topic id is passed where a secret id is expected.
+//!
+//! | identifier        | naming rule                                                                |
+//! | ----------------- | -------------------------------------------------------------------------- |
+//! | [`AppId`]         | lowercase letter, then lowercase letters, digits and `-`, at most 63       |
+//! | [`ApplicationId`] | lowercase letter, then lowercase letters, digits and `-`, at most 63       |
+//! | [`BucketId`]      | lowercase letter, then lowercase letters, digits and `-`, 3 up to 63       |
+//! | [`CertificateId`] | lowercase letter, then lowercase letters, digits and `-`, at most 63       |
+//! | [`SecretId`]      | lowercase letter, then lowercase letters, digits, `-` and `!`, at most 253 |
+//! | [`TopicId`]       | lowercase letter, then lowercase letters, digits and `-`, at most 100      |
+//! | [`VhostId`]       | lowercase letter, then lowercase letters, digits and `-`, at most 63       |
+//! | [`VolumeId`]      | lowercase letter, then lowercase letters, digits and `-`, at most 63       |
+//!
+//! Identifiers that end with a `-` are not allowed.
+//! Managed streams are identified by the generated type [`ManagedStreamId`],
+//! which validates its pattern from the openapi specification.
+//!
+//! All identifiers implement [`AsRef<str>`], [`Display`], [`FromStr`], [`Serialize`] and
+//! [`Deserialize`] (which validates the value). Since the wrapped and derived methods of the
+//! [`DshApiClient`](crate::dsh_api_client::DshApiClient) accept any `impl AsRef<str>` as identifier,
+//! both the typed identifiers and plain strings can be used.
+//!
+//! # Examples
+//!
+//! ```
+//! use dsh_api::id::{SecretId, TopicId};
+//! use std::str::FromStr;
+//!
+//! let topic_id = TopicId::new("my-topic").unwrap();
+//! assert_eq!(topic_id.to_string(), "my-topic");
+//! assert!(SecretId::from_str("My_Secret").is_err());
+//! ```
+//!
+//! ```no_run
+//! # use dsh_api::dsh_api_client_factory::DshApiClientFactory;
+//! # use dsh_api::id::TopicId;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = DshApiClientFactory::default().client().await?;
+//! let topic_id: TopicId = "my-topic".parse()?;
+//! let topic = client.get_topic_configuration(&topic_id).await?;
+//! let same_topic = client.get_topic_configuration("my-topic").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::types::ManagedStreamId;
+use crate::{DshApiError, DshApiResult};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::borrow::Borrow;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+lazy_static! {
+  static ref NAME_REGEX: Regex = Regex::new(r"^[a-z]([a-z0-9-]*[a-z0-9])?$").unwrap();
+  static ref SECRET_NAME_REGEX: Regex = Regex::new(r"^[a-z]([a-z0-9!-]*[a-z0-9])?$").unwrap();
+}
+
+macro_rules! resource_id {
+  ($id_type:ident, $kind:literal, $regex:ident, $min_length:literal, $max_length:literal) => {
+    #[doc = concat!("# Identifier of a ", $kind)]
+    ///
+    /// See the [module documentation](self) for the naming rules.
+    #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+    #[serde(transparent)]
+    pub struct $id_type(String);
+
+    impl $id_type {
+      /// The kind of resource that is identified
+      pub const KIND: &'static str = $kind;
+
+      #[doc = concat!("# Create a new ", $kind, " identifier")]
+      ///
+      /// # Parameters
+      /// * `id` - the identifier, which must comply with the naming rules
+      ///
+      /// # Returns
+      /// * `Ok<Self>` - when `id` is a valid identifier
+      /// * `Err<`[`DshApiError::Parameter`]`>` - when `id` is not a valid identifier
+      pub fn new(id: impl Into<String>) -> DshApiResult<Self> {
+        let id = id.into();
+        validate_id(&id, $kind, &$regex, $min_length, $max_length)?;
+        Ok(Self(id))
+      }
+
+      /// # Returns the identifier as a string slice
+      pub fn as_str(&self) -> &str {
+        self.0.as_str()
+      }
+    }
+
+    impl AsRef<str> for $id_type {
+      fn as_ref(&self) -> &str {
+        self.0.as_str()
+      }
+    }
+
+    impl Borrow<str> for $id_type {
+      fn borrow(&self) -> &str {
+        self.0.as_str()
+      }
+    }
+
+    impl Display for $id_type {
+      fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+      }
+    }
+
+    impl FromStr for $id_type {
+      type Err = DshApiError;
+
+      fn from_str(id: &str) -> Result<Self, Self::Err> {
+        Self::new(id)
+      }
+    }
+
+    impl TryFrom<&str> for $id_type {
+      type Error = DshApiError;
+
+      fn try_from(id: &str) -> Result<Self, Self::Error> {
+        Self::new(id)
+      }
+    }
+
+    impl TryFrom<String> for $id_type {
+      type Error = DshApiError;
+
+      fn try_from(id: String) -> Result<Self, Self::Error> {
+        Self::new(id)
+      }
+    }
+
+    impl From<$id_type> for String {
+      fn from(id: $id_type) -> Self {
+        id.0
+      }
+    }
+
+    impl PartialEq<str> for $id_type {
+      fn eq(&self, other: &str) -> bool {
+        self.0 == other
+      }
+    }
+
+    impl PartialEq<&str> for $id_type {
+      fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+      }
+    }
+
+    impl<'de> Deserialize<'de> for $id_type {
+      fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+      where
+        D: Deserializer<'de>,
+      {
+        Self::new(String::deserialize(deserializer)?).map_err(de::Error::custom)
+      }
+    }
+  };
+}
+
+resource_id!(AppId, "app", NAME_REGEX, 1, 63);
+resource_id!(ApplicationId, "application", NAME_REGEX, 1, 63);
+resource_id!(BucketId, "bucket", NAME_REGEX, 3, 63);
+resource_id!(CertificateId, "certificate", NAME_REGEX, 1, 63);
+resource_id!(SecretId, "secret", SECRET_NAME_REGEX, 1, 253);
+resource_id!(TopicId, "topic", NAME_REGEX, 1, 100);
+resource_id!(VhostId, "vhost", NAME_REGEX, 1, 63);
+resource_id!(VolumeId, "volume", NAME_REGEX, 1, 63);
+
+impl AsRef<str> for ManagedStreamId {
+  fn as_ref(&self) -> &str {
+    self.as_str()
+  }
+}
+
+fn validate_id(id: &str, kind: &str, regex: &Regex, min_length: usize, max_length: usize) -> DshApiResult<()> {
+  if id.len() < min_length || id.len() > max_length {
+    Err(DshApiError::Parameter(format!(
+      "{} id '{}' must have a length between {} and {} characters",
+      kind, id, min_length, max_length
+    )))
+  } else if !regex.is_match(id) {
+    Err(DshApiError::Parameter(format!("'{}' is not a valid {} id", id, kind)))
+  } else {
+    Ok(())
+  }
+}
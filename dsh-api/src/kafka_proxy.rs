@@ -0,0 +1,339 @@
+//! # Additional methods to manage Kafka proxies
+//!
+//! Module that contains methods and functions to manage Kafka proxies,
+//! and to generate the client configuration for consumers and producers
+//! outside the DSH platform.
+//!
+//! # Generated methods
+//!
+//! [`DshApiClient`] methods that are generated from the `openapi` specification.
+//!
+//! * [`delete_kafkaproxy_configuration(id)`](DshApiClient::delete_kafkaproxy_configuration)
+//! * [`get_kafkaproxy_actual(id) -> KafkaProxy`](DshApiClient::get_kafkaproxy_actual)
+//!   (not available for openapi version 1.8)
+//! * [`get_kafkaproxy_configuration(id) -> KafkaProxy`](DshApiClient::get_kafkaproxy_configuration)
+//! * [`get_kafkaproxy_ids() -> [id]`](DshApiClient::get_kafkaproxy_ids)
+//! * [`get_kafkaproxy_status(id) -> AllocationStatus`](DshApiClient::get_kafkaproxy_status)
+//!   (not available for openapi version 1.8)
+//! * [`put_kafkaproxy_configuration(id, body)`](DshApiClient::put_kafkaproxy_configuration)
+//!
+//! # Derived methods
+//!
+//! [`DshApiClient`] methods that add extra capabilities but do not directly call the
+//! DSH resource management API. These derived methods depend on the API methods for this.
+//!
+//! * [`kafka_proxies() -> [(id, kafka proxy)]`](DshApiClient::kafka_proxies)
+//! * [`kafka_proxies_with_dependant_applications() -> [(id, [dependant application])]`](DshApiClient::kafka_proxies_with_dependant_applications)
+//! * [`kafka_proxies_with_status() -> [(id, kafka proxy, status)]`](DshApiClient::kafka_proxies_with_status)
+//!   (not available for openapi version 1.8)
+//! * [`kafka_proxy_client_configuration(id, zone) -> client configuration`](DshApiClient::kafka_proxy_client_configuration)
+//!
+//! # Functions
+//!
+//! * [`kafka_proxy_vhosts_from_applications(proxy id, tenant, applications) -> [(application id, application, [(port, vhost)])]`](kafka_proxy_vhosts_from_applications)
+
+use crate::application_types::ApplicationValues;
+use crate::dsh_api_client::DshApiClient;
+#[cfg(not(dsh_api_1_8))]
+use crate::types::AllocationStatus;
+use crate::types::{Application, KafkaProxy, KafkaProxyZone};
+use crate::vhost::{VhostInjection, VhostString};
+use crate::{DependantApplication, DshApiError, DshApiResult};
+#[cfg(not(dsh_api_1_8))]
+use futures::future::try_join;
+use futures::future::try_join_all;
+use futures::try_join;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// # Kafka client configuration for a Kafka proxy
+///
+/// Describes how Kafka clients outside the DSH platform can connect to a Kafka proxy.
+/// The clients authenticate with a client certificate that must be signed by the
+/// certificate authority from the secret
+/// [`ca_chain_secret`](KafkaProxyClientConfiguration::ca_chain_secret).
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct KafkaProxyClientConfiguration {
+  /// Identifies the Kafka proxy
+  pub proxy_id: String,
+  /// Zone from which the Kafka proxy will be accessed
+  pub zone: KafkaProxyZone,
+  /// Bootstrap servers, including the port
+  pub bootstrap_servers: Vec<String>,
+  /// Schema store host, including the port, when the Kafka proxy has a schema store
+  pub schema_store_host: Option<String>,
+  /// Name of the secret that contains the certificate authority chain for the client certificates
+  pub ca_chain_secret: String,
+  /// Location of the PEM file with the private key and the client certificate chain,
+  /// default [`DEFAULT_KEYSTORE_LOCATION`]
+  pub keystore_location: String,
+  /// Location of the PEM file with the certificates of the trusted certificate authorities,
+  /// default [`DEFAULT_TRUSTSTORE_LOCATION`]
+  pub truststore_location: String,
+}
+
+/// Default location of the PEM file with the private key and the client certificate chain
+pub const DEFAULT_KEYSTORE_LOCATION: &str = "client.pem";
+
+/// Default location of the PEM file with the certificates of the trusted certificate authorities
+pub const DEFAULT_TRUSTSTORE_LOCATION: &str = "truststore.pem";
+
+impl KafkaProxyClientConfiguration {
+  /// # Set the locations of the key material
+  ///
+  /// # Parameters
+  /// * `keystore_location` - location of the PEM file with the private key
+  ///   and the client certificate chain
+  /// * `truststore_location` - location of the PEM file with the certificates
+  ///   of the trusted certificate authorities
+  ///
+  /// # Returns
+  /// The client configuration with the new locations
+  pub fn with_key_material(self, keystore_location: impl AsRef<str>, truststore_location: impl AsRef<str>) -> Self {
+    Self { keystore_location: keystore_location.as_ref().to_string(), truststore_location: truststore_location.as_ref().to_string(), ..self }
+  }
+
+  /// # Returns the Kafka client properties
+  ///
+  /// The properties configure the bootstrap servers and mutual TLS with PEM encoded
+  /// certificates and keys, read from the
+  /// [`keystore_location`](KafkaProxyClientConfiguration::keystore_location) and
+  /// [`truststore_location`](KafkaProxyClientConfiguration::truststore_location) files.
+  /// When the Kafka proxy has a schema store,
+  /// the property `schema.registry.url` will also be included.
+  ///
+  /// # Returns
+  /// `Vec<(String, String)>` - list of key/value pairs with the client properties
+  pub fn properties(&self) -> Vec<(String, String)> {
+    let mut properties = vec![
+      ("bootstrap.servers".to_string(), self.bootstrap_servers.join(",")),
+      ("security.protocol".to_string(), "SSL".to_string()),
+      ("ssl.endpoint.identification.algorithm".to_string(), "https".to_string()),
+      ("ssl.keystore.type".to_string(), "PEM".to_string()),
+      ("ssl.keystore.location".to_string(), self.keystore_location.clone()),
+      ("ssl.truststore.type".to_string(), "PEM".to_string()),
+      ("ssl.truststore.location".to_string(), self.truststore_location.clone()),
+    ];
+    if let Some(ref schema_store_host) = self.schema_store_host {
+      properties.push(("schema.registry.url".to_string(), format!("https://{}", schema_store_host)));
+    }
+    properties
+  }
+}
+
+impl Display for KafkaProxyClientConfiguration {
+  /// Formats the client configuration as the contents of a Java properties file
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let zone = match self.zone {
+      KafkaProxyZone::Private => "private",
+      KafkaProxyZone::Public => "public",
+    };
+    writeln!(f, "# kafka proxy {} ({} zone)", self.proxy_id, zone)?;
+    writeln!(f, "# client certificates must be signed by the ca chain from secret {}", self.ca_chain_secret)?;
+    for (key, value) in self.properties() {
+      writeln!(f, "{}={}", key, value)?;
+    }
+    Ok(())
+  }
+}
+
+/// # Additional methods to manage Kafka proxies
+///
+/// Module that contains methods to manage Kafka proxies.
+/// * Derived methods - DshApiClient methods that add extra capabilities
+///   but depend on the API methods.
+///
+/// # Derived methods
+///
+/// [`DshApiClient`] methods that add extra capabilities but do not directly call the
+/// DSH resource management API. These derived methods depend on the API methods for this.
+///
+/// * [`kafka_proxies() -> [(id, kafka proxy)]`](DshApiClient::kafka_proxies)
+/// * [`kafka_proxies_with_dependant_applications() -> [(id, [dependant application])]`](DshApiClient::kafka_proxies_with_dependant_applications)
+/// * [`kafka_proxies_with_status() -> [(id, kafka proxy, status)]`](DshApiClient::kafka_proxies_with_status)
+/// * [`kafka_proxy_client_configuration(id, zone) -> client configuration`](DshApiClient::kafka_proxy_client_configuration)
+impl DshApiClient {
+  /// # Returns all Kafka proxies
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(String, `[`KafkaProxy`]`)>>` - list of all Kafka proxy ids and configurations,
+  ///   sorted by Kafka proxy id
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn kafka_proxies(&self) -> DshApiResult<Vec<(String, KafkaProxy)>> {
+    let mut kafka_proxy_ids: Vec<String> = self.get_kafkaproxy_ids().await?;
+    kafka_proxy_ids.sort();
+    let kafka_proxies = try_join_all(kafka_proxy_ids.iter().map(|kafka_proxy_id| self.get_kafkaproxy_configuration(kafka_proxy_id))).await?;
+    Ok(kafka_proxy_ids.into_iter().zip(kafka_proxies).collect_vec())
+  }
+
+  /// # Returns all Kafka proxies with the applications that use them
+  ///
+  /// An application uses a Kafka proxy when it exposes a port on the Kafka vhost of the proxy,
+  /// e.g. `{ vhost('my-proxy.kafka.my-tenant','public') }`.
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(String, Vec<`[`DependantApplication`]`<`[`VhostInjection`]`>>)>>` - list of all
+  ///   Kafka proxy ids, sorted by Kafka proxy id, together with the applications that use them
+  ///   and the ports and zones of the vhosts
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn kafka_proxies_with_dependant_applications(&self) -> DshApiResult<Vec<(String, Vec<DependantApplication<VhostInjection>>)>> {
+    let (mut kafka_proxy_ids, applications) = try_join!(self.get_kafkaproxy_ids(), self.get_application_configuration_map())?;
+    kafka_proxy_ids.sort();
+    Ok(
+      kafka_proxy_ids
+        .into_iter()
+        .map(|kafka_proxy_id| {
+          let dependant_applications = kafka_proxy_dependant_applications_from_applications(&kafka_proxy_id, self.tenant_name(), &applications);
+          (kafka_proxy_id, dependant_applications)
+        })
+        .collect_vec(),
+    )
+  }
+
+  /// # Returns all Kafka proxies with their allocation status
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(String, `[`KafkaProxy`]`, `[`AllocationStatus`]`)>>` - list of all Kafka proxy ids,
+  ///   configurations and allocation statuses, sorted by Kafka proxy id
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  #[cfg(not(dsh_api_1_8))]
+  pub async fn kafka_proxies_with_status(&self) -> DshApiResult<Vec<(String, KafkaProxy, AllocationStatus)>> {
+    let mut kafka_proxy_ids: Vec<String> = self.get_kafkaproxy_ids().await?;
+    kafka_proxy_ids.sort();
+    let kafka_proxies = try_join_all(
+      kafka_proxy_ids
+        .iter()
+        .map(|kafka_proxy_id| try_join(self.get_kafkaproxy_configuration(kafka_proxy_id), self.get_kafkaproxy_status(kafka_proxy_id))),
+    )
+    .await?;
+    Ok(
+      kafka_proxy_ids
+        .into_iter()
+        .zip(kafka_proxies)
+        .map(|(kafka_proxy_id, (kafka_proxy, status))| (kafka_proxy_id, kafka_proxy, status))
+        .collect_vec(),
+    )
+  }
+
+  /// # Returns the Kafka client configuration for a Kafka proxy
+  ///
+  /// # Parameters
+  /// * `kafka_proxy_id` - identifies the Kafka proxy
+  /// * `zone` - zone from which the clients will access the Kafka proxy
+  ///
+  /// # Returns
+  /// * `Ok<`[`KafkaProxyClientConfiguration`]`>` - the client configuration
+  /// * `Err<`[`DshApiError::Configuration`]`>` - when the private zone is requested
+  ///   and the platform has no private domain
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn kafka_proxy_client_configuration(&self, kafka_proxy_id: impl AsRef<str>, zone: KafkaProxyZone) -> DshApiResult<KafkaProxyClientConfiguration> {
+    let kafka_proxy_id = kafka_proxy_id.as_ref();
+    let kafka_proxy = self.get_kafkaproxy_configuration(kafka_proxy_id).await?;
+    let (tenant_name, platform) = (self.tenant_name(), self.platform());
+    let schema_store = kafka_proxy.schema_store.unwrap_or_default();
+    let (bootstrap_servers, schema_store_host) = match zone {
+      KafkaProxyZone::Private => (
+        platform
+          .tenant_proxy_private_bootstrap_servers(tenant_name, kafka_proxy_id)
+          .map_err(DshApiError::Configuration)?,
+        if schema_store {
+          Some(
+            platform
+              .tenant_proxy_private_schema_store_host(tenant_name, kafka_proxy_id)
+              .map_err(DshApiError::Configuration)?,
+          )
+        } else {
+          None
+        },
+      ),
+      KafkaProxyZone::Public => (
+        platform.tenant_proxy_public_bootstrap_servers(tenant_name, kafka_proxy_id),
+        if schema_store { Some(platform.tenant_proxy_public_schema_store_host(tenant_name, kafka_proxy_id)) } else { None },
+      ),
+    };
+    Ok(KafkaProxyClientConfiguration {
+      proxy_id: kafka_proxy_id.to_string(),
+      zone,
+      bootstrap_servers,
+      schema_store_host,
+      ca_chain_secret: kafka_proxy.secret_name_ca_chain,
+      keystore_location: DEFAULT_KEYSTORE_LOCATION.to_string(),
+      truststore_location: DEFAULT_TRUSTSTORE_LOCATION.to_string(),
+    })
+  }
+}
+
+/// # Get the Kafka vhosts of a Kafka proxy from applications
+///
+/// Get all exposed ports from all `Application`s that use the Kafka vhost of the Kafka proxy
+/// with `kafka_proxy_id` of tenant `tenant_name`, e.g. `{ vhost('my-proxy.kafka.my-tenant','public') }`.
+/// Applications are only included if they reference the Kafka vhost at least once.
+///
+/// # Parameters
+/// * `kafka_proxy_id` - id of the Kafka proxy to look for
+/// * `tenant_name` - name of the tenant of the Kafka proxy
+/// * `applications` - hashmap containing id/application pairs
+///
+/// # Returns
+/// `Vec<ApplicationValues<(&str, VhostString)>>` - list of tuples containing:
+/// * application id
+/// * reference to application
+/// * list of pairs of port number and parsed vhost string, sorted by port number
+///
+/// The list is sorted by application id.
+pub fn kafka_proxy_vhosts_from_applications<'a>(
+  kafka_proxy_id: &str,
+  tenant_name: &str,
+  applications: &'a HashMap<String, Application>,
+) -> Vec<ApplicationValues<'a, (&'a str, VhostString)>> {
+  let mut application_values = applications
+    .iter()
+    .filter_map(|(application_id, application)| {
+      let vhosts = application
+        .exposed_ports
+        .iter()
+        .filter_map(|(port, port_mapping)| {
+          port_mapping
+            .vhost
+            .as_ref()
+            .and_then(|vhost| VhostString::from_str(vhost).ok())
+            .filter(|vhost_string| vhost_string.kafka && vhost_string.vhost_name == kafka_proxy_id && vhost_string.tenant_name.as_deref() == Some(tenant_name))
+            .map(|vhost_string| (port.as_str(), vhost_string))
+        })
+        .sorted_by_key(|(port, _)| *port)
+        .collect_vec();
+      if vhosts.is_empty() {
+        None
+      } else {
+        Some(ApplicationValues::new(application_id, application, vhosts))
+      }
+    })
+    .collect_vec();
+  application_values.sort_by(|application_values_a, application_values_b| application_values_a.id.cmp(application_values_b.id));
+  application_values
+}
+
+// Returns the applications that use a Kafka proxy, from the application configurations
+pub(crate) fn kafka_proxy_dependant_applications_from_applications(
+  kafka_proxy_id: &str,
+  tenant_name: &str,
+  applications: &HashMap<String, Application>,
+) -> Vec<DependantApplication<VhostInjection>> {
+  kafka_proxy_vhosts_from_applications(kafka_proxy_id, tenant_name, applications)
+    .into_iter()
+    .map(|application_values| {
+      DependantApplication::new(
+        application_values.id.to_string(),
+        application_values.application.instances,
+        application_values
+          .values
+          .into_iter()
+          .map(|(port, vhost_string)| VhostInjection::Vhost(port.to_string(), vhost_string.zone))
+          .collect_vec(),
+      )
+    })
+    .collect_vec()
+}
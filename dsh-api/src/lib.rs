@@ -37,7 +37,7 @@
 //!
 //! For their parameters and return values the methods and functions in the crate
 //! make use of rust `struct`s that where generated from the DSH resource management API
-//! Openapi specification (version 1.10.0).
+//! Openapi specification (by default version 1.10.0, see [`openapi_version()`]).
 //!
 //! The generated types are defined as follows:
 //!
@@ -119,9 +119,15 @@
 //!
 //! The following features are defined:
 //!
+//! * `api-1-8` - Generate the client from openapi specification version 1.8.0.
+//! * `api-1-9` - Generate the client from openapi specification version 1.9.2.
+//! * `api-1-10` - Generate the client from openapi specification version 1.10.0 (default).
 //! * `generic` - Enables the generic methods.
 //! * `manage` -  Enables the manage methods.
+//! * `mock` - Enables the in-process mock server for tests.
+//! * `offline` - Enables the offline read-only client, implies `mock`.
 //! * `robot` - Enables the robot operation.
+//! * `testing` - Enables the in-memory fake implementation of the api traits.
 /// # Types generated from openapi file
 pub use crate::generated::types;
 use std::cmp::Ordering;
@@ -133,7 +139,9 @@ pub(crate) mod generated {
 
 include!(concat!(env!("OUT_DIR"), "/wrapped.rs"));
 
-/// Openapi specification version 1.10.0
+/// Openapi specification that the crate has been generated from
+///
+/// See [`openapi_version()`] for the version of this specification.
 pub static OPENAPI_SPEC: &str = include_str!(concat!(env!("OUT_DIR"), "/openapi.json"));
 
 /// Specification of default platforms
@@ -141,7 +149,7 @@ pub static DEFAULT_PLATFORMS: &str = include_str!("../default-platforms.json");
 
 use crate::token_fetcher::ManagementApiTokenError;
 use crate::types::error::ConversionError;
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use itertools::Itertools;
 use log::{debug, error, trace};
 use progenitor_client::Error as ProgenitorError;
@@ -152,12 +160,18 @@ use serde_json::Error as SerdeJsonError;
 use std::error::Error as StdError;
 use std::fmt::{Debug, Display, Formatter};
 use std::str::Utf8Error;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+#[cfg(not(dsh_api_1_8))]
+pub mod acl_group;
+pub mod api;
 pub mod app;
 pub mod application;
 pub mod application_types;
 pub mod bucket;
+pub mod builder;
 pub mod certificate;
+pub mod data_catalog;
 pub mod database;
 pub mod default;
 pub mod display;
@@ -165,20 +179,35 @@ pub mod dsh_api_client;
 pub mod dsh_api_client_factory;
 pub mod dsh_api_tenant;
 pub mod dsh_jwt;
+#[cfg(feature = "testing")]
+pub mod fake;
+pub mod flink;
 #[cfg(feature = "generic")]
 pub mod generic;
+pub mod id;
+pub mod kafka_proxy;
 pub mod manifest;
+#[cfg(feature = "mock")]
+pub mod mock_server;
 pub mod new;
+#[cfg(feature = "offline")]
+pub mod offline;
 pub mod parse;
 pub mod platform;
 pub mod query_processor;
+pub mod resource;
 pub mod secret;
-#[cfg(feature = "manage")]
+pub mod snapshot;
+#[cfg(all(feature = "manage", not(dsh_api_1_8)))]
 pub mod stream;
+pub mod streaming;
+pub mod task;
 #[cfg(feature = "manage")]
 pub mod tenant;
+pub mod third_party_bucket;
 pub mod token_fetcher;
 pub mod topic;
+pub mod validation;
 pub mod version;
 pub mod vhost;
 pub mod volume;
@@ -197,10 +226,14 @@ pub fn crate_version() -> &'static str {
 /// # Returns the version of the openapi spec
 ///
 /// Version number of the openapi file that the crate has been generated from.
+/// By default this is the newest supported version (1.10.0).
+/// An older version can be selected at build time by enabling one of the features
+/// `api-1-8` or `api-1-9`, or by setting the environment variable `DSH_API_OPENAPI_VERSION`.
 ///
 /// ## Example
 ///
 /// ```
+/// # #[cfg(dsh_api_1_10)]
 /// assert_eq!(dsh_api::openapi_version(), "1.10.0");
 /// ```
 pub fn openapi_version() -> &'static str {
@@ -545,6 +578,11 @@ pub(crate) fn epoch_milliseconds_to_string(timestamp: impl Into<i64>) -> String
   epoch_seconds_to_string(timestamp.into() / 1000)
 }
 
+// Returns the current moment
+pub(crate) fn now() -> DateTime<Utc> {
+  DateTime::<Utc>::from_timestamp(SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64, 0).unwrap_or_default()
+}
+
 #[test]
 fn test_epoch_seconds_to_string() {
   const REPRESENTATION: &str = "2000-01-01 00:00:00 UTC";
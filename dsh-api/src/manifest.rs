@@ -65,7 +65,8 @@ impl DshApiClient {
   /// * `Ok<Manifest>` - manifest
   /// * `Err<`[`DshApiError::NotFound`]`>` - when the manifest could not be found
   /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
-  pub async fn manifest(&self, manifest_id: &str, manifest_version: &Version) -> DshApiResult<Manifest> {
+  pub async fn manifest(&self, manifest_id: impl AsRef<str>, manifest_version: &Version) -> DshApiResult<Manifest> {
+    let manifest_id = manifest_id.as_ref();
     self
       .manifests()
       .await?
@@ -87,9 +88,10 @@ impl DshApiClient {
   /// * `Ok<Manifest>` - Manifest.
   /// * `Err<`[`DshApiError::NotFound`]`>` - When no manifest could be found.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed.
-  pub async fn manifest_latest_version(&self, manifest_id: &str, allow_draft_version: bool) -> DshApiResult<Manifest> {
+  pub async fn manifest_latest_version(&self, manifest_id: impl AsRef<str>, allow_draft_version: bool) -> DshApiResult<Manifest> {
+    let manifest_id = manifest_id.as_ref();
     match self.manifest_all_versions(manifest_id).await {
-      Ok(manifests) => match manifests.into_iter().filter(|manifest| !manifest.draft || allow_draft_version).next_back() {
+      Ok(manifests) => match manifests.into_iter().rfind(|manifest| !manifest.draft || allow_draft_version) {
         Some(latest_manifest) => Ok(latest_manifest),
         None => Err(DshApiError::NotFound(None)),
       },
@@ -108,7 +110,8 @@ impl DshApiClient {
   /// * `Ok<Vec<manifest>>` - List of version/manifest pairs sorted by version.
   /// * `Err<`[`DshApiError::NotFound`]`>` - When the manifest could not be found.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed.
-  pub async fn manifest_all_versions(&self, manifest_id: &str) -> DshApiResult<Vec<Manifest>> {
+  pub async fn manifest_all_versions(&self, manifest_id: impl AsRef<str>) -> DshApiResult<Vec<Manifest>> {
+    let manifest_id = manifest_id.as_ref();
     let mut manifests: Vec<Manifest> = self.manifests().await?.into_iter().filter(|manifest| manifest.id == manifest_id).collect_vec();
     if manifests.is_empty() {
       Err(DshApiError::NotFound(None))
@@ -139,7 +142,8 @@ impl DshApiClient {
   /// * `Ok<(manifest, draft)>` - Manifest as a json formatted string.
   /// * `Err<`[`DshApiError::NotFound`]`>` - When the manifest could not be found.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed.
-  pub async fn manifest_raw(&self, manifest_id: &str, manifest_version: &Version) -> DshApiResult<(String, bool)> {
+  pub async fn manifest_raw(&self, manifest_id: impl AsRef<str>, manifest_version: &Version) -> DshApiResult<(String, bool)> {
+    let manifest_id = manifest_id.as_ref();
     for app_catalog_manifest in self.get_appcatalog_manifests().await?.iter() {
       let payload = from_str::<HashMap<String, Value>>(app_catalog_manifest.payload.as_str())?;
       if payload.get("id").is_some_and(|payload_id| payload_id.as_str().unwrap() == manifest_id)
@@ -166,7 +170,8 @@ impl DshApiClient {
   ///   formatted string and whether the manifest is a draft.
   /// * `Err<`[`DshApiError::NotFound`]`>` - When the manifest could not be found.
   /// * `Err<`[`DshApiError`]`>` - When the request could not be processed.
-  pub async fn manifest_raw_latest(&self, manifest_id: &str, allow_draft_version: bool) -> DshApiResult<(Version, String, bool)> {
+  pub async fn manifest_raw_latest(&self, manifest_id: impl AsRef<str>, allow_draft_version: bool) -> DshApiResult<(Version, String, bool)> {
+    let manifest_id = manifest_id.as_ref();
     let mut raw_manifests: Vec<(Version, bool, HashMap<String, Value>)> = self
       .get_appcatalog_manifests()
       .await?
@@ -0,0 +1,503 @@
+//! # Mock server for tests
+//!
+//! Module that contains an in-process HTTP server that mocks the DSH resource management API.
+//! The server implements all allocation, app catalog and manage operations from the openapi
+//! specification on an in-memory store. The table that describes how each operation
+//! is handled is generated from the openapi specification at build time.
+//!
+//! * `PUT` and `POST` requests store the request body, after it has been validated
+//!   against the openapi specification.
+//! * `GET` requests return what was stored. The `/actual` and `/status` operations mirror
+//!   the stored configuration, and operations that return a list of ids or a map
+//!   return the stored resources below the requested path.
+//! * `DELETE` requests remove the stored resource.
+//!
+//! For each operation, identified by its method and its path as it appears in the openapi
+//! specification, an error status or a latency can be injected.
+//!
+//! This module is only available when the `mock` feature is enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use dsh_api::dsh_api_client_factory::DshApiClientFactory;
+//! use dsh_api::dsh_api_tenant::DshApiTenant;
+//! use dsh_api::mock_server::MockServer;
+//! use dsh_api::platform::DshPlatform;
+//! use dsh_api::types::Volume;
+//!
+//! # use dsh_api::DshApiError;
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), DshApiError> {
+//! let server = MockServer::start().unwrap();
+//! let tenant = DshApiTenant::new("my-tenant".to_string(), DshPlatform::new("nplz"));
+//! let client = DshApiClientFactory::create_from_access_token(tenant, "token".to_string())
+//!   .with_endpoint(&server.url())
+//!   .client()
+//!   .await?;
+//! client.put_volume_configuration("my-volume", &Volume::new(10)).await?;
+//! assert_eq!(client.get_volume_actual("my-volume").await?.size_gi_b, 10);
+//! assert_eq!(client.get_volume_ids().await?, vec!["my-volume".to_string()]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Methods
+//!
+//! * [`start() -> mock server`](MockServer::start)
+//! * [`clear_injections()`](MockServer::clear_injections)
+//! * [`inject_error(method, path, status)`](MockServer::inject_error)
+//! * [`inject_latency(method, path, latency)`](MockServer::inject_latency)
+//! * [`insert(resource path, value)`](MockServer::insert)
+//! * [`requests() -> [request]`](MockServer::requests)
+//! * [`resource(resource path) -> value`](MockServer::resource)
+//! * [`resource_paths() -> [resource path]`](MockServer::resource_paths)
+//! * [`url() -> url`](MockServer::url)
+
+use crate::validation::validate_request_body;
+use itertools::Itertools;
+use log::debug;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+include!(concat!(env!("OUT_DIR"), "/mock_operations.rs"));
+
+/// Describes how the mock server handles an operation
+pub(crate) struct MockOperation {
+  pub(crate) method: &'static str,
+  pub(crate) path: &'static str,
+  kind: MockOperationKind,
+  status: u16,
+}
+
+/// Ways in which the mock server handles an operation
+///
+/// Which kinds are used depends on the openapi specification and the enabled features.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum MockOperationKind {
+  /// Return the stored configuration as configuration and actual value, together with a status
+  Aggregate,
+  /// Remove the stored resource and all resources below it
+  Delete,
+  /// Return an empty object when the resource exists
+  Empty,
+  /// Return the ids of the resources directly below the path
+  Ids,
+  /// Return a map with the ids and values of the resources directly below the path
+  Map,
+  /// Return a provisioned status when the resource exists
+  Status,
+  /// Store the request body
+  Store,
+  /// Store each element of the request body, which must be an array of named objects
+  StoreEach,
+  /// Store the request body below the path, using its `name` field as id
+  StoreNamed,
+  /// Store the plain text request body
+  StoreText,
+  /// Return the stored plain text value
+  Text,
+  /// Return the stored value
+  Value,
+  /// Return the values of the resources directly below the path
+  Values,
+}
+
+/// # In-process mock server
+///
+/// The server is started with [`MockServer::start()`] and is stopped when it is dropped.
+pub struct MockServer {
+  address: SocketAddr,
+  state: Arc<Mutex<MockState>>,
+  stopped: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct MockState {
+  resources: BTreeMap<String, Value>,
+  errors: HashMap<(String, String), u16>,
+  latencies: HashMap<(String, String), Duration>,
+  requests: Vec<String>,
+}
+
+struct MockResponse {
+  status: u16,
+  content_type: &'static str,
+  body: String,
+}
+
+impl MockServer {
+  /// # Start a mock server
+  ///
+  /// The server listens on a random free port on `localhost`.
+  ///
+  /// # Returns
+  /// * `Ok<MockServer>` - the started server
+  /// * `Err<std::io::Error>` - when the server could not be started
+  pub fn start() -> std::io::Result<Self> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let address = listener.local_addr()?;
+    let state = Arc::new(Mutex::new(MockState::default()));
+    let stopped = Arc::new(AtomicBool::new(false));
+    let (thread_state, thread_stopped) = (state.clone(), stopped.clone());
+    thread::spawn(move || {
+      for stream in listener.incoming() {
+        if thread_stopped.load(Ordering::SeqCst) {
+          break;
+        }
+        if let Ok(stream) = stream {
+          let connection_state = thread_state.clone();
+          thread::spawn(move || handle_connection(stream, &connection_state));
+        }
+      }
+    });
+    debug!("mock server started on {}", address);
+    Ok(Self { address, state, stopped })
+  }
+
+  /// # Returns the url of the mock server
+  ///
+  /// Use this url as the endpoint of the client, see
+  /// [`DshApiClientFactory::with_endpoint()`](crate::dsh_api_client_factory::DshApiClientFactory::with_endpoint).
+  pub fn url(&self) -> String {
+    format!("http://{}", self.address)
+  }
+
+  /// # Store a resource
+  ///
+  /// # Parameters
+  /// * `path` - path of the resource, e.g. `/allocation/my-tenant/topic/my-topic`
+  ///   (a `/configuration` postfix will be ignored)
+  /// * `value` - value of the resource
+  pub fn insert(&self, path: &str, value: Value) {
+    self.state.lock().unwrap().resources.insert(resource_path(path).to_string(), value);
+  }
+
+  /// # Returns a stored resource
+  ///
+  /// # Parameters
+  /// * `path` - path of the resource, e.g. `/allocation/my-tenant/topic/my-topic`
+  ///   (a `/configuration` postfix will be ignored)
+  pub fn resource(&self, path: &str) -> Option<Value> {
+    self.state.lock().unwrap().resources.get(resource_path(path)).cloned()
+  }
+
+  /// # Returns the paths of all stored resources, sorted
+  pub fn resource_paths(&self) -> Vec<String> {
+    self.state.lock().unwrap().resources.keys().cloned().collect_vec()
+  }
+
+  /// # Inject an error
+  ///
+  /// All subsequent requests for the operation will be answered with the error status.
+  ///
+  /// # Parameters
+  /// * `method` - method of the operation, e.g. `GET`
+  /// * `path` - path of the operation as it appears in the openapi specification,
+  ///   e.g. `/allocation/{tenant}/topic/{id}/configuration`
+  /// * `status` - status code of the error response, e.g. `500`
+  pub fn inject_error(&self, method: &str, path: &str, status: u16) {
+    self.state.lock().unwrap().errors.insert((method.to_uppercase(), path.to_string()), status);
+  }
+
+  /// # Inject latency
+  ///
+  /// All subsequent requests for the operation will be delayed.
+  ///
+  /// # Parameters
+  /// * `method` - method of the operation, e.g. `GET`
+  /// * `path` - path of the operation as it appears in the openapi specification,
+  ///   e.g. `/allocation/{tenant}/topic/{id}/configuration`
+  /// * `latency` - delay before the request will be handled
+  pub fn inject_latency(&self, method: &str, path: &str, latency: Duration) {
+    self.state.lock().unwrap().latencies.insert((method.to_uppercase(), path.to_string()), latency);
+  }
+
+  /// # Remove all injected errors and latencies
+  pub fn clear_injections(&self) {
+    let mut state = self.state.lock().unwrap();
+    state.errors.clear();
+    state.latencies.clear();
+  }
+
+  /// # Returns all handled requests, in the order in which they were received
+  ///
+  /// Each request is described by its method and path, e.g. `GET /allocation/my-tenant/topic`.
+  pub fn requests(&self) -> Vec<String> {
+    self.state.lock().unwrap().requests.clone()
+  }
+}
+
+impl Drop for MockServer {
+  fn drop(&mut self) {
+    self.stopped.store(true, Ordering::SeqCst);
+    // Wake up the listener thread, which will stop since the stopped flag is set
+    let _ = TcpStream::connect(self.address);
+  }
+}
+
+fn handle_connection(stream: TcpStream, state: &Mutex<MockState>) {
+  let mut reader = BufReader::new(&stream);
+  let mut request_line = String::new();
+  if reader.read_line(&mut request_line).is_err() || request_line.trim().is_empty() {
+    return;
+  }
+  let mut content_length = 0;
+  loop {
+    let mut header = String::new();
+    if reader.read_line(&mut header).is_err() || header.trim().is_empty() {
+      break;
+    }
+    if let Some((name, value)) = header.split_once(':') {
+      if name.trim().eq_ignore_ascii_case("content-length") {
+        content_length = value.trim().parse().unwrap_or_default();
+      }
+    }
+  }
+  let mut body = vec![0; content_length];
+  if reader.read_exact(&mut body).is_err() {
+    return;
+  }
+  let mut request_parts = request_line.split_whitespace();
+  let (method, target) = (request_parts.next().unwrap_or_default(), request_parts.next().unwrap_or_default());
+  let path = percent_decode(target.split('?').next().unwrap_or_default());
+  let response = handle_request(state, method, &path, &String::from_utf8_lossy(&body));
+  debug!("mock server {} {} -> {}", method, path, response.status);
+  let _ = write!(
+    &stream,
+    "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+    response.status,
+    reason_phrase(response.status),
+    response.content_type,
+    response.body.len(),
+    response.body
+  );
+}
+
+fn handle_request(state: &Mutex<MockState>, method: &str, path: &str, body: &str) -> MockResponse {
+  let operation = MOCK_OPERATIONS
+    .iter()
+    .filter(|operation| operation.method == method)
+    .filter_map(|operation| matching_literals(operation.path, path).map(|literals| (literals, operation)))
+    .max_by_key(|(literals, _)| *literals)
+    .map(|(_, operation)| operation);
+  let Some(operation) = operation else { return MockResponse::text(404, format!("no operation for {} {}", method, path)) };
+  let (error, latency) = {
+    let mut state = state.lock().unwrap();
+    state.requests.push(format!("{} {}", method, path));
+    let key = (operation.method.to_string(), operation.path.to_string());
+    (state.errors.get(&key).cloned(), state.latencies.get(&key).cloned())
+  };
+  if let Some(latency) = latency {
+    thread::sleep(latency);
+  }
+  if let Some(status) = error {
+    return MockResponse::text(status, format!("injected error for {} {}", operation.method, operation.path));
+  }
+  let mut state = state.lock().unwrap();
+  let resources = &mut state.resources;
+  let resource = resource_path(path);
+  match operation.kind {
+    MockOperationKind::Aggregate => match resources.get(resource) {
+      Some(value) => MockResponse::json(200, &json!({ "actual": value, "configuration": value, "status": provisioned_status() })),
+      None => MockResponse::not_found(path),
+    },
+    MockOperationKind::Delete => {
+      if resources.remove(resource).is_some() {
+        resources.retain(|key, _| !key.starts_with(&format!("{}/", resource)));
+        MockResponse::empty(operation.status)
+      } else {
+        MockResponse::not_found(path)
+      }
+    }
+    MockOperationKind::Empty => match resources.get(resource) {
+      Some(_) => MockResponse::json(operation.status, &json!({})),
+      None => MockResponse::not_found(path),
+    },
+    MockOperationKind::Ids => MockResponse::json(operation.status, &Value::from(children(resources, path).map(|(id, _)| id).collect_vec())),
+    MockOperationKind::Map => MockResponse::json(
+      operation.status,
+      &Value::Object(children(resources, resource).map(|(id, value)| (id, value.clone())).collect::<Map<String, Value>>()),
+    ),
+    MockOperationKind::Status => match resources.get(resource) {
+      Some(_) => MockResponse::json(operation.status, &provisioned_status()),
+      None => MockResponse::not_found(path),
+    },
+    MockOperationKind::Store => match parse_body(operation, body) {
+      Ok(value) => {
+        resources.insert(resource.to_string(), value);
+        MockResponse::empty(operation.status)
+      }
+      Err(response) => response,
+    },
+    MockOperationKind::StoreEach => match parse_body(operation, body) {
+      Ok(Value::Array(values)) => {
+        for value in values {
+          if let Some(name) = value.get("name").and_then(Value::as_str).map(str::to_string) {
+            resources.insert(format!("{}/{}", resource, name), value);
+          }
+        }
+        MockResponse::empty(operation.status)
+      }
+      Ok(_) => MockResponse::text(400, "request body must be an array".to_string()),
+      Err(response) => response,
+    },
+    MockOperationKind::StoreNamed => match parse_body(operation, body) {
+      Ok(value) => match value.get("name").and_then(Value::as_str).map(str::to_string) {
+        Some(name) => {
+          resources.insert(format!("{}/{}", resource, name), value);
+          MockResponse::empty(operation.status)
+        }
+        None => MockResponse::text(400, "request body has no name".to_string()),
+      },
+      Err(response) => response,
+    },
+    MockOperationKind::StoreText => {
+      match resources.get_mut(resource) {
+        Some(Value::Object(object)) => {
+          object.insert("value".to_string(), Value::from(body));
+        }
+        _ => {
+          resources.insert(resource.to_string(), Value::from(body));
+        }
+      }
+      MockResponse::empty(operation.status)
+    }
+    MockOperationKind::Text => match resources.get(resource) {
+      Some(Value::String(text)) => MockResponse::text(operation.status, text.to_string()),
+      Some(Value::Object(object)) if object.get("value").is_some_and(Value::is_string) => {
+        MockResponse::text(operation.status, object.get("value").and_then(Value::as_str).unwrap_or_default().to_string())
+      }
+      _ => MockResponse::not_found(path),
+    },
+    MockOperationKind::Value => match resources.get(resource) {
+      Some(value) => MockResponse::json(operation.status, value),
+      None => MockResponse::not_found(path),
+    },
+    MockOperationKind::Values => MockResponse::json(
+      operation.status,
+      &Value::from(children(resources, path).map(|(_, value)| value.clone()).collect_vec()),
+    ),
+  }
+}
+
+impl MockResponse {
+  fn empty(status: u16) -> Self {
+    Self { status, content_type: "text/plain", body: "".to_string() }
+  }
+
+  fn json(status: u16, value: &Value) -> Self {
+    Self { status, content_type: "application/json", body: value.to_string() }
+  }
+
+  fn not_found(path: &str) -> Self {
+    Self::text(404, format!("{} not found", path))
+  }
+
+  fn text(status: u16, text: String) -> Self {
+    Self { status, content_type: "text/plain", body: text }
+  }
+}
+
+// Parses and validates a json request body, an empty request body results in an empty object
+fn parse_body(operation: &MockOperation, body: &str) -> Result<Value, MockResponse> {
+  if body.trim().is_empty() {
+    return Ok(json!({}));
+  }
+  let value = serde_json::from_str::<Value>(body).map_err(|error| MockResponse::text(400, format!("invalid json ({})", error)))?;
+  match validate_request_body(&operation.method.to_lowercase(), operation.path, &value) {
+    Ok(validation_errors) if !validation_errors.is_empty() => Err(MockResponse::text(400, validation_errors.iter().join(", "))),
+    _ => Ok(value),
+  }
+}
+
+// Returns the ids and values of the resources directly below the path
+fn children<'a>(resources: &'a BTreeMap<String, Value>, path: &str) -> impl Iterator<Item = (String, &'a Value)> {
+  let prefix = format!("{}/", path);
+  resources.iter().filter_map(move |(key, value)| match key.strip_prefix(&prefix) {
+    Some(id) if !id.contains('/') => Some((id.to_string(), value)),
+    _ => None,
+  })
+}
+
+// Returns the number of literal path elements when the path matches the operation path, else `None`
+fn matching_literals(operation_path: &str, path: &str) -> Option<usize> {
+  let (operation_elements, path_elements) = (operation_path.split('/').collect_vec(), path.split('/').collect_vec());
+  if operation_elements.len() != path_elements.len() {
+    return None;
+  }
+  let mut literals = 0;
+  for (operation_element, path_element) in operation_elements.iter().zip(path_elements.iter()) {
+    if operation_element.starts_with('{') {
+      if path_element.is_empty() {
+        return None;
+      }
+    } else if operation_element == path_element {
+      literals += 1;
+    } else {
+      return None;
+    }
+  }
+  Some(literals)
+}
+
+// Decodes percent-encoded characters in the path, invalid escape sequences are left as they are
+fn percent_decode(path: &str) -> String {
+  let bytes = path.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut index = 0;
+  while index < bytes.len() {
+    match (bytes[index], bytes.get(index + 1..index + 3)) {
+      (b'%', Some(hex)) => match std::str::from_utf8(hex).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+        Some(byte) => {
+          decoded.push(byte);
+          index += 3;
+        }
+        None => {
+          decoded.push(b'%');
+          index += 1;
+        }
+      },
+      (byte, _) => {
+        decoded.push(byte);
+        index += 1;
+      }
+    }
+  }
+  String::from_utf8_lossy(&decoded).to_string()
+}
+
+// Removes the `/configuration`, `/actual` or `/status` postfix from the path
+fn resource_path(path: &str) -> &str {
+  ["/configuration", "/actual", "/status"]
+    .iter()
+    .find_map(|postfix| path.strip_suffix(postfix))
+    .unwrap_or(path)
+}
+
+fn provisioned_status() -> Value {
+  json!({ "notifications": [], "provisioned": true })
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+  match status {
+    200 => "OK",
+    201 => "Created",
+    202 => "Accepted",
+    204 => "No Content",
+    400 => "Bad Request",
+    401 => "Unauthorized",
+    403 => "Forbidden",
+    404 => "Not Found",
+    500 => "Internal Server Error",
+    503 => "Service Unavailable",
+    _ => "Unknown",
+  }
+}
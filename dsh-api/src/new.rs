@@ -1,6 +1,11 @@
 //! # New functions for selected types
 //!
-//! This module provides some `new` constructor/factory functions for selected types.
+//! This module provides `new` constructor/factory functions for the generated types.
+//! For most types with named fields the function is generated from the openapi specification
+//! at build time. The required fields will be the parameters of the generated function,
+//! except for fields that can have only one value, which will be set automatically.
+//! Optional fields will be set to `None` or to their default value from the specification.
+//! The types listed below have a hand-written function.
 //!
 //! * [`AllocationStatus::new(derived from, provisioned)`](AllocationStatus::new)
 //! * [`AppCatalogApp::new(name, manifest urn)`](AppCatalogApp::new)
@@ -32,14 +37,18 @@
 
 use crate::types::{
   AllocationStatus, AppCatalogApp, AppCatalogAppConfiguration, Application, ApplicationSecret, ApplicationVolumes, Bucket, Empty, HealthCheck, HealthCheckProtocol,
-  LimitValueCertificateCount, LimitValueCertificateCountName, LimitValueConsumerRate, LimitValueConsumerRateName, LimitValueCpu, LimitValueCpuName, LimitValueKafkaAclGroupCount,
-  LimitValueKafkaAclGroupCountName, LimitValueMem, LimitValueMemName, LimitValuePartitionCount, LimitValuePartitionCountName, LimitValueProducerRate, LimitValueProducerRateName,
-  LimitValueRequestRate, LimitValueRequestRateName, LimitValueSecretCount, LimitValueSecretCountName, LimitValueTopicCount, LimitValueTopicCountName, ManagedStreamId,
-  ManagedTenant, ManagedTenantServices, ManagedTenantServicesName, Metrics, Notification, PathSpec, Secret, Vhost, Volume,
+  LimitValueCertificateCount, LimitValueCertificateCountName, LimitValueConsumerRate, LimitValueConsumerRateName, LimitValueCpu, LimitValueCpuName, LimitValueMem,
+  LimitValueMemName, LimitValuePartitionCount, LimitValuePartitionCountName, LimitValueProducerRate, LimitValueProducerRateName, LimitValueRequestRate, LimitValueRequestRateName,
+  LimitValueSecretCount, LimitValueSecretCountName, LimitValueTopicCount, LimitValueTopicCountName, ManagedTenant, ManagedTenantServices, ManagedTenantServicesName, Metrics,
+  Notification, PathSpec, Secret, Vhost, Volume,
 };
+#[cfg(not(dsh_api_1_8))]
+use crate::types::{LimitValueKafkaAclGroupCount, LimitValueKafkaAclGroupCountName, ManagedStreamId};
 use itertools::Itertools;
 use std::collections::HashMap;
+#[cfg(not(dsh_api_1_8))]
 use std::fmt::Display;
+#[cfg(not(dsh_api_1_8))]
 use std::str::FromStr;
 
 impl AllocationStatus {
@@ -390,6 +399,7 @@ impl LimitValueCpu {
   }
 }
 
+#[cfg(not(dsh_api_1_8))]
 impl LimitValueKafkaAclGroupCount {
   /// # Create a new `LimitValueKafkaAclGroupCount`
   ///
@@ -565,6 +575,8 @@ impl LimitValueTopicCount {
   }
 }
 
+// Managed stream ids have a different format in openapi specification version 1.8.0
+#[cfg(not(dsh_api_1_8))]
 impl ManagedStreamId {
   /// # Create a new `ManagedStreamId`
   ///
@@ -817,3 +829,7 @@ impl Volume {
     Self { size_gi_b }
   }
 }
+
+mod generated {
+  include!(concat!(env!("OUT_DIR"), "/generated_new.rs"));
+}
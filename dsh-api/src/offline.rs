@@ -0,0 +1,222 @@
+//! # Offline read-only client
+//!
+//! Module that contains the [`DshApiOfflineClient`], which provides the read-only surface
+//! of the [`DshApiClient`] from a directory with exported json files,
+//! without connecting to the DSH and without credentials.
+//! This allows dependency reports, searches and comparisons to run on an air-gapped machine
+//! or in a CI pipeline.
+//!
+//! The offline client dereferences to a [`DshApiClient`] that is connected to an in-process
+//! [`MockServer`], which is loaded from the exported files. Hence all read methods,
+//! both the generated and the derived methods, are available with the same signatures.
+//! All methods that would change a resource return a [`DshApiError::NotAuthorized`] error.
+//!
+//! The directory can contain the files below. A missing file means that the tenant
+//! has no resources of that kind. The file names are derived from the names of the methods
+//! that return the same data.
+//!
+//! | file                                   | contents                                               |
+//! | -------------------------------------- | ------------------------------------------------------ |
+//! | `application-configuration-map.json`   | map from application ids to application configurations |
+//! | `appcatalogapp-configuration-map.json` | map from app ids to app catalog app configurations     |
+//! | `bucket-configuration-map.json`        | map from bucket ids to bucket configurations           |
+//! | `certificate-configuration-map.json`   | map from certificate ids to certificate configurations |
+//! | `manifests.json`                       | list of app catalog manifests                          |
+//! | `secret-ids.json`                      | list of secret ids                                     |
+//! | `topic-configuration-map.json`         | map from topic ids to topic configurations             |
+//! | `volume-configuration-map.json`        | map from volume ids to volume configurations           |
+//!
+//! Secret values are never exported, so [`get_secret()`](DshApiClient::get_secret)
+//! will return a [`DshApiError::NotFound`] error. The actual state and the status of a resource
+//! are derived from its configuration, and the actual state is reported as provisioned.
+//!
+//! This module is only available when the `offline` feature is enabled.
+//!
+//! # Derived methods
+//!
+//! [`DshApiClient`] methods that add extra capabilities but do not directly call the
+//! DSH resource management API. These derived methods depend on the API methods for this.
+//!
+//! * [`export_to_directory(directory, concurrency)`](DshApiClient::export_to_directory)
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dsh_api::dsh_api_tenant::DshApiTenant;
+//! use dsh_api::offline::DshApiOfflineClient;
+//! use dsh_api::platform::DshPlatform;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let tenant = DshApiTenant::new("my-tenant".to_string(), DshPlatform::new("nplz"));
+//! let client = DshApiOfflineClient::from_directory("info", tenant).await?;
+//! for (secret_id, dependants) in client.secrets_with_dependants().await? {
+//!   println!("{} -> {} dependants", secret_id, dependants.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::dsh_api_client::DshApiClient;
+use crate::dsh_api_client_factory::DshApiClientFactory;
+use crate::dsh_api_tenant::DshApiTenant;
+use crate::mock_server::{MockServer, MOCK_OPERATIONS};
+use crate::types::{Bucket, Certificate, Topic, Volume};
+use crate::{DshApiError, DshApiResult};
+use futures::{try_join, TryStreamExt};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::ops::Deref;
+use std::path::Path;
+
+const APPLICATION_CONFIGURATION_MAP: &str = "application-configuration-map.json";
+const APPCATALOGAPP_CONFIGURATION_MAP: &str = "appcatalogapp-configuration-map.json";
+const BUCKET_CONFIGURATION_MAP: &str = "bucket-configuration-map.json";
+const CERTIFICATE_CONFIGURATION_MAP: &str = "certificate-configuration-map.json";
+const MANIFESTS: &str = "manifests.json";
+const SECRET_IDS: &str = "secret-ids.json";
+const TOPIC_CONFIGURATION_MAP: &str = "topic-configuration-map.json";
+const VOLUME_CONFIGURATION_MAP: &str = "volume-configuration-map.json";
+
+/// # Offline read-only client
+///
+/// Dereferences to a [`DshApiClient`] that serves the resources from a directory
+/// with exported json files. See the [module documentation](self) for the supported files.
+pub struct DshApiOfflineClient {
+  client: DshApiClient,
+  // The server must live as long as the client
+  _server: MockServer,
+}
+
+impl DshApiOfflineClient {
+  /// # Create an offline client from a directory with exported json files
+  ///
+  /// # Parameters
+  /// * `directory` - directory that contains the exported json files
+  /// * `tenant` - tenant that the exported resources belong to
+  ///
+  /// # Returns
+  /// * `Ok<DshApiOfflineClient>` - the offline client
+  /// * `Err<`[`DshApiError::Configuration`]`>` - when a file could not be read or parsed
+  pub async fn from_directory(directory: impl AsRef<Path>, tenant: DshApiTenant) -> DshApiResult<Self> {
+    let directory = directory.as_ref();
+    let server = MockServer::start().map_err(|error| DshApiError::Configuration(format!("could not start offline server ({})", error)))?;
+    let allocation = format!("/allocation/{}", tenant.name());
+    let appcatalog = format!("/appcatalog/{}", tenant.name());
+    for (application_id, application) in read_map(directory, APPLICATION_CONFIGURATION_MAP)? {
+      server.insert(&format!("{}/application/{}", allocation, application_id), application);
+    }
+    for (app_id, app) in read_map(directory, APPCATALOGAPP_CONFIGURATION_MAP)? {
+      server.insert(&format!("{}/appcatalogapp/{}", allocation, app_id), app.clone());
+      server.insert(&format!("{}/appcatalogapp/{}", appcatalog, app_id), app);
+    }
+    for (kind, file_name) in
+      [("bucket", BUCKET_CONFIGURATION_MAP), ("certificate", CERTIFICATE_CONFIGURATION_MAP), ("topic", TOPIC_CONFIGURATION_MAP), ("volume", VOLUME_CONFIGURATION_MAP)]
+    {
+      for (id, configuration) in read_map(directory, file_name)? {
+        server.insert(&format!("{}/{}/{}", allocation, kind, id), configuration);
+      }
+    }
+    for (index, manifest) in read_list(directory, MANIFESTS)?.into_iter().enumerate() {
+      server.insert(&format!("{}/manifest/{:06}", appcatalog, index), manifest);
+    }
+    for secret_id in read_list(directory, SECRET_IDS)? {
+      match secret_id {
+        Value::String(secret_id) => server.insert(&format!("{}/secret/{}", allocation, secret_id), Value::Object(Default::default())),
+        _ => return Err(DshApiError::Configuration(format!("file '{}' must contain a list of strings", SECRET_IDS))),
+      }
+    }
+    for operation in MOCK_OPERATIONS.iter().filter(|operation| operation.method != "GET") {
+      server.inject_error(operation.method, operation.path, 405);
+    }
+    let client = DshApiClientFactory::create_from_access_token(tenant, "offline".to_string())
+      .with_endpoint(&server.url())
+      .client()
+      .await?;
+    Ok(Self { client, _server: server })
+  }
+}
+
+impl Deref for DshApiOfflineClient {
+  type Target = DshApiClient;
+
+  fn deref(&self) -> &Self::Target {
+    &self.client
+  }
+}
+
+impl DshApiClient {
+  /// # Export the resources of the tenant to a directory
+  ///
+  /// Writes the json files that can be read by
+  /// [`DshApiOfflineClient::from_directory()`]. Secret values are not exported.
+  /// The configurations are retrieved one request per id,
+  /// with a bounded number of concurrent requests per resource kind.
+  ///
+  /// # Parameters
+  /// * `directory` - directory where the files will be written, which must exist
+  /// * `concurrency` - maximum number of concurrent configuration requests per resource kind,
+  ///   at least one request is executed
+  ///
+  /// # Returns
+  /// * `Ok(())` - when all files were written
+  /// * `Err<`[`DshApiError::Configuration`]`>` - when a file could not be written
+  /// * `Err<`[`DshApiError`]`>` - when a request could not be processed by the DSH
+  pub async fn export_to_directory(&self, directory: impl AsRef<Path>, concurrency: usize) -> DshApiResult<()> {
+    let directory = directory.as_ref();
+    let (applications, apps, manifests, secret_ids, buckets, certificates, topics, volumes) = try_join!(
+      self.get_application_configuration_map(),
+      self.get_appcatalogapp_configuration_map(),
+      self.get_appcatalog_manifests(),
+      self.get_secret_ids(),
+      self.configurations_stream::<Bucket>(concurrency).try_collect::<HashMap<_, _>>(),
+      self.configurations_stream::<Certificate>(concurrency).try_collect::<HashMap<_, _>>(),
+      self.configurations_stream::<Topic>(concurrency).try_collect::<HashMap<_, _>>(),
+      self.configurations_stream::<Volume>(concurrency).try_collect::<HashMap<_, _>>()
+    )?;
+    write_json(directory, APPLICATION_CONFIGURATION_MAP, &applications)?;
+    write_json(directory, APPCATALOGAPP_CONFIGURATION_MAP, &apps)?;
+    write_json(directory, BUCKET_CONFIGURATION_MAP, &buckets)?;
+    write_json(directory, CERTIFICATE_CONFIGURATION_MAP, &certificates)?;
+    write_json(directory, MANIFESTS, &manifests)?;
+    write_json(directory, SECRET_IDS, &secret_ids)?;
+    write_json(directory, TOPIC_CONFIGURATION_MAP, &topics)?;
+    write_json(directory, VOLUME_CONFIGURATION_MAP, &volumes)
+  }
+}
+
+// Reads a json file, a missing file results in `None`
+fn read_json(directory: &Path, file_name: &str) -> DshApiResult<Option<Value>> {
+  let path = directory.join(file_name);
+  match fs::read_to_string(&path) {
+    Ok(contents) => serde_json::from_str::<Value>(&contents)
+      .map(Some)
+      .map_err(|error| DshApiError::Configuration(format!("could not parse file '{}' ({})", path.display(), error))),
+    Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+    Err(error) => Err(DshApiError::Configuration(format!("could not read file '{}' ({})", path.display(), error))),
+  }
+}
+
+fn read_list(directory: &Path, file_name: &str) -> DshApiResult<Vec<Value>> {
+  match read_json(directory, file_name)? {
+    Some(Value::Array(values)) => Ok(values),
+    Some(_) => Err(DshApiError::Configuration(format!("file '{}' must contain a list", file_name))),
+    None => Ok(vec![]),
+  }
+}
+
+fn read_map(directory: &Path, file_name: &str) -> DshApiResult<Vec<(String, Value)>> {
+  match read_json(directory, file_name)? {
+    Some(Value::Object(map)) => Ok(map.into_iter().collect()),
+    Some(_) => Err(DshApiError::Configuration(format!("file '{}' must contain a map", file_name))),
+    None => Ok(vec![]),
+  }
+}
+
+fn write_json<T: Serialize>(directory: &Path, file_name: &str, value: &T) -> DshApiResult<()> {
+  let path = directory.join(file_name);
+  fs::write(&path, serde_json::to_string_pretty(value)?).map_err(|error| DshApiError::Configuration(format!("could not write file '{}' ({})", path.display(), error)))
+}
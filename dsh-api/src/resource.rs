@@ -0,0 +1,358 @@
+//! # Generic access to all resource kinds
+//!
+//! Module that contains the [`DshResource`] trait, which gives uniform access to the
+//! resources of a tenant, independent of their kind.
+//! The trait is implemented for the types that represent the resource kinds:
+//!
+//! | kind          | type            | configuration   | actual                | status               | injection           |
+//! | ------------- | --------------- | --------------- | --------------------- | -------------------- | ------------------- |
+//! | `application` | [`Application`] | [`Application`] | [`Application`]       | [`AllocationStatus`] | `String`            |
+//! | `bucket`      | [`Bucket`]      | [`Bucket`]      | [`Bucket`]            | [`AllocationStatus`] | [`BucketInjection`] |
+//! | `certificate` | [`Certificate`] | [`Certificate`] | [`ActualCertificate`] | [`AllocationStatus`] | `String`            |
+//! | `secret`      | [`Secret`]      | [`Empty`]       | [`Empty`]             | [`AllocationStatus`] | [`SecretInjection`] |
+//! | `topic`       | [`Topic`]       | [`Topic`]       | [`Topic`]             | [`AllocationStatus`] | [`TopicInjection`]  |
+//! | `vhost`       | [`Vhost`]       | [`Vhost`]       | [`Vhost`]             | [`AllocationStatus`] | [`VhostInjection`]  |
+//! | `volume`      | [`Volume`]      | [`Volume`]      | [`Volume`]            | [`AllocationStatus`] | [`VolumeInjection`] |
+//!
+//! Applications and certificates are not injected in other applications,
+//! so their dependants will only contain apps.
+//! Vhosts are not supported by the DSH resource management API, so the vhost ids and dependants
+//! are derived from the applications and apps, and the other methods will return an error.
+//!
+//! # Examples
+//!
+//! Generic tooling can be written once for all resource kinds:
+//!
+//! ```no_run
+//! use dsh_api::dsh_api_client::DshApiClient;
+//! use dsh_api::resource::DshResource;
+//! use dsh_api::types::{Topic, Volume};
+//! use dsh_api::DshApiResult;
+//!
+//! async fn unused<R: DshResource>(client: &DshApiClient) -> DshApiResult<Vec<String>> {
+//!   let mut unused_ids = vec![];
+//!   for id in R::list_ids(client).await? {
+//!     if R::dependants(client, &id).await?.is_empty() {
+//!       unused_ids.push(format!("{} {}", R::KIND, id));
+//!     }
+//!   }
+//!   Ok(unused_ids)
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # use dsh_api::dsh_api_client_factory::DshApiClientFactory;
+//! # let client = DshApiClientFactory::default().client().await?;
+//! println!("{:?}", unused::<Topic>(&client).await?);
+//! println!("{:?}", unused::<Volume>(&client).await?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::app::apps_that_use_secret;
+use crate::application_types::ApplicationValues;
+use crate::bucket::BucketInjection;
+use crate::dsh_api_client::DshApiClient;
+use crate::secret::{secret_env_vars_from_applications, SecretInjection};
+use crate::topic::TopicInjection;
+use crate::types::{ActualCertificate, AllocationStatus, Application, Bucket, Certificate, Empty, Secret, Topic, Vhost, Volume};
+use crate::vhost::VhostInjection;
+use crate::volume::VolumeInjection;
+use crate::{Dependant, DshApiError, DshApiResult};
+use futures::try_join;
+use itertools::Itertools;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::{Debug, Display};
+use std::future::Future;
+
+/// # Resource of a tenant
+///
+/// Trait that describes a kind of resource, with its associated types and generic methods
+/// to list, get, delete and find the dependants of the resources of this kind.
+/// The methods call the corresponding kind-specific methods of the [`DshApiClient`].
+pub trait DshResource {
+  /// Name of the resource kind, e.g. `topic`
+  const KIND: &'static str;
+
+  /// Type of the configuration of the resource
+  type Configuration: Clone + Debug + DeserializeOwned + Serialize + Send;
+
+  /// Type of the actual state of the resource
+  type Actual: Clone + Debug + DeserializeOwned + Serialize + Send;
+
+  /// Type of the allocation status of the resource
+  type Status: Clone + Debug + DeserializeOwned + Serialize + Send;
+
+  /// Type that describes how the resource is injected in a dependant application
+  type Injection: Clone + Debug + Display + Serialize + Send;
+
+  /// # Returns the sorted ids of all resources of this kind
+  fn list_ids(client: &DshApiClient) -> impl Future<Output = DshApiResult<Vec<String>>> + Send;
+
+  /// # Returns the configuration of a resource
+  fn configuration(client: &DshApiClient, id: &str) -> impl Future<Output = DshApiResult<Self::Configuration>> + Send;
+
+  /// # Returns the actual state of a resource
+  fn actual(client: &DshApiClient, id: &str) -> impl Future<Output = DshApiResult<Self::Actual>> + Send;
+
+  /// # Returns the allocation status of a resource
+  fn status(client: &DshApiClient, id: &str) -> impl Future<Output = DshApiResult<Self::Status>> + Send;
+
+  /// # Deletes a resource
+  fn delete(client: &DshApiClient, id: &str) -> impl Future<Output = DshApiResult<()>> + Send;
+
+  /// # Returns the applications and apps that depend on a resource
+  fn dependants(client: &DshApiClient, id: &str) -> impl Future<Output = DshApiResult<Vec<Dependant<Self::Injection>>>> + Send;
+}
+
+impl DshResource for Application {
+  const KIND: &'static str = "application";
+  type Configuration = Application;
+  type Actual = Application;
+  type Status = AllocationStatus;
+  type Injection = String;
+
+  async fn list_ids(client: &DshApiClient) -> DshApiResult<Vec<String>> {
+    client.application_ids().await
+  }
+
+  async fn configuration(client: &DshApiClient, id: &str) -> DshApiResult<Application> {
+    client.get_application_configuration(id).await
+  }
+
+  async fn actual(client: &DshApiClient, id: &str) -> DshApiResult<Application> {
+    client.get_application_actual(id).await
+  }
+
+  async fn status(client: &DshApiClient, id: &str) -> DshApiResult<AllocationStatus> {
+    client.get_application_status(id).await
+  }
+
+  async fn delete(client: &DshApiClient, id: &str) -> DshApiResult<()> {
+    client.delete_application_configuration(id).await
+  }
+
+  async fn dependants(client: &DshApiClient, id: &str) -> DshApiResult<Vec<Dependant<String>>> {
+    Ok(client.apps_dependant_on_application(id).await?.into_iter().map(Dependant::App).collect_vec())
+  }
+}
+
+impl DshResource for Bucket {
+  const KIND: &'static str = "bucket";
+  type Configuration = Bucket;
+  type Actual = Bucket;
+  type Status = AllocationStatus;
+  type Injection = BucketInjection;
+
+  async fn list_ids(client: &DshApiClient) -> DshApiResult<Vec<String>> {
+    client.get_bucket_ids().await
+  }
+
+  async fn configuration(client: &DshApiClient, id: &str) -> DshApiResult<Bucket> {
+    client.get_bucket_configuration(id).await
+  }
+
+  async fn actual(client: &DshApiClient, id: &str) -> DshApiResult<Bucket> {
+    client.get_bucket_actual(id).await
+  }
+
+  async fn status(client: &DshApiClient, id: &str) -> DshApiResult<AllocationStatus> {
+    client.get_bucket_status(id).await
+  }
+
+  async fn delete(client: &DshApiClient, id: &str) -> DshApiResult<()> {
+    client.delete_bucket_configuration(id).await
+  }
+
+  async fn dependants(client: &DshApiClient, id: &str) -> DshApiResult<Vec<Dependant<BucketInjection>>> {
+    Ok(client.bucket_with_dependants(id).await?.dependants)
+  }
+}
+
+impl DshResource for Certificate {
+  const KIND: &'static str = "certificate";
+  type Configuration = Certificate;
+  type Actual = ActualCertificate;
+  type Status = AllocationStatus;
+  type Injection = String;
+
+  async fn list_ids(client: &DshApiClient) -> DshApiResult<Vec<String>> {
+    client.get_certificate_ids().await
+  }
+
+  async fn configuration(client: &DshApiClient, id: &str) -> DshApiResult<Certificate> {
+    client.get_certificate_configuration(id).await
+  }
+
+  async fn actual(client: &DshApiClient, id: &str) -> DshApiResult<ActualCertificate> {
+    client.get_certificate_actual(id).await
+  }
+
+  async fn status(client: &DshApiClient, id: &str) -> DshApiResult<AllocationStatus> {
+    client.get_certificate_status(id).await
+  }
+
+  async fn delete(client: &DshApiClient, id: &str) -> DshApiResult<()> {
+    client.delete_certificate_configuration(id).await
+  }
+
+  async fn dependants(client: &DshApiClient, id: &str) -> DshApiResult<Vec<Dependant<String>>> {
+    Ok(client.apps_dependant_on_certificate(id).await?.into_iter().map(Dependant::App).collect_vec())
+  }
+}
+
+impl DshResource for Secret {
+  const KIND: &'static str = "secret";
+  type Configuration = Empty;
+  type Actual = Empty;
+  type Status = AllocationStatus;
+  type Injection = SecretInjection;
+
+  async fn list_ids(client: &DshApiClient) -> DshApiResult<Vec<String>> {
+    client.get_secret_ids().await
+  }
+
+  async fn configuration(client: &DshApiClient, id: &str) -> DshApiResult<Empty> {
+    client.get_secret_configuration(id).await
+  }
+
+  async fn actual(client: &DshApiClient, id: &str) -> DshApiResult<Empty> {
+    client.get_secret_actual(id).await
+  }
+
+  async fn status(client: &DshApiClient, id: &str) -> DshApiResult<AllocationStatus> {
+    client.get_secret_status(id).await
+  }
+
+  async fn delete(client: &DshApiClient, id: &str) -> DshApiResult<()> {
+    client.delete_secret_configuration(id).await
+  }
+
+  async fn dependants(client: &DshApiClient, id: &str) -> DshApiResult<Vec<Dependant<SecretInjection>>> {
+    let (applications, apps) = try_join!(client.get_application_configuration_map(), client.get_appcatalogapp_configuration_map())?;
+    let mut dependants: Vec<Dependant<SecretInjection>> = vec![];
+    for ApplicationValues { id, application, values } in secret_env_vars_from_applications(id, &applications) {
+      dependants.push(Dependant::application(
+        id.to_string(),
+        application.instances,
+        values.iter().map(|env_var| SecretInjection::EnvVar(env_var.to_string())).collect_vec(),
+      ));
+    }
+    for (app_id, _, resource_ids) in apps_that_use_secret(id, &apps) {
+      dependants.push(Dependant::app(
+        app_id.to_string(),
+        resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec(),
+      ));
+    }
+    Ok(dependants)
+  }
+}
+
+impl DshResource for Topic {
+  const KIND: &'static str = "topic";
+  type Configuration = Topic;
+  type Actual = Topic;
+  type Status = AllocationStatus;
+  type Injection = TopicInjection;
+
+  async fn list_ids(client: &DshApiClient) -> DshApiResult<Vec<String>> {
+    client.get_topic_ids().await
+  }
+
+  async fn configuration(client: &DshApiClient, id: &str) -> DshApiResult<Topic> {
+    client.get_topic_configuration(id).await
+  }
+
+  async fn actual(client: &DshApiClient, id: &str) -> DshApiResult<Topic> {
+    client.get_topic_actual(id).await
+  }
+
+  async fn status(client: &DshApiClient, id: &str) -> DshApiResult<AllocationStatus> {
+    client.get_topic_status(id).await
+  }
+
+  async fn delete(client: &DshApiClient, id: &str) -> DshApiResult<()> {
+    client.delete_topic_configuration(id).await
+  }
+
+  async fn dependants(client: &DshApiClient, id: &str) -> DshApiResult<Vec<Dependant<TopicInjection>>> {
+    client.topic_dependants(id).await
+  }
+}
+
+impl DshResource for Vhost {
+  const KIND: &'static str = "vhost";
+  type Configuration = Vhost;
+  type Actual = Vhost;
+  type Status = AllocationStatus;
+  type Injection = VhostInjection;
+
+  async fn list_ids(client: &DshApiClient) -> DshApiResult<Vec<String>> {
+    Ok(client.vhosts_with_dependants().await?.into_iter().map(|(vhost_id, _)| vhost_id).collect_vec())
+  }
+
+  async fn configuration(_client: &DshApiClient, _id: &str) -> DshApiResult<Vhost> {
+    Err(vhost_not_supported("configuration"))
+  }
+
+  async fn actual(_client: &DshApiClient, _id: &str) -> DshApiResult<Vhost> {
+    Err(vhost_not_supported("actual state"))
+  }
+
+  async fn status(_client: &DshApiClient, _id: &str) -> DshApiResult<AllocationStatus> {
+    Err(vhost_not_supported("status"))
+  }
+
+  async fn delete(_client: &DshApiClient, _id: &str) -> DshApiResult<()> {
+    Err(vhost_not_supported("deletion"))
+  }
+
+  async fn dependants(client: &DshApiClient, id: &str) -> DshApiResult<Vec<Dependant<VhostInjection>>> {
+    Ok(
+      client
+        .vhosts_with_dependants()
+        .await?
+        .into_iter()
+        .find(|(vhost_id, _)| vhost_id == id)
+        .map(|(_, dependants)| dependants)
+        .unwrap_or_default(),
+    )
+  }
+}
+
+impl DshResource for Volume {
+  const KIND: &'static str = "volume";
+  type Configuration = Volume;
+  type Actual = Volume;
+  type Status = AllocationStatus;
+  type Injection = VolumeInjection;
+
+  async fn list_ids(client: &DshApiClient) -> DshApiResult<Vec<String>> {
+    client.get_volume_ids().await
+  }
+
+  async fn configuration(client: &DshApiClient, id: &str) -> DshApiResult<Volume> {
+    client.get_volume_configuration(id).await
+  }
+
+  async fn actual(client: &DshApiClient, id: &str) -> DshApiResult<Volume> {
+    client.get_volume_actual(id).await
+  }
+
+  async fn status(client: &DshApiClient, id: &str) -> DshApiResult<AllocationStatus> {
+    client.get_volume_status(id).await
+  }
+
+  async fn delete(client: &DshApiClient, id: &str) -> DshApiResult<()> {
+    client.delete_volume_configuration(id).await
+  }
+
+  async fn dependants(client: &DshApiClient, id: &str) -> DshApiResult<Vec<Dependant<VolumeInjection>>> {
+    Ok(client.volume_with_dependants(id).await?.1)
+  }
+}
+
+fn vhost_not_supported(operation: &str) -> DshApiError {
+  DshApiError::Configuration(format!("vhost {} is not supported by the dsh resource management api", operation))
+}
@@ -24,6 +24,7 @@
 //! * [`secrets_with_dependant_applications() -> [id, [application]]`](DshApiClient::secrets_with_dependant_applications)
 //! * [`secrets_with_dependant_apps() -> [id, [app]]`](DshApiClient::secrets_with_dependant_apps)
 
+use crate::api::DshApi;
 use crate::app::{app_resources, apps_that_use_secret};
 use crate::application_types::{ApplicationValues, EnvVarInjection};
 use crate::dsh_api_client::DshApiClient;
@@ -75,30 +76,7 @@ impl DshApiClient {
   ///
   /// Returns a sorted list of all secrets together with the applications and apps that use them.
   pub async fn secrets_with_dependants(&self) -> DshApiResult<Vec<(String, Vec<Dependant<SecretInjection>>)>> {
-    let (secret_ids, applications, apps) = try_join!(
-      self.get_secret_ids(),
-      self.get_application_configuration_map(),
-      self.get_appcatalogapp_configuration_map()
-    )?;
-    let mut secrets = Vec::<(String, Vec<Dependant<SecretInjection>>)>::new();
-    for secret_id in secret_ids {
-      let mut dependants: Vec<Dependant<SecretInjection>> = vec![];
-      for application in secret_env_vars_from_applications(secret_id.as_str(), &applications) {
-        dependants.push(Dependant::application(
-          application.id.to_string(),
-          application.application.instances,
-          application.values.iter().map(|env_var| SecretInjection::EnvVar(env_var.to_string())).collect_vec(),
-        ));
-      }
-      for (app_id, _, resource_ids) in apps_that_use_secret(secret_id.as_str(), &apps) {
-        dependants.push(Dependant::app(
-          app_id.to_string(),
-          resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec(),
-        ));
-      }
-      secrets.push((secret_id, dependants));
-    }
-    Ok(secrets)
+    DshApi::secrets_with_dependants(self).await
   }
 
   /// # Returns all secrets with dependant applications
@@ -211,6 +189,29 @@ pub fn secret_env_vars_from_applications<'a>(secret_id: &str, applications: &'a
   application_tuples
 }
 
+// Returns the applications and apps that use a secret, from the application and app configurations
+pub(crate) fn secret_dependants_from_configurations(
+  secret_id: &str,
+  applications: &HashMap<String, Application>,
+  apps: &HashMap<String, AppCatalogApp>,
+) -> Vec<Dependant<SecretInjection>> {
+  let mut dependants: Vec<Dependant<SecretInjection>> = vec![];
+  for application in secret_env_vars_from_applications(secret_id, applications) {
+    dependants.push(Dependant::application(
+      application.id.to_string(),
+      application.application.instances,
+      application.values.iter().map(|env_var| SecretInjection::EnvVar(env_var.to_string())).collect_vec(),
+    ));
+  }
+  for (app_id, _, resource_ids) in apps_that_use_secret(secret_id, apps) {
+    dependants.push(Dependant::app(
+      app_id.to_string(),
+      resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec(),
+    ));
+  }
+  dependants
+}
+
 /// # Checks if secret is a system secret
 pub fn secret_is_system(secret_id: &str) -> bool {
   secret_id.contains('!')
@@ -246,7 +247,7 @@ pub fn secret_resources_from_app(app: &AppCatalogApp) -> Vec<(&str, &Secret)> {
 /// * lists of environment variables that reference the secret
 ///
 /// The list is sorted by secret id.
-pub fn secrets_from_application(application: &Application) -> Vec<EnvVarInjection> {
+pub fn secrets_from_application(application: &Application) -> Vec<EnvVarInjection<'_>> {
   let mut grouped_injections: Vec<(&String, Vec<&str>)> = application
     .secrets
     .iter()
@@ -284,7 +285,7 @@ pub fn secrets_from_application(application: &Application) -> Vec<EnvVarInjectio
 /// * sorted list of pairs of secret ids and lists of environment variables referencing those secrets
 ///
 /// The list is sorted by application id.
-pub fn secrets_from_applications(applications: &HashMap<String, Application>) -> Vec<ApplicationValues<EnvVarInjection>> {
+pub fn secrets_from_applications(applications: &HashMap<String, Application>) -> Vec<ApplicationValues<'_, EnvVarInjection<'_>>> {
   let mut application_tuples = applications
     .iter()
     .filter_map(|(application_id, application)| {
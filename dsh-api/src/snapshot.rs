@@ -0,0 +1,531 @@
+//! # Snapshot of all resources of a tenant
+//!
+//! Module that contains the [`TenantSnapshot`], which holds the configurations and statuses
+//! of all resources of a tenant and all apps from the app catalog.
+//! The snapshot is loaded once with [`tenant_snapshot()`](DshApiClient::tenant_snapshot),
+//! which first retrieves all listings concurrently, and then all statuses,
+//! with a bounded number of concurrent requests per resource kind.
+//! After that, all dependency analyses can be performed in memory, without additional requests.
+//! A full tenant report that uses several of the `*_with_dependants` methods
+//! will therefore fetch the application and app catalog configurations only once.
+//!
+//! The snapshot can be serialized, in order to save it and to analyze it later.
+//!
+//! The snapshot contains the applications, app catalog apps and manifests, buckets, certificates,
+//! databases, the Flink cluster, Kafka proxies, secrets, topics and volumes.
+//! The DSH resource management API has no listing for vhosts,
+//! therefore the vhosts are derived from the application and app configurations,
+//! see [`vhost_ids()`](TenantSnapshot::vhost_ids) and
+//! [`vhosts_with_dependants()`](TenantSnapshot::vhosts_with_dependants).
+//!
+//! # Derived methods
+//!
+//! [`DshApiClient`] methods that add extra capabilities but do not directly call the
+//! DSH resource management API. These derived methods depend on the API methods for this.
+//!
+//! * [`tenant_snapshot(concurrency) -> TenantSnapshot`](DshApiClient::tenant_snapshot)
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dsh_api::dsh_api_client_factory::DshApiClientFactory;
+//! use dsh_api::snapshot::TenantSnapshot;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = DshApiClientFactory::default().client().await?;
+//! let snapshot = client.tenant_snapshot(8).await?;
+//! for (secret_id, dependants) in snapshot.secrets_with_dependants() {
+//!   println!("{} -> {} dependants", secret_id, dependants.len());
+//! }
+//! std::fs::write("snapshot.json", serde_json::to_string(&snapshot)?)?;
+//! let reloaded: TenantSnapshot = serde_json::from_str(&std::fs::read_to_string("snapshot.json")?)?;
+//! assert_eq!(snapshot, reloaded);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::app::{
+  apps_that_use_application, apps_that_use_certificate, apps_that_use_resource, apps_that_use_secret, apps_that_use_topic, apps_that_use_vhost, apps_that_use_volume,
+};
+use crate::application_types::{ApplicationValues, EnvVarInjection};
+use crate::bucket::{bucket_dependants_from_configurations, bucket_injections_from_applications, bucket_resources_from_app, buckets_from_applications, BucketInjection};
+use crate::certificate::certificate_dependant_apps_from_configurations;
+use crate::database::{apps_that_use_database, database_dependants_from_configurations, DatabaseInjection};
+use crate::dsh_api_client::DshApiClient;
+use crate::kafka_proxy::kafka_proxy_dependant_applications_from_applications;
+use crate::manifest::Manifest;
+use crate::parse::TopicString;
+use crate::secret::{secret_dependants_from_configurations, secret_env_vars_from_applications, secrets_from_applications, SecretInjection};
+use crate::topic::{topic_dependants_from_configurations, topic_env_vars_from_applications, topic_injections_from_applications, topics_from_applications, TopicInjection};
+use crate::types::{
+  AllocationStatus, AppCatalogApp, Application, BucketStatus, CertificateStatus, DatabaseStatus, FlinkClusterStatus, KafkaProxy, PortMapping, TopicStatus, VolumeStatus,
+};
+use crate::vhost::{vhost_port_mappings_from_applications, vhosts_from_applications, vhosts_with_dependants_from_configurations, VhostInjection};
+use crate::volume::{volume_dependants_from_configurations, volume_paths_from_applications, volumes_from_applications, VolumeInjection};
+use crate::{Dependant, DependantApp, DependantApplication, DshApiError, DshApiResult};
+use futures::future;
+use futures::{try_join, TryStreamExt};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// # Snapshot of all resources of a tenant
+///
+/// Holds the configurations and statuses of all resources of a tenant,
+/// and the configurations of all apps from the app catalog, all keyed by their id.
+/// See the [module documentation](crate::snapshot) for the resource kinds that are included.
+/// All analysis methods are pure and do not call the DSH resource management API.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct TenantSnapshot {
+  /// Name of the tenant
+  pub tenant: String,
+  /// Time when the snapshot was taken, in milliseconds since the epoch
+  pub timestamp: i64,
+  /// Application configurations
+  pub applications: HashMap<String, Application>,
+  /// Application allocation statuses
+  pub application_statuses: HashMap<String, AllocationStatus>,
+  /// App catalog app configurations
+  pub apps: HashMap<String, AppCatalogApp>,
+  /// App catalog manifests, all versions
+  pub manifests: Vec<Manifest>,
+  /// Bucket statuses, including configuration and actual state
+  pub buckets: HashMap<String, BucketStatus>,
+  /// Platform specific bucket names, when they could be determined
+  pub bucket_names: HashMap<String, String>,
+  /// Certificate statuses, including configuration and actual state
+  pub certificates: HashMap<String, CertificateStatus>,
+  /// Database statuses, including configuration and actual state
+  pub databases: HashMap<String, DatabaseStatus>,
+  /// Platform specific database host names
+  pub database_hosts: HashMap<String, String>,
+  /// Flink cluster status, including configuration and actual state, when the tenant has a Flink cluster
+  pub flink_cluster: Option<FlinkClusterStatus>,
+  /// Kafka proxy configurations
+  pub kafka_proxies: HashMap<String, KafkaProxy>,
+  /// Secret allocation statuses
+  pub secrets: HashMap<String, AllocationStatus>,
+  /// Topic statuses, including configuration and actual state
+  pub topics: HashMap<String, TopicStatus>,
+  /// Volume statuses, including configuration and actual state
+  pub volumes: HashMap<String, VolumeStatus>,
+}
+
+impl DshApiClient {
+  /// # Returns a snapshot of all resources of the tenant
+  ///
+  /// Loads all resource listings, configurations, statuses and app catalog apps.
+  /// First all listings are retrieved concurrently, after which the statuses of all
+  /// resource kinds are retrieved concurrently, one request per id.
+  ///
+  /// # Parameters
+  /// * `concurrency` - maximum number of concurrent status requests per resource kind,
+  ///   at least one request is executed
+  ///
+  /// # Returns
+  /// * `Ok<`[`TenantSnapshot`]`>` - the snapshot
+  /// * `Err<`[`DshApiError`](crate::DshApiError)`>` - when one of the requests could not be processed by the DSH
+  pub async fn tenant_snapshot(&self, concurrency: usize) -> DshApiResult<TenantSnapshot> {
+    let (applications, apps, manifests, bucket_ids, certificate_ids, database_ids, flink_cluster, kafka_proxy_ids, secret_ids, topic_ids, volume_ids, access_key_id) = try_join!(
+      self.get_application_configuration_map(),
+      self.get_appcatalogapp_configuration_map(),
+      self.manifests(),
+      self.get_bucket_ids(),
+      self.get_certificate_ids(),
+      self.get_database_ids(),
+      self.flink_cluster_status_if_present(),
+      self.get_kafkaproxy_ids(),
+      self.get_secret_ids(),
+      self.get_topic_ids(),
+      self.get_volume_ids(),
+      self.object_store_access_key_id_if_required()
+    )?;
+    let application_ids = applications.keys().cloned().collect_vec();
+    let (application_statuses, buckets, certificates, databases, kafka_proxies, secrets, topics, volumes) = try_join!(
+      self.statuses(application_ids, |application_id| self.get_application_status(application_id), concurrency),
+      self.statuses(bucket_ids, |bucket_id| self.get_bucket(bucket_id), concurrency),
+      self.statuses(certificate_ids, |certificate_id| self.get_certificate(certificate_id), concurrency),
+      self.statuses(database_ids, |database_id| self.get_database(database_id), concurrency),
+      self.statuses(kafka_proxy_ids, |kafka_proxy_id| self.get_kafkaproxy_configuration(kafka_proxy_id), concurrency),
+      self.statuses(secret_ids, |secret_id| self.get_secret_status(secret_id), concurrency),
+      self.statuses(topic_ids, |topic_id| self.get_topic(topic_id), concurrency),
+      self.statuses(volume_ids, |volume_id| self.get_volume(volume_id), concurrency)
+    )?;
+    let bucket_names = buckets
+      .keys()
+      .filter_map(|bucket_id| {
+        self
+          .platform()
+          .bucket_name(self.tenant_name(), bucket_id, access_key_id.as_deref())
+          .ok()
+          .map(|bucket_name| (bucket_id.to_string(), bucket_name))
+      })
+      .collect::<HashMap<_, _>>();
+    let database_hosts = databases
+      .keys()
+      .map(|database_id| (database_id.to_string(), self.platform().internal_service_domain(self.tenant_name(), database_id)))
+      .collect::<HashMap<_, _>>();
+    Ok(TenantSnapshot {
+      tenant: self.tenant_name().to_string(),
+      timestamp: SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or_default(),
+      applications,
+      application_statuses,
+      apps,
+      manifests,
+      buckets,
+      bucket_names,
+      certificates,
+      databases,
+      database_hosts,
+      flink_cluster,
+      kafka_proxies,
+      secrets,
+      topics,
+      volumes,
+    })
+  }
+}
+
+impl TenantSnapshot {
+  /// # Returns the sorted application ids
+  pub fn application_ids(&self) -> Vec<&str> {
+    sorted_keys(&self.applications)
+  }
+
+  /// # Returns the sorted app ids
+  pub fn app_ids(&self) -> Vec<&str> {
+    sorted_keys(&self.apps)
+  }
+
+  /// # Returns the sorted bucket ids
+  pub fn bucket_ids(&self) -> Vec<&str> {
+    sorted_keys(&self.buckets)
+  }
+
+  /// # Returns the sorted certificate ids
+  pub fn certificate_ids(&self) -> Vec<&str> {
+    sorted_keys(&self.certificates)
+  }
+
+  /// # Returns the sorted database ids
+  pub fn database_ids(&self) -> Vec<&str> {
+    sorted_keys(&self.databases)
+  }
+
+  /// # Returns the sorted Kafka proxy ids
+  pub fn kafka_proxy_ids(&self) -> Vec<&str> {
+    sorted_keys(&self.kafka_proxies)
+  }
+
+  /// # Returns the sorted secret ids
+  pub fn secret_ids(&self) -> Vec<&str> {
+    sorted_keys(&self.secrets)
+  }
+
+  /// # Returns the sorted topic ids
+  pub fn topic_ids(&self) -> Vec<&str> {
+    sorted_keys(&self.topics)
+  }
+
+  /// # Returns the sorted vhost ids
+  ///
+  /// Only vhosts that are referenced in the applications and apps are included.
+  pub fn vhost_ids(&self) -> Vec<String> {
+    self.vhosts_with_dependants().into_iter().map(|(vhost_id, _)| vhost_id).collect_vec()
+  }
+
+  /// # Returns the sorted volume ids
+  pub fn volume_ids(&self) -> Vec<&str> {
+    sorted_keys(&self.volumes)
+  }
+
+  /// # Returns the apps that use an application
+  ///
+  /// See [`apps_that_use_application`](crate::app::apps_that_use_application).
+  pub fn apps_that_use_application(&self, application_id: impl AsRef<str>) -> Vec<(&str, &AppCatalogApp, Vec<&str>)> {
+    apps_that_use_application(application_id.as_ref(), &self.apps)
+  }
+
+  /// # Returns the apps that use a bucket
+  pub fn apps_that_use_bucket(&self, bucket_id: impl AsRef<str>) -> Vec<(&str, &AppCatalogApp, Vec<&str>)> {
+    apps_that_use_resource(bucket_id.as_ref(), &self.apps, &bucket_resources_from_app)
+  }
+
+  /// # Returns the apps that use a certificate
+  ///
+  /// See [`apps_that_use_certificate`](crate::app::apps_that_use_certificate).
+  pub fn apps_that_use_certificate(&self, certificate_id: impl AsRef<str>) -> Vec<(&str, &AppCatalogApp, Vec<&str>)> {
+    apps_that_use_certificate(certificate_id.as_ref(), &self.apps)
+  }
+
+  /// # Returns the apps that use a database
+  ///
+  /// See [`apps_that_use_database`](crate::database::apps_that_use_database).
+  pub fn apps_that_use_database(&self, database_id: impl AsRef<str>) -> Vec<(&str, &AppCatalogApp, Vec<String>)> {
+    apps_that_use_database(database_id.as_ref(), &self.apps, &self.manifests)
+  }
+
+  /// # Returns the apps that use a secret
+  ///
+  /// See [`apps_that_use_secret`](crate::app::apps_that_use_secret).
+  pub fn apps_that_use_secret(&self, secret_id: impl AsRef<str>) -> Vec<(&str, &AppCatalogApp, Vec<&str>)> {
+    apps_that_use_secret(secret_id.as_ref(), &self.apps)
+  }
+
+  /// # Returns the apps that use a topic
+  ///
+  /// See [`apps_that_use_topic`](crate::app::apps_that_use_topic).
+  pub fn apps_that_use_topic(&self, topic_id: impl AsRef<str>) -> Vec<(&str, &AppCatalogApp, Vec<&str>)> {
+    apps_that_use_topic(topic_id.as_ref(), &self.apps)
+  }
+
+  /// # Returns the apps that use a vhost
+  ///
+  /// See [`apps_that_use_vhost`](crate::app::apps_that_use_vhost).
+  pub fn apps_that_use_vhost(&self, vhost_id: impl AsRef<str>) -> Vec<(&str, &AppCatalogApp, Vec<&str>)> {
+    apps_that_use_vhost(vhost_id.as_ref(), &self.apps)
+  }
+
+  /// # Returns the apps that use a volume
+  ///
+  /// See [`apps_that_use_volume`](crate::app::apps_that_use_volume).
+  pub fn apps_that_use_volume(&self, volume_id: impl AsRef<str>) -> Vec<(&str, &AppCatalogApp, Vec<&str>)> {
+    apps_that_use_volume(volume_id.as_ref(), &self.apps)
+  }
+
+  /// # Returns the injections of a bucket in the applications
+  ///
+  /// See [`bucket_injections_from_applications`](crate::bucket::bucket_injections_from_applications).
+  pub fn bucket_injections_from_applications(&self, bucket_id: impl AsRef<str>) -> Vec<ApplicationValues<'_, BucketInjection>> {
+    let bucket_id = bucket_id.as_ref();
+    bucket_injections_from_applications(bucket_id, self.bucket_names.get(bucket_id).map(String::as_str), &self.applications)
+  }
+
+  /// # Returns the bucket injections in the applications
+  ///
+  /// See [`buckets_from_applications`](crate::bucket::buckets_from_applications).
+  pub fn buckets_from_applications(&self) -> Vec<ApplicationValues<'_, EnvVarInjection<'_>>> {
+    buckets_from_applications(&self.applications)
+  }
+
+  /// # Returns the environment variables that reference a secret in the applications
+  ///
+  /// See [`secret_env_vars_from_applications`](crate::secret::secret_env_vars_from_applications).
+  pub fn secret_env_vars_from_applications(&self, secret_id: impl AsRef<str>) -> Vec<ApplicationValues<'_, &str>> {
+    secret_env_vars_from_applications(secret_id.as_ref(), &self.applications)
+  }
+
+  /// # Returns the secret injections in the applications
+  ///
+  /// See [`secrets_from_applications`](crate::secret::secrets_from_applications).
+  pub fn secrets_from_applications(&self) -> Vec<ApplicationValues<'_, EnvVarInjection<'_>>> {
+    secrets_from_applications(&self.applications)
+  }
+
+  /// # Returns the environment variables that reference a topic in the applications
+  ///
+  /// See [`topic_env_vars_from_applications`](crate::topic::topic_env_vars_from_applications).
+  pub fn topic_env_vars_from_applications(&self, topic_id: impl AsRef<str>) -> Vec<ApplicationValues<'_, (&str, TopicString<'_>)>> {
+    topic_env_vars_from_applications(topic_id.as_ref(), &self.applications)
+  }
+
+  /// # Returns the injections of a topic in the applications
+  ///
+  /// See [`topic_injections_from_applications`](crate::topic::topic_injections_from_applications).
+  pub fn topic_injections_from_applications(&self, topic_id: impl AsRef<str>) -> Vec<ApplicationValues<'_, TopicInjection>> {
+    topic_injections_from_applications(topic_id.as_ref(), &self.applications)
+  }
+
+  /// # Returns the topics that are used in the applications
+  ///
+  /// See [`topics_from_applications`](crate::topic::topics_from_applications).
+  pub fn topics_from_applications(&self) -> Vec<ApplicationValues<'_, &str>> {
+    topics_from_applications(&self.applications)
+  }
+
+  /// # Returns the port mappings that use a vhost in the applications
+  ///
+  /// See [`vhost_port_mappings_from_applications`](crate::vhost::vhost_port_mappings_from_applications).
+  pub fn vhost_port_mappings_from_applications(&self, vhost_id: impl AsRef<str>) -> Vec<ApplicationValues<'_, (&str, &PortMapping)>> {
+    vhost_port_mappings_from_applications(vhost_id.as_ref(), &self.applications)
+  }
+
+  /// # Returns the vhosts that are used in the applications
+  ///
+  /// See [`vhosts_from_applications`](crate::vhost::vhosts_from_applications).
+  pub fn vhosts_from_applications(&self) -> Vec<ApplicationValues<'_, (String, &str, &PortMapping)>> {
+    vhosts_from_applications(&self.applications)
+  }
+
+  /// # Returns the mount paths of a volume in the applications
+  ///
+  /// See [`volume_paths_from_applications`](crate::volume::volume_paths_from_applications).
+  pub fn volume_paths_from_applications(&self, volume_id: impl AsRef<str>) -> Vec<ApplicationValues<'_, &str>> {
+    volume_paths_from_applications(volume_id.as_ref(), &self.applications)
+  }
+
+  /// # Returns the volumes that are used in the applications
+  ///
+  /// See [`volumes_from_applications`](crate::volume::volumes_from_applications).
+  pub fn volumes_from_applications(&self) -> Vec<ApplicationValues<'_, (&str, &str)>> {
+    volumes_from_applications(&self.applications)
+  }
+
+  /// # Returns all buckets with dependant applications and apps
+  ///
+  /// Returns a list of all buckets, sorted by id, together with the applications and apps that use them.
+  pub fn buckets_with_dependants(&self) -> Vec<(&str, &BucketStatus, Vec<Dependant<BucketInjection>>)> {
+    self
+      .bucket_ids()
+      .into_iter()
+      .map(|bucket_id| {
+        let bucket_name = self.bucket_names.get(bucket_id).map(String::as_str);
+        (
+          bucket_id,
+          &self.buckets[bucket_id],
+          bucket_dependants_from_configurations(bucket_id, bucket_name, &self.applications, &self.apps),
+        )
+      })
+      .collect_vec()
+  }
+
+  /// # Returns all certificates with dependant apps
+  ///
+  /// Returns a list of all certificates, sorted by id, together with the apps that use them.
+  pub fn certificates_with_dependant_apps(&self) -> Vec<(&str, &CertificateStatus, Vec<DependantApp>)> {
+    self
+      .certificate_ids()
+      .into_iter()
+      .map(|certificate_id| {
+        let certificate_status = &self.certificates[certificate_id];
+        (
+          certificate_id,
+          certificate_status,
+          certificate_dependant_apps_from_configurations(certificate_status, &self.apps),
+        )
+      })
+      .collect_vec()
+  }
+
+  /// # Returns all databases with dependant applications and apps
+  ///
+  /// Returns a list of all databases, sorted by id, together with the applications and apps that use them.
+  pub fn databases_with_dependants(&self) -> Vec<(&str, &DatabaseStatus, Vec<Dependant<DatabaseInjection>>)> {
+    self
+      .database_ids()
+      .into_iter()
+      .map(|database_id| {
+        let database_host = self.database_hosts.get(database_id).map(String::as_str).unwrap_or_default();
+        (
+          database_id,
+          &self.databases[database_id],
+          database_dependants_from_configurations(database_id, database_host, &self.applications, &self.apps, &self.manifests),
+        )
+      })
+      .collect_vec()
+  }
+
+  /// # Returns all Kafka proxies with dependant applications
+  ///
+  /// Returns a list of all Kafka proxies, sorted by id, together with the applications that use them.
+  pub fn kafka_proxies_with_dependant_applications(&self) -> Vec<(&str, &KafkaProxy, Vec<DependantApplication<VhostInjection>>)> {
+    self
+      .kafka_proxy_ids()
+      .into_iter()
+      .map(|kafka_proxy_id| {
+        (
+          kafka_proxy_id,
+          &self.kafka_proxies[kafka_proxy_id],
+          kafka_proxy_dependant_applications_from_applications(kafka_proxy_id, &self.tenant, &self.applications),
+        )
+      })
+      .collect_vec()
+  }
+
+  /// # Returns all secrets with dependant applications and apps
+  ///
+  /// Returns a list of all secrets, sorted by id, together with the applications and apps that use them.
+  pub fn secrets_with_dependants(&self) -> Vec<(&str, Vec<Dependant<SecretInjection>>)> {
+    self
+      .secret_ids()
+      .into_iter()
+      .map(|secret_id| (secret_id, secret_dependants_from_configurations(secret_id, &self.applications, &self.apps)))
+      .collect_vec()
+  }
+
+  /// # Returns all topics with dependant applications and apps
+  ///
+  /// Returns a list of all topics, sorted by id, together with the applications and apps that use them.
+  pub fn topics_with_dependants(&self) -> Vec<(&str, Vec<Dependant<TopicInjection>>)> {
+    self
+      .topic_ids()
+      .into_iter()
+      .map(|topic_id| (topic_id, topic_dependants_from_configurations(topic_id, &self.applications, &self.apps)))
+      .collect_vec()
+  }
+
+  /// # Returns all vhosts with dependant applications and apps
+  ///
+  /// Returns a list of all vhosts, sorted by id, together with the applications and apps that use them.
+  /// Note that only vhosts that are actually referenced in the applications and apps will be included.
+  pub fn vhosts_with_dependants(&self) -> Vec<(String, Vec<Dependant<VhostInjection>>)> {
+    vhosts_with_dependants_from_configurations(&self.applications, &self.apps)
+  }
+
+  /// # Returns all volumes with dependant applications and apps
+  ///
+  /// Returns a list of all volumes, sorted by id, together with the applications and apps that use them.
+  pub fn volumes_with_dependants(&self) -> Vec<(&str, &VolumeStatus, Vec<Dependant<VolumeInjection>>)> {
+    self
+      .volume_ids()
+      .into_iter()
+      .map(|volume_id| {
+        (
+          volume_id,
+          &self.volumes[volume_id],
+          volume_dependants_from_configurations(volume_id, &self.applications, &self.apps),
+        )
+      })
+      .collect_vec()
+  }
+}
+
+impl DshApiClient {
+  // Retrieves the Flink cluster status, or `None` when the tenant has no Flink cluster
+  async fn flink_cluster_status_if_present(&self) -> DshApiResult<Option<FlinkClusterStatus>> {
+    match self.get_flinkcluster().await {
+      Ok(flink_cluster_status) => Ok(Some(flink_cluster_status)),
+      Err(DshApiError::NotFound(_)) => Ok(None),
+      Err(error) => Err(error),
+    }
+  }
+
+  // Retrieves the statuses for all ids, with at most `concurrency` requests in flight
+  async fn statuses<T, F, R>(&self, ids: Vec<String>, status: F, concurrency: usize) -> DshApiResult<HashMap<String, T>>
+  where
+    F: Fn(String) -> R + Clone,
+    R: Future<Output = DshApiResult<T>>,
+  {
+    self
+      .stream_per_id(
+        future::ready(Ok(ids)),
+        move |id| {
+          let status = status.clone();
+          async move { Ok((id.clone(), status(id).await?)) }
+        },
+        concurrency,
+      )
+      .try_collect()
+      .await
+  }
+}
+
+fn sorted_keys<T>(map: &HashMap<String, T>) -> Vec<&str> {
+  map.keys().map(String::as_str).sorted().collect_vec()
+}
@@ -1,6 +1,7 @@
 //! # Additional methods to manage streams
 //!
-//! _These functions are only available when the `manage` feature is enabled._
+//! _These functions are only available when the `manage` feature is enabled
+//! and are not available when the crate is generated from openapi specification version 1.8.0._
 //!
 //! Module that contains methods and functions to manage streams.
 //!
@@ -111,7 +112,8 @@ impl DshApiClient {
   /// * `Ok(None)` - when the managed tenant does not have access to the managed stream,
   ///   or when the managed stream or the managed tenant does not exist
   /// * `Err<DshApiError>` - when the request could not be processed by the DSH
-  pub async fn managed_stream_access_rights(&self, stream_id: &ManagedStreamId, tenant_id: &str) -> DshApiResult<Option<AccessRights>> {
+  pub async fn managed_stream_access_rights(&self, stream_id: &ManagedStreamId, tenant_id: impl AsRef<str>) -> DshApiResult<Option<AccessRights>> {
+    let tenant_id = tenant_id.as_ref();
     let (internal_read_access, internal_write_access, public_read_access, public_write_access) = try_join!(
       self.managed_tenant_has_internal_read_access(tenant_id, stream_id),
       self.managed_tenant_has_internal_write_access(tenant_id, stream_id),
@@ -250,7 +252,13 @@ impl DshApiClient {
   ///   will be returned
   /// * `Err<DshApiError>` - when the managed stream does not exist or the request
   ///   could not be processed by the DSH
-  pub async fn managed_stream_grant_access_rights(&self, managed_stream_id: &ManagedStreamId, managed_tenant_id: &str, access_rights: &AccessRights) -> DshApiResult<Stream> {
+  pub async fn managed_stream_grant_access_rights(
+    &self,
+    managed_stream_id: &ManagedStreamId,
+    managed_tenant_id: impl AsRef<str>,
+    access_rights: &AccessRights,
+  ) -> DshApiResult<Stream> {
+    let managed_tenant_id = managed_tenant_id.as_ref();
     match self.managed_stream_configuration(managed_stream_id).await? {
       Some(Stream::Internal(internal)) => {
         match access_rights {
@@ -294,7 +302,13 @@ impl DshApiClient {
   ///   will be returned
   /// * `Err<DshApiError>` - when the managed stream does not exist or the request
   ///   could not be processed by the DSH
-  pub async fn managed_stream_revoke_access_rights(&self, managed_stream_id: &ManagedStreamId, managed_tenant_id: &str, access_rights: &AccessRights) -> DshApiResult<Stream> {
+  pub async fn managed_stream_revoke_access_rights(
+    &self,
+    managed_stream_id: &ManagedStreamId,
+    managed_tenant_id: impl AsRef<str>,
+    access_rights: &AccessRights,
+  ) -> DshApiResult<Stream> {
+    let managed_tenant_id = managed_tenant_id.as_ref();
     match self.managed_stream_configuration(managed_stream_id).await? {
       Some(Stream::Internal(internal)) => {
         match access_rights {
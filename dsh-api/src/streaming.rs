@@ -0,0 +1,194 @@
+//! # Asynchronous streams for large listings
+//!
+//! Module that contains [`Stream`] variants of methods that list resources.
+//! Where the methods that return a [`Vec`] only return after all requests have completed,
+//! these variants yield the items as soon as they are available.
+//! This allows a caller to show progress or to start processing the first items early,
+//! which makes a difference for tenants with many resources or large app catalogs.
+//!
+//! When an item requires a separate request per resource id, the requests are executed
+//! concurrently, with the number of requests in flight bounded by the `concurrency` parameter.
+//! In that case the items are yielded in the order in which the requests complete,
+//! not in the order of the ids.
+//! The application configurations and the manifests can only be retrieved with a single request,
+//! so [`applications_stream`](DshApiClient::applications_stream) and
+//! [`manifests_all_versions_stream`](DshApiClient::manifests_all_versions_stream)
+//! yield their items as soon as that request has completed.
+//!
+//! # Derived methods
+//!
+//! [`DshApiClient`] methods that add extra capabilities but do not directly call the
+//! DSH resource management API. These derived methods depend on the API methods for this.
+//!
+//! * [`applications_stream() -> stream of (id, application)`](DshApiClient::applications_stream)
+//! * [`configurations_stream::<R>(concurrency) -> stream of (id, configuration)`](DshApiClient::configurations_stream)
+//! * [`manifests_all_versions_stream() -> stream of (id, [manifest])`](DshApiClient::manifests_all_versions_stream)
+//! * [`stream_per_id(ids, f, concurrency) -> stream of T`](DshApiClient::stream_per_id)
+//! * [`topics_with_dependants_stream(concurrency) -> stream of (topic id, topic, [dependant])`](DshApiClient::topics_with_dependants_stream)
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dsh_api::dsh_api_client_factory::DshApiClientFactory;
+//! use dsh_api::types::Topic;
+//! use futures::{pin_mut, TryStreamExt};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = DshApiClientFactory::default().client().await?;
+//! let topics = client.configurations_stream::<Topic>(8);
+//! pin_mut!(topics);
+//! while let Some((topic_id, topic)) = topics.try_next().await? {
+//!   println!("{} -> {} partitions", topic_id, topic.partitions);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::dsh_api_client::DshApiClient;
+use crate::manifest::Manifest;
+use crate::resource::DshResource;
+use crate::topic::{topic_dependants_from_configurations, TopicInjection};
+use crate::types::{Application, Topic};
+use crate::{Dependant, DshApiResult};
+use futures::future;
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use futures::{try_join, Future};
+use itertools::Itertools;
+use std::sync::Arc;
+
+impl DshApiClient {
+  /// # Stream all application configurations with their ids
+  ///
+  /// The application configurations are retrieved in a single request,
+  /// after which the applications are yielded sorted by id.
+  ///
+  /// # Returns
+  /// Stream that yields
+  /// * `Ok<(String, `[`Application`]`)>` - application id and configuration
+  /// * `Err<`[`DshApiError`](crate::DshApiError)`>` - when the request could not be processed by the DSH,
+  ///   after which the stream ends
+  pub fn applications_stream(&self) -> impl Stream<Item = DshApiResult<(String, Application)>> + '_ {
+    stream::once(self.get_application_configuration_map())
+      .map_ok(|applications| stream::iter(applications.into_iter().sorted_by(|(id_a, _), (id_b, _)| id_a.cmp(id_b)).map(Ok)))
+      .try_flatten()
+  }
+
+  /// # Stream the configurations of all resources of a kind
+  ///
+  /// First the ids of the resources of kind `R` are retrieved,
+  /// after which the configurations are retrieved concurrently, one request per id.
+  ///
+  /// # Parameters
+  /// * `concurrency` - maximum number of concurrent requests, at least one request is executed
+  ///
+  /// # Returns
+  /// Stream that yields, in the order in which the requests complete
+  /// * `Ok<(String, R::Configuration)>` - resource id and configuration
+  /// * `Err<`[`DshApiError`](crate::DshApiError)`>` - when a request could not be processed by the DSH
+  pub fn configurations_stream<'a, R>(&'a self, concurrency: usize) -> impl Stream<Item = DshApiResult<(String, R::Configuration)>> + 'a
+  where
+    R: DshResource + 'a,
+  {
+    self.stream_per_id(
+      R::list_ids(self),
+      move |id| async move {
+        let configuration = R::configuration(self, id.as_str()).await?;
+        Ok((id, configuration))
+      },
+      concurrency,
+    )
+  }
+
+  /// # Stream all manifests with all available versions
+  ///
+  /// The manifests are retrieved in a single request,
+  /// after which the manifests are yielded sorted by id and version.
+  ///
+  /// # Returns
+  /// Stream that yields
+  /// * `Ok<(String, Vec<`[`Manifest`]`>)>` - manifest id and all versions of the manifest
+  /// * `Err<`[`DshApiError`](crate::DshApiError)`>` - when the request could not be processed by the DSH,
+  ///   after which the stream ends
+  pub fn manifests_all_versions_stream(&self) -> impl Stream<Item = DshApiResult<(String, Vec<Manifest>)>> + '_ {
+    stream::once(self.manifests())
+      .map_ok(|manifests| {
+        stream::iter(
+          manifests
+            .into_iter()
+            .map(|manifest| (manifest.id.clone(), manifest))
+            .into_group_map()
+            .into_iter()
+            .sorted_by(|(id_a, _), (id_b, _)| id_a.cmp(id_b))
+            .map(|(manifest_id, manifests)| Ok((manifest_id, manifests.into_iter().sorted_by(|a, b| a.version.cmp(&b.version)).collect_vec()))),
+        )
+      })
+      .try_flatten()
+  }
+
+  /// # Stream the results of a request per id
+  ///
+  /// Generic method that first awaits the future `ids`, and then calls `f` for each id.
+  /// The futures returned by `f` are executed concurrently.
+  ///
+  /// # Parameters
+  /// * `ids` - future that yields the ids
+  /// * `f` - function that returns the future that yields the item for an id
+  /// * `concurrency` - maximum number of concurrently executed futures,
+  ///   at least one future is executed
+  ///
+  /// # Returns
+  /// Stream that yields, in the order in which the futures complete
+  /// * `Ok<T>` - item for an id
+  /// * `Err<`[`DshApiError`](crate::DshApiError)`>` - when `ids` or a future returned by `f` failed
+  pub fn stream_per_id<'a, T, I, F, R>(&'a self, ids: I, f: F, concurrency: usize) -> impl Stream<Item = DshApiResult<T>> + 'a
+  where
+    T: 'a,
+    I: Future<Output = DshApiResult<Vec<String>>> + 'a,
+    F: Fn(String) -> R + Clone + 'a,
+    R: Future<Output = DshApiResult<T>> + 'a,
+  {
+    stream::once(ids)
+      .map_ok(move |ids| stream::iter(ids).map(f.clone()).buffer_unordered(concurrency.max(1)))
+      .try_flatten()
+  }
+
+  /// # Stream all topics with their configurations and dependant applications and apps
+  ///
+  /// First the topic ids, application configurations and app configurations are retrieved concurrently,
+  /// after which the topic configurations are retrieved concurrently, one request per topic id.
+  /// Each topic is yielded together with its dependants as soon as its configuration is available.
+  ///
+  /// # Parameters
+  /// * `concurrency` - maximum number of concurrent requests, at least one request is executed
+  ///
+  /// # Returns
+  /// Stream that yields, in the order in which the requests complete
+  /// * `Ok<(String, `[`Topic`]`, Vec<`[`Dependant`]`<`[`TopicInjection`]`>>)>` - topic id, configuration and dependants
+  /// * `Err<`[`DshApiError`](crate::DshApiError)`>` - when a request could not be processed by the DSH
+  pub fn topics_with_dependants_stream(&self, concurrency: usize) -> impl Stream<Item = DshApiResult<(String, Topic, Vec<Dependant<TopicInjection>>)>> + '_ {
+    stream::once(async {
+      try_join!(
+        self.get_topic_ids(),
+        self.get_application_configuration_map(),
+        self.get_appcatalogapp_configuration_map()
+      )
+    })
+    .map_ok(move |(topic_ids, applications, apps)| {
+      let (applications, apps) = (Arc::new(applications), Arc::new(apps));
+      self.stream_per_id(
+        future::ready(Ok(topic_ids)),
+        move |topic_id| {
+          let (applications, apps) = (applications.clone(), apps.clone());
+          async move {
+            let topic = self.get_topic_configuration(topic_id.as_str()).await?;
+            let dependants = topic_dependants_from_configurations(topic_id.as_str(), &applications, &apps);
+            Ok((topic_id, topic, dependants))
+          }
+        },
+        concurrency,
+      )
+    })
+    .try_flatten()
+  }
+}
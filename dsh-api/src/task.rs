@@ -0,0 +1,302 @@
+//! # Additional methods to inspect tasks
+//!
+//! Module that contains methods and functions to inspect the tasks of applications.
+//!
+//! # Generated methods
+//!
+//! [`DshApiClient`] methods that are generated from the `openapi` specification.
+//!
+//! * [`get_task(appid, id) -> TaskStatus`](DshApiClient::get_task)
+//! * [`get_task_actual(appid, id) -> Task`](DshApiClient::get_task_actual)
+//! * [`get_task_appid_ids(appid) -> [id]`](DshApiClient::get_task_appid_ids)
+//! * [`get_task_ids() -> [id]`](DshApiClient::get_task_ids)
+//! * [`get_task_status(appid, id) -> AllocationStatus`](DshApiClient::get_task_status)
+//!
+//! # Derived methods
+//!
+//! [`DshApiClient`] methods that add extra capabilities but do not directly call the
+//! DSH resource management API. These derived methods depend on the API methods for this.
+//!
+//! * [`application_health_summaries(concurrency) -> [ApplicationHealth]`](DshApiClient::application_health_summaries)
+//! * [`task_timeline(concurrency) -> [TaskEvent]`](DshApiClient::task_timeline)
+//! * [`tasks(concurrency) -> [(application id, task id, task)]`](DshApiClient::tasks)
+//!
+//! # Functions
+//!
+//! * [`application_health_from_tasks(application id, application, tasks, crash loop threshold, crash loop since) -> ApplicationHealth`](application_health_from_tasks)
+//! * [`task_timeline_from_tasks(tasks) -> [TaskEvent]`](task_timeline_from_tasks)
+
+use crate::dsh_api_client::DshApiClient;
+use crate::types::{Application, Task, TaskState};
+#[allow(unused_imports)]
+use crate::DshApiError;
+use crate::{now, DshApiResult};
+use chrono::{DateTime, Duration, Utc};
+use futures::{stream, try_join, StreamExt, TryStreamExt};
+use itertools::Itertools;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+
+/// Number of recently failed tasks from which an application is considered to be crash looping
+pub const CRASH_LOOP_THRESHOLD: usize = 3;
+
+/// Number of minutes in which failed tasks are considered to be recently failed
+pub const CRASH_LOOP_WINDOW_MINUTES: i64 = 15;
+
+/// # Describes the kind of a task event
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum TaskEventKind {
+  /// Task was staged
+  Staged,
+  /// Task was started
+  Started,
+  /// Task was stopped
+  Stopped,
+}
+
+impl Display for TaskEventKind {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      TaskEventKind::Staged => write!(f, "staged"),
+      TaskEventKind::Started => write!(f, "started"),
+      TaskEventKind::Stopped => write!(f, "stopped"),
+    }
+  }
+}
+
+/// # Describes one event in the timeline of the tasks
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct TaskEvent {
+  /// Moment that the event occurred
+  pub timestamp: DateTime<Utc>,
+  /// Identifies the application that the task belongs to
+  pub application_id: String,
+  /// Identifies the task
+  pub task_id: String,
+  /// Kind of the event
+  pub kind: TaskEventKind,
+}
+
+impl Display for TaskEvent {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} {}/{} {}", self.timestamp, self.application_id, self.task_id, self.kind)
+  }
+}
+
+/// # Summarizes the health of an application
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ApplicationHealth {
+  /// Identifies the application
+  pub application_id: String,
+  /// Number of configured instances of the application
+  pub instances: u64,
+  /// Number of running tasks
+  pub running_tasks: usize,
+  /// Number of running tasks that did not fail their health checks
+  pub healthy_tasks: usize,
+  /// Number of tasks that stopped
+  pub stopped_tasks: usize,
+  /// Whether the tasks of the application recently failed repeatedly
+  pub crash_looping: bool,
+}
+
+impl ApplicationHealth {
+  /// # Check if the application is healthy
+  ///
+  /// # Returns
+  /// * `true` - the application has at least `instances` healthy tasks and is not crash looping
+  /// * `false` - otherwise
+  pub fn is_healthy(&self) -> bool {
+    !self.crash_looping && self.healthy_tasks as u64 >= self.instances
+  }
+}
+
+impl Display for ApplicationHealth {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{}: {}/{} healthy, {} running, {} stopped",
+      self.application_id, self.healthy_tasks, self.instances, self.running_tasks, self.stopped_tasks
+    )?;
+    if self.crash_looping {
+      write!(f, ", crash looping")?;
+    }
+    Ok(())
+  }
+}
+
+/// # Additional methods to inspect tasks
+///
+/// Module that contains methods to inspect the tasks of applications.
+/// * Derived methods - DshApiClient methods that add extra capabilities
+///   but depend on the API methods.
+///
+/// # Derived methods
+///
+/// [`DshApiClient`] methods that add extra capabilities but do not directly call the
+/// DSH resource management API. These derived methods depend on the API methods for this.
+///
+/// * [`application_health_summaries(concurrency) -> [ApplicationHealth]`](DshApiClient::application_health_summaries)
+/// * [`task_timeline(concurrency) -> [TaskEvent]`](DshApiClient::task_timeline)
+/// * [`tasks(concurrency) -> [(application id, task id, task)]`](DshApiClient::tasks)
+impl DshApiClient {
+  /// # Returns the health summaries of all applications
+  ///
+  /// An application is considered to be crash looping when at least
+  /// [`CRASH_LOOP_THRESHOLD`] of its tasks have failed
+  /// in the last [`CRASH_LOOP_WINDOW_MINUTES`] minutes.
+  ///
+  /// # Parameters
+  /// * `concurrency` - maximum number of concurrent task requests, at least one request is executed
+  ///
+  /// # Returns
+  /// * `Ok<Vec<`[`ApplicationHealth`]`>>` - health summaries of all applications, sorted by application id
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn application_health_summaries(&self, concurrency: usize) -> DshApiResult<Vec<ApplicationHealth>> {
+    let (applications, tasks) = try_join!(self.get_application_configuration_map(), self.tasks(concurrency))?;
+    let crash_loop_since = now() - Duration::minutes(CRASH_LOOP_WINDOW_MINUTES);
+    Ok(
+      applications
+        .iter()
+        .sorted_by_key(|(application_id, _)| *application_id)
+        .map(|(application_id, application)| {
+          let application_tasks = tasks
+            .iter()
+            .filter_map(|(task_application_id, _, task)| if task_application_id == application_id { Some(task) } else { None })
+            .collect_vec();
+          application_health_from_tasks(application_id, application, &application_tasks, CRASH_LOOP_THRESHOLD, crash_loop_since)
+        })
+        .collect_vec(),
+    )
+  }
+
+  /// # Returns the timeline of all tasks
+  ///
+  /// # Parameters
+  /// * `concurrency` - maximum number of concurrent task requests, at least one request is executed
+  ///
+  /// # Returns
+  /// * `Ok<Vec<`[`TaskEvent`]`>>` - staged, started and stopped events of all tasks, sorted by timestamp
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn task_timeline(&self, concurrency: usize) -> DshApiResult<Vec<TaskEvent>> {
+    Ok(task_timeline_from_tasks(&self.tasks(concurrency).await?))
+  }
+
+  /// # Returns all tasks of all applications
+  ///
+  /// First the task ids of all applications are retrieved, one request per application,
+  /// after which the actual tasks are retrieved, one request per task.
+  ///
+  /// # Parameters
+  /// * `concurrency` - maximum number of concurrent requests, at least one request is executed
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(String, String, `[`Task`]`)>>` - list of application ids, task ids and the actual tasks,
+  ///   sorted by application id and task id
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn tasks(&self, concurrency: usize) -> DshApiResult<Vec<(String, String, Task)>> {
+    let application_task_ids: Vec<(String, Vec<String>)> = self
+      .stream_per_id(
+        self.get_task_ids(),
+        |application_id| async move {
+          let task_ids = self.get_task_appid_ids(&application_id).await?;
+          Ok((application_id, task_ids))
+        },
+        concurrency,
+      )
+      .try_collect()
+      .await?;
+    let mut tasks: Vec<(String, String, Task)> = stream::iter(
+      application_task_ids
+        .into_iter()
+        .flat_map(|(application_id, task_ids)| task_ids.into_iter().map(move |task_id| (application_id.clone(), task_id))),
+    )
+    .map(|(application_id, task_id)| async move { self.get_task_actual(&application_id, &task_id).await.map(|task| (application_id, task_id, task)) })
+    .buffer_unordered(concurrency.max(1))
+    .try_collect()
+    .await?;
+    tasks.sort_by(|(application_id_a, task_id_a, _), (application_id_b, task_id_b, _)| (application_id_a, task_id_a).cmp(&(application_id_b, task_id_b)));
+    Ok(tasks)
+  }
+}
+
+/// # Determine the health of an application from its tasks
+///
+/// A task is considered to be healthy when it is running and did not fail its health checks.
+/// A task is considered to be stopped when it has a stopped timestamp or when it is in a terminal state.
+/// Only the tasks that stopped in a failure state (`Dropped`, `Error`, `Failed` or `Lost`)
+/// at or after `crash_loop_since` are counted for the crash loop detection,
+/// so that tasks that were stopped by a redeployment or that stopped long ago
+/// do not mark an application as crash looping.
+///
+/// # Parameters
+/// * `application_id` - id of the application
+/// * `application` - configuration of the application
+/// * `tasks` - tasks of the application
+/// * `crash_loop_threshold` - number of recently failed tasks from which the application
+///   is considered to be crash looping
+/// * `crash_loop_since` - moment from which failed tasks are considered to be recently failed
+///
+/// # Returns
+/// [`ApplicationHealth`] - health summary of the application
+pub fn application_health_from_tasks(
+  application_id: &str,
+  application: &Application,
+  tasks: &[&Task],
+  crash_loop_threshold: usize,
+  crash_loop_since: DateTime<Utc>,
+) -> ApplicationHealth {
+  let running_tasks = tasks.iter().filter(|task| task.state == TaskState::Running).collect_vec();
+  let stopped_tasks = tasks.iter().filter(|task| task.stopped_at.is_some() || task_state_is_terminal(task.state)).count();
+  let recently_failed_tasks = tasks
+    .iter()
+    .filter(|task| task_state_is_failure(task.state) && task.stopped_at.is_some_and(|stopped_at| stopped_at >= crash_loop_since))
+    .count();
+  ApplicationHealth {
+    application_id: application_id.to_string(),
+    instances: application.instances,
+    running_tasks: running_tasks.len(),
+    healthy_tasks: running_tasks.iter().filter(|task| task.healthy != Some(false)).count(),
+    stopped_tasks,
+    crash_looping: recently_failed_tasks >= crash_loop_threshold,
+  }
+}
+
+/// # Build a timeline from tasks
+///
+/// # Parameters
+/// * `tasks` - list of application ids, task ids and tasks
+///
+/// # Returns
+/// `Vec<`[`TaskEvent`]`>` - staged, started and stopped events of all tasks, sorted by timestamp
+pub fn task_timeline_from_tasks(tasks: &[(String, String, Task)]) -> Vec<TaskEvent> {
+  let event = |application_id: &String, task_id: &String, timestamp: DateTime<Utc>, kind: TaskEventKind| TaskEvent {
+    timestamp,
+    application_id: application_id.to_string(),
+    task_id: task_id.to_string(),
+    kind,
+  };
+  tasks
+    .iter()
+    .flat_map(|(application_id, task_id, task)| {
+      vec![
+        Some(event(application_id, task_id, task.staged_at, TaskEventKind::Staged)),
+        Some(event(application_id, task_id, task.started_at, TaskEventKind::Started)),
+        task.stopped_at.map(|stopped_at| event(application_id, task_id, stopped_at, TaskEventKind::Stopped)),
+      ]
+    })
+    .flatten()
+    .sorted_by(|event_a, event_b| (event_a.timestamp, event_a.kind).cmp(&(event_b.timestamp, event_b.kind)))
+    .collect_vec()
+}
+
+fn task_state_is_failure(task_state: TaskState) -> bool {
+  matches!(task_state, TaskState::Dropped | TaskState::Error | TaskState::Failed | TaskState::Lost)
+}
+
+fn task_state_is_terminal(task_state: TaskState) -> bool {
+  matches!(
+    task_state,
+    TaskState::Dropped | TaskState::Error | TaskState::Failed | TaskState::Finished | TaskState::Gone | TaskState::GoneByOperator | TaskState::Killed | TaskState::Lost
+  )
+}
@@ -1,6 +1,8 @@
 //! # Additional methods to manage tenants
 //!
 //! _These functions are only available when the `manage` feature is enabled._
+//! _The methods that deal with managed streams are not available when the crate is generated
+//! from openapi specification version 1.8.0._
 //!
 //! Module that contains methods and functions to manage tenants.
 //!
@@ -45,16 +47,22 @@
 //! * [`managed_tenant_public_streams_access_rights(tenant name) -> [(stream id, rights)]`](DshApiClient::managed_tenant_public_streams_access_rights)
 
 use crate::dsh_api_client::DshApiClient;
+#[cfg(not(dsh_api_1_8))]
 use crate::stream::Stream;
 use crate::types::error::ConversionError;
 use crate::types::{
   GetTenantLimitByManagerByTenantByKindKind, LimitValue, LimitValueCertificateCount, LimitValueCertificateCountName, LimitValueConsumerRate, LimitValueConsumerRateName,
-  LimitValueCpu, LimitValueCpuName, LimitValueKafkaAclGroupCount, LimitValueKafkaAclGroupCountName, LimitValueMem, LimitValueMemName, LimitValuePartitionCount,
-  LimitValuePartitionCountName, LimitValueProducerRate, LimitValueProducerRateName, LimitValueRequestRate, LimitValueRequestRateName, LimitValueSecretCount,
-  LimitValueSecretCountName, LimitValueTopicCount, LimitValueTopicCountName, ManagedStream, ManagedStreamId, PublicManagedStream,
+  LimitValueCpu, LimitValueCpuName, LimitValueMem, LimitValueMemName, LimitValuePartitionCount, LimitValuePartitionCountName, LimitValueProducerRate, LimitValueProducerRateName,
+  LimitValueRequestRate, LimitValueRequestRateName, LimitValueSecretCount, LimitValueSecretCountName, LimitValueTopicCount, LimitValueTopicCountName,
 };
-use crate::{AccessRights, DshApiError, DshApiResult};
+#[cfg(not(dsh_api_1_8))]
+use crate::types::{LimitValueKafkaAclGroupCount, LimitValueKafkaAclGroupCountName, ManagedStream, ManagedStreamId, PublicManagedStream};
+use crate::DshApiResult;
+#[cfg(not(dsh_api_1_8))]
+use crate::{AccessRights, DshApiError};
+#[cfg(not(dsh_api_1_8))]
 use futures::future::{try_join, try_join_all};
+#[cfg(not(dsh_api_1_8))]
 use itertools::Itertools;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::{Display, Formatter};
@@ -92,7 +100,9 @@ impl DshApiClient {
   /// * `Ok<Vec<(ManagedStreamId, `[`ManagedStream`]`, `[`AccessRights`]`)>>` -
   ///   list of tuples consisting of stream ids, public streams and access rights
   /// * `Err<DshApiError>` - when the request could not be processed by the DSH
-  pub async fn managed_tenant_granted_internal_streams(&self, managed_tenant: &str) -> Result<Vec<(ManagedStreamId, ManagedStream, AccessRights)>, DshApiError> {
+  #[cfg(not(dsh_api_1_8))]
+  pub async fn managed_tenant_granted_internal_streams(&self, managed_tenant: impl AsRef<str>) -> Result<Vec<(ManagedStreamId, ManagedStream, AccessRights)>, DshApiError> {
+    let managed_tenant = managed_tenant.as_ref();
     let access_rights = self.managed_tenant_internal_streams_access_rights(managed_tenant).await?;
     let streams = try_join_all(
       access_rights
@@ -118,7 +128,9 @@ impl DshApiClient {
   /// * `Ok<Vec<(ManagedStreamId, `[`Stream`]`, `[`AccessRights`]`)>>` -
   ///   list of tuples consisting of stream ids, streams and access rights
   /// * `Err<DshApiError>` - when the request could not be processed by the DSH
-  pub async fn managed_tenant_granted_managed_streams(&self, managed_tenant: &str) -> DshApiResult<Vec<(ManagedStreamId, Stream, AccessRights)>> {
+  #[cfg(not(dsh_api_1_8))]
+  pub async fn managed_tenant_granted_managed_streams(&self, managed_tenant: impl AsRef<str>) -> DshApiResult<Vec<(ManagedStreamId, Stream, AccessRights)>> {
+    let managed_tenant = managed_tenant.as_ref();
     let (internal_streams, public_streams) = try_join(
       self.managed_tenant_granted_internal_streams(managed_tenant),
       self.managed_tenant_granted_public_streams(managed_tenant),
@@ -140,7 +152,9 @@ impl DshApiClient {
   /// * `Ok<Vec<(ManagedStreamId, `[`PublicManagedStream`]`, `[`AccessRights`]`)>>` -
   ///   list of tuples consisting of stream ids, public streams and access rights
   /// * `Err<DshApiError>` - when the request could not be processed by the DSH
-  pub async fn managed_tenant_granted_public_streams(&self, managed_tenant: &str) -> Result<Vec<(ManagedStreamId, PublicManagedStream, AccessRights)>, DshApiError> {
+  #[cfg(not(dsh_api_1_8))]
+  pub async fn managed_tenant_granted_public_streams(&self, managed_tenant: impl AsRef<str>) -> Result<Vec<(ManagedStreamId, PublicManagedStream, AccessRights)>, DshApiError> {
+    let managed_tenant = managed_tenant.as_ref();
     let access_rights = self.managed_tenant_public_streams_access_rights(managed_tenant).await?;
     let streams = try_join_all(access_rights.iter().map(|(managed_stream, _)| self.get_stream_public_configuration(managed_stream))).await?;
     Ok(
@@ -166,7 +180,9 @@ impl DshApiClient {
   /// * `Ok(false)` - when the managed tenant does not have read access to the internal managed
   ///   stream, or when the internal managed stream or the managed tenant does not exist
   /// * `Err<DshApiError>` - when the request could not be processed by the DSH
-  pub async fn managed_tenant_has_internal_read_access(&self, managed_tenant: &str, managed_stream: &ManagedStreamId) -> DshApiResult<bool> {
+  #[cfg(not(dsh_api_1_8))]
+  pub async fn managed_tenant_has_internal_read_access(&self, managed_tenant: impl AsRef<str>, managed_stream: &ManagedStreamId) -> DshApiResult<bool> {
+    let managed_tenant = managed_tenant.as_ref();
     match self.head_stream_internal_access_read(managed_stream, managed_tenant).await {
       Ok(()) => Ok(true),
       Err(DshApiError::NotFound(_)) => Ok(false),
@@ -188,7 +204,9 @@ impl DshApiClient {
   /// * `Ok(false)` - when the managed tenant does not have write access to the internal managed
   ///   stream, or when the internal managed stream or the managed tenant does not exist
   /// * `Err<DshApiError>` - when the request could not be processed by the DSH
-  pub async fn managed_tenant_has_internal_write_access(&self, managed_tenant: &str, managed_stream: &ManagedStreamId) -> DshApiResult<bool> {
+  #[cfg(not(dsh_api_1_8))]
+  pub async fn managed_tenant_has_internal_write_access(&self, managed_tenant: impl AsRef<str>, managed_stream: &ManagedStreamId) -> DshApiResult<bool> {
+    let managed_tenant = managed_tenant.as_ref();
     match self.head_stream_internal_access_write(managed_stream, managed_tenant).await {
       Ok(()) => Ok(true),
       Err(DshApiError::NotFound(_)) => Ok(false),
@@ -210,7 +228,9 @@ impl DshApiClient {
   /// * `Ok(false)` - when the managed tenant does not have read access to the public managed
   ///   stream, or when the public managed stream or the managed tenant does not exist
   /// * `Err<DshApiError>` - when the request could not be processed by the DSH
-  pub async fn managed_tenant_has_public_read_access(&self, managed_tenant: &str, managed_stream: &ManagedStreamId) -> DshApiResult<bool> {
+  #[cfg(not(dsh_api_1_8))]
+  pub async fn managed_tenant_has_public_read_access(&self, managed_tenant: impl AsRef<str>, managed_stream: &ManagedStreamId) -> DshApiResult<bool> {
+    let managed_tenant = managed_tenant.as_ref();
     match self.head_stream_public_access_read(managed_stream, managed_tenant).await {
       Ok(()) => Ok(true),
       Err(DshApiError::NotFound(_)) => Ok(false),
@@ -232,7 +252,9 @@ impl DshApiClient {
   /// * `Ok(false)` - when the managed tenant does not have write access to the public managed
   ///   stream, or when the public managed stream or the managed tenant does not exist
   /// * `Err<DshApiError>` - when the request could not be processed by the DSH
-  pub async fn managed_tenant_has_public_write_access(&self, managed_tenant: &str, managed_stream: &ManagedStreamId) -> DshApiResult<bool> {
+  #[cfg(not(dsh_api_1_8))]
+  pub async fn managed_tenant_has_public_write_access(&self, managed_tenant: impl AsRef<str>, managed_stream: &ManagedStreamId) -> DshApiResult<bool> {
+    let managed_tenant = managed_tenant.as_ref();
     match self.head_stream_public_access_write(managed_stream, managed_tenant).await {
       Ok(()) => Ok(true),
       Err(DshApiError::NotFound(_)) => Ok(false),
@@ -249,7 +271,9 @@ impl DshApiClient {
   /// * `Ok<Vec<(ManagedStreamId, `[`AccessRights`]`)>>` -
   ///   list of tuples consisting of stream ids and access rights
   /// * `Err<DshApiError>` - when the request could not be processed by the DSH
-  pub async fn managed_tenant_internal_streams_access_rights(&self, managed_tenant: &str) -> Result<Vec<(ManagedStreamId, AccessRights)>, DshApiError> {
+  #[cfg(not(dsh_api_1_8))]
+  pub async fn managed_tenant_internal_streams_access_rights(&self, managed_tenant: impl AsRef<str>) -> Result<Vec<(ManagedStreamId, AccessRights)>, DshApiError> {
+    let managed_tenant = managed_tenant.as_ref();
     let internal_managed_streams = self.get_stream_internals().await?;
     let internal_access = try_join_all(internal_managed_streams.iter().map(|managed_stream| {
       try_join(
@@ -281,7 +305,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<`[`LimitValue`]`>` - limit of the managed tenant
   /// * `Err<DshApiError>` - when the request could not be processed by the DSH
-  pub async fn managed_tenant_limit<T: TryInto<GetTenantLimitByManagerByTenantByKindKind>>(&self, managed_tenant: &str, kind: T) -> DshApiResult<LimitValue> {
+  pub async fn managed_tenant_limit<T: TryInto<GetTenantLimitByManagerByTenantByKindKind>>(&self, managed_tenant: impl AsRef<str>, kind: T) -> DshApiResult<LimitValue> {
+    let managed_tenant = managed_tenant.as_ref();
     let kind = kind.try_into().map_err(|_| ConversionError::from("invalid limit kind"))?;
     let limit = self.get_tenant_limit(managed_tenant, kind).await?;
     match limit {
@@ -293,6 +318,7 @@ impl DshApiClient {
       // They are included for completeness.
       LimitValue::CertificateCount(certificate_count) => Ok(LimitValue::from(certificate_count)),
       LimitValue::ConsumerRate(consumer_rate) => Ok(LimitValue::from(consumer_rate)),
+      #[cfg(not(dsh_api_1_8))]
       LimitValue::KafkaAclGroupCount(kafka_acl_group_count) => Ok(LimitValue::from(kafka_acl_group_count)),
       LimitValue::Mem(mem) => Ok(LimitValue::from(mem)),
       LimitValue::PartitionCount(partition_count) => Ok(LimitValue::from(partition_count)),
@@ -311,7 +337,8 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<`[`TenantLimits`]`>` - struct containing the limits of the managed tenant
   /// * `Err<DshApiError>` - when the request could not be processed by the DSH
-  pub async fn managed_tenant_limits(&self, managed_tenant: &str) -> DshApiResult<TenantLimits> {
+  pub async fn managed_tenant_limits(&self, managed_tenant: impl AsRef<str>) -> DshApiResult<TenantLimits> {
+    let managed_tenant = managed_tenant.as_ref();
     Ok(TenantLimits::from(&self.get_tenant_limits(managed_tenant).await?))
   }
 
@@ -324,7 +351,9 @@ impl DshApiClient {
   /// * `Ok<Vec<(ManagedStreamId, `[`AccessRights`]`)>>` -
   ///   list of tuples consisting of stream ids and access rights
   /// * `Err<DshApiError>` - when the request could not be processed by the DSH
-  pub async fn managed_tenant_public_streams_access_rights(&self, managed_tenant: &str) -> Result<Vec<(ManagedStreamId, AccessRights)>, DshApiError> {
+  #[cfg(not(dsh_api_1_8))]
+  pub async fn managed_tenant_public_streams_access_rights(&self, managed_tenant: impl AsRef<str>) -> Result<Vec<(ManagedStreamId, AccessRights)>, DshApiError> {
+    let managed_tenant = managed_tenant.as_ref();
     let public_managed_streams = self.get_stream_publics().await?;
     let public_access = try_join_all(public_managed_streams.iter().map(|managed_stream| {
       try_join(
@@ -358,6 +387,7 @@ impl DshApiClient {
         value: float_value as i64,
       })),
       GetTenantLimitByManagerByTenantByKindKind::Cpu => Ok(LimitValue::Cpu(LimitValueCpu { name: LimitValueCpuName::Cpu, value: float_value })),
+      #[cfg(not(dsh_api_1_8))]
       GetTenantLimitByManagerByTenantByKindKind::Kafkaaclgroupcount => Ok(LimitValue::KafkaAclGroupCount(LimitValueKafkaAclGroupCount {
         name: LimitValueKafkaAclGroupCountName::KafkaAclGroupCount,
         value: float_value as i64,
@@ -535,6 +565,7 @@ impl From<&TenantLimits> for Vec<LimitValue> {
     if let Some(cpu) = value.cpu {
       limit_values.push(LimitValue::Cpu(LimitValueCpu { name: LimitValueCpuName::Cpu, value: cpu }))
     }
+    #[cfg(not(dsh_api_1_8))]
     if let Some(kafka_acl_group_count) = value.kafka_acl_group_count {
       limit_values.push(LimitValue::KafkaAclGroupCount(LimitValueKafkaAclGroupCount {
         name: LimitValueKafkaAclGroupCountName::KafkaAclGroupCount,
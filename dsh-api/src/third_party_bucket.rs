@@ -0,0 +1,177 @@
+//! # Additional methods to manage third party bucket concessions
+//!
+//! Module that contains methods and functions to manage buckets that are shared between tenants.
+//! * Incoming concessions are buckets owned by a third party, that are registered in this tenant
+//!   with the credentials provided by the third party.
+//! * Outgoing concessions are named access grants on buckets owned by this tenant,
+//!   that provide the credentials for a third party.
+//!
+//! # Generated methods
+//!
+//! [`DshApiClient`] methods that are generated from the `openapi` specification.
+//!
+//! * [`delete_thirdpartybucket_configuration(id)`](DshApiClient::delete_thirdpartybucket_configuration)
+//! * [`get_bucket_fromthirdparty_ids() -> [id]`](DshApiClient::get_bucket_fromthirdparty_ids)
+//! * [`get_thirdpartybucket(id) -> ThirdPartyBucketConcessionStatus`](DshApiClient::get_thirdpartybucket)
+//! * [`get_thirdpartybucket_actual(id) -> ThirdPartyBucketConcession`](DshApiClient::get_thirdpartybucket_actual)
+//! * [`get_thirdpartybucket_configuration(id) -> ThirdPartyBucketConcession`](DshApiClient::get_thirdpartybucket_configuration)
+//! * [`get_thirdpartybucket_ids() -> [id]`](DshApiClient::get_thirdpartybucket_ids)
+//! * [`get_thirdpartybucket_status(id) -> AllocationStatus`](DshApiClient::get_thirdpartybucket_status)
+//! * [`post_thirdpartybucket(body)`](DshApiClient::post_thirdpartybucket)
+//!
+//! # Derived methods
+//!
+//! [`DshApiClient`] methods that add extra capabilities but do not directly call the
+//! DSH resource management API. These derived methods depend on the API methods for this.
+//!
+//! * [`incoming_bucket_concessions() -> [(id, concession status)]`](DshApiClient::incoming_bucket_concessions)
+//! * [`outgoing_bucket_concessions() -> [(bucket id, access name, access status)]`](DshApiClient::outgoing_bucket_concessions)
+//! * [`register_third_party_bucket(registration)`](DshApiClient::register_third_party_bucket)
+//! * [`third_party_buckets_with_dependant_applications() -> [(id, [dependant application])]`](DshApiClient::third_party_buckets_with_dependant_applications)
+//! * [`unregister_third_party_bucket(id)`](DshApiClient::unregister_third_party_bucket)
+//!
+//! # Functions
+//!
+//! * [`third_party_bucket_injections_from_applications(third party bucket ids, applications) -> [(id, [dependant application])]`](third_party_bucket_injections_from_applications)
+
+use crate::bucket::{bucket_injections_from_applications, BucketInjection};
+use crate::dsh_api_client::DshApiClient;
+use crate::types::{Application, BucketAccessStatus, ThirdPartyBucketConcessionRegistration, ThirdPartyBucketConcessionStatus};
+#[allow(unused_imports)]
+use crate::DshApiError;
+use crate::{DependantApplication, DshApiResult};
+use futures::future::try_join_all;
+use futures::try_join;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// # Additional methods to manage third party bucket concessions
+///
+/// Module that contains methods to manage buckets that are shared between tenants.
+/// * Derived methods - DshApiClient methods that add extra capabilities
+///   but depend on the API methods.
+///
+/// # Derived methods
+///
+/// [`DshApiClient`] methods that add extra capabilities but do not directly call the
+/// DSH resource management API. These derived methods depend on the API methods for this.
+///
+/// * [`incoming_bucket_concessions() -> [(id, concession status)]`](DshApiClient::incoming_bucket_concessions)
+/// * [`outgoing_bucket_concessions() -> [(bucket id, access name, access status)]`](DshApiClient::outgoing_bucket_concessions)
+/// * [`register_third_party_bucket(registration)`](DshApiClient::register_third_party_bucket)
+/// * [`third_party_buckets_with_dependant_applications() -> [(id, [dependant application])]`](DshApiClient::third_party_buckets_with_dependant_applications)
+/// * [`unregister_third_party_bucket(id)`](DshApiClient::unregister_third_party_bucket)
+impl DshApiClient {
+  /// # Returns all incoming bucket concessions
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(String, `[`ThirdPartyBucketConcessionStatus`]`)>>` - list of the ids and statuses
+  ///   of all third party buckets that are registered in this tenant, sorted by id
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn incoming_bucket_concessions(&self) -> DshApiResult<Vec<(String, ThirdPartyBucketConcessionStatus)>> {
+    let mut concession_ids = self.get_thirdpartybucket_ids().await?;
+    concession_ids.sort();
+    let concession_statuses = try_join_all(concession_ids.iter().map(|concession_id| self.get_thirdpartybucket(concession_id))).await?;
+    Ok(concession_ids.into_iter().zip(concession_statuses).collect_vec())
+  }
+
+  /// # Returns all outgoing bucket concessions
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(String, String, `[`BucketAccessStatus`]`)>>` - list of the bucket ids,
+  ///   access names and statuses of all access grants on the buckets of this tenant,
+  ///   sorted by bucket id and access name
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn outgoing_bucket_concessions(&self) -> DshApiResult<Vec<(String, String, BucketAccessStatus)>> {
+    let mut bucket_ids = self.get_bucket_ids().await?;
+    bucket_ids.sort();
+    let accesses_per_bucket = try_join_all(bucket_ids.iter().map(|bucket_id| self.bucket_accesses(bucket_id))).await?;
+    Ok(
+      bucket_ids
+        .into_iter()
+        .zip(accesses_per_bucket)
+        .flat_map(|(bucket_id, accesses)| {
+          accesses
+            .into_iter()
+            .map(move |(access_name, access_status)| (bucket_id.clone(), access_name, access_status))
+        })
+        .collect_vec(),
+    )
+  }
+
+  /// # Register a third party bucket
+  ///
+  /// Registers a bucket owned by a third party in this tenant,
+  /// using the share identifier and credentials provided by the third party.
+  ///
+  /// # Parameters
+  /// * `registration` - the name, share identifier and plaintext credentials of the bucket
+  ///
+  /// # Returns
+  /// * `Ok(())` - when the third party bucket was registered
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn register_third_party_bucket(&self, registration: &ThirdPartyBucketConcessionRegistration) -> DshApiResult<()> {
+    self.post_thirdpartybucket(registration).await
+  }
+
+  /// # Returns all third party buckets with the applications that use them
+  ///
+  /// See [`third_party_bucket_injections_from_applications`] for a description
+  /// of how the applications are matched.
+  ///
+  /// # Returns
+  /// * `Ok<Vec<(String, Vec<`[`DependantApplication`]`<`[`BucketInjection`]`>>)>>` - list of the ids
+  ///   of all third party buckets, sorted by id, together with the applications that use them
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn third_party_buckets_with_dependant_applications(&self) -> DshApiResult<Vec<(String, Vec<DependantApplication<BucketInjection>>)>> {
+    let (third_party_bucket_ids, applications) = try_join!(self.get_bucket_fromthirdparty_ids(), self.get_application_configuration_map())?;
+    Ok(third_party_bucket_injections_from_applications(&third_party_bucket_ids, &applications))
+  }
+
+  /// # Unregister a third party bucket
+  ///
+  /// # Parameters
+  /// * `third_party_bucket_id` - id of the registered third party bucket
+  ///
+  /// # Returns
+  /// * `Ok(())` - when the third party bucket was unregistered
+  /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
+  pub async fn unregister_third_party_bucket(&self, third_party_bucket_id: impl AsRef<str>) -> DshApiResult<()> {
+    self.delete_thirdpartybucket_configuration(third_party_bucket_id).await
+  }
+}
+
+/// # Get the applications that use third party buckets
+///
+/// Applications refer to a third party bucket in the same way as to buckets owned by the tenant,
+/// hence the injections are found with [`bucket_injections_from_applications`].
+///
+/// # Parameters
+/// * `third_party_bucket_ids` - ids of the third party buckets
+/// * `applications` - hashmap containing id/application pairs
+///
+/// # Returns
+/// `Vec<(String, Vec<DependantApplication<BucketInjection>>)>` - list of all third party bucket ids,
+/// sorted by id, together with the applications that use them, sorted by application id
+pub fn third_party_bucket_injections_from_applications(
+  third_party_bucket_ids: &[String],
+  applications: &HashMap<String, Application>,
+) -> Vec<(String, Vec<DependantApplication<BucketInjection>>)> {
+  third_party_bucket_ids
+    .iter()
+    .sorted()
+    .map(|third_party_bucket_id| {
+      let dependant_applications = bucket_injections_from_applications(third_party_bucket_id, None, applications)
+        .into_iter()
+        .map(|application_values| {
+          DependantApplication::new(
+            application_values.id.to_string(),
+            application_values.application.instances,
+            application_values.values,
+          )
+        })
+        .collect_vec();
+      (third_party_bucket_id.to_string(), dependant_applications)
+    })
+    .collect_vec()
+}
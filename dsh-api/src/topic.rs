@@ -26,6 +26,7 @@
 //! * [`topics_with_dependant_apps() -> [topic id, [app id, [resource]]]`](DshApiClient::topics_with_dependant_apps)
 //! * [`topics_with_dependants() -> [topic id, [dependant]]`](DshApiClient::topics_with_dependants)
 
+use crate::api::DshApi;
 use crate::app::{app_resources, apps_that_use_topic};
 use crate::application_types::ApplicationValues;
 use crate::dsh_api_client::DshApiClient;
@@ -84,7 +85,8 @@ impl DshApiClient {
   /// * `topic_id` - Identifies the scratch topic.
   ///
   /// Returns a sorted list of all topics together with the applications and apps that use them.
-  pub async fn topic_dependant_applications(&self, topic_id: &str) -> DshApiResult<Vec<DependantApplication<TopicInjection>>> {
+  pub async fn topic_dependant_applications(&self, topic_id: impl AsRef<str>) -> DshApiResult<Vec<DependantApplication<TopicInjection>>> {
+    let topic_id = topic_id.as_ref();
     let application_configuration_map = self.get_application_configuration_map().await?;
     let mut dependant_applications = Vec::<DependantApplication<TopicInjection>>::new();
     for application in topic_env_vars_from_applications(topic_id, &application_configuration_map) {
@@ -107,7 +109,8 @@ impl DshApiClient {
   /// * `topic_id` - Identifies the scratch topic.
   ///
   /// Returns a sorted list of all topics together with the applications and apps that use them.
-  pub async fn topic_dependant_apps(&self, topic_id: &str) -> DshApiResult<Vec<DependantApp>> {
+  pub async fn topic_dependant_apps(&self, topic_id: impl AsRef<str>) -> DshApiResult<Vec<DependantApp>> {
+    let topic_id = topic_id.as_ref();
     let appcatalogapp_configuration_map = self.get_appcatalogapp_configuration_map().await?;
     let mut dependant_apps = Vec::<DependantApp>::new();
     for (app_id, _, resource_ids) in apps_that_use_topic(topic_id, &appcatalogapp_configuration_map) {
@@ -125,7 +128,8 @@ impl DshApiClient {
   /// * `topic_id` - Identifies the scratch topic.
   ///
   /// Returns a sorted list of all topics together with the applications and apps that use them.
-  pub async fn topic_dependants(&self, topic_id: &str) -> DshApiResult<Vec<Dependant<TopicInjection>>> {
+  pub async fn topic_dependants(&self, topic_id: impl AsRef<str>) -> DshApiResult<Vec<Dependant<TopicInjection>>> {
+    let topic_id = topic_id.as_ref();
     let (application_configuration_map, appcatalogapp_configuration_map) = try_join!(self.get_application_configuration_map(), self.get_appcatalogapp_configuration_map())?;
     let mut dependants = Vec::<Dependant<TopicInjection>>::new();
     for application in topic_injections_from_applications(topic_id, &application_configuration_map) {
@@ -192,35 +196,35 @@ impl DshApiClient {
   ///
   /// Returns a sorted list of all topics together with the applications and apps that use them.
   pub async fn topics_with_dependants(&self) -> DshApiResult<Vec<(String, Vec<Dependant<TopicInjection>>)>> {
-    let (topic_ids, application_configuration_map, appcatalogapp_configuration_map) = try_join!(
-      self.get_topic_ids(),
-      self.get_application_configuration_map(),
-      self.get_appcatalogapp_configuration_map()
-    )?;
-    let mut topics = Vec::<(String, Vec<Dependant<TopicInjection>>)>::new();
-    for topic_id in topic_ids {
-      let mut dependants: Vec<Dependant<TopicInjection>> = vec![];
-      for application in topic_env_vars_from_applications(topic_id.as_str(), &application_configuration_map) {
-        dependants.push(Dependant::application(
-          application.id.to_string(),
-          application.application.instances,
-          application
-            .values
-            .iter()
-            .map(|(env_var, _)| TopicInjection::EnvVar(env_var.to_string()))
-            .collect_vec(),
-        ));
-      }
-      for (app_id, _, resource_ids) in apps_that_use_topic(topic_id.as_str(), &appcatalogapp_configuration_map) {
-        dependants.push(Dependant::app(
-          app_id.to_string(),
-          resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec(),
-        ));
-      }
-      topics.push((topic_id, dependants));
-    }
-    Ok(topics)
+    DshApi::topics_with_dependants(self).await
+  }
+}
+
+// Returns the applications and apps that use a topic, from the application and app configurations
+pub(crate) fn topic_dependants_from_configurations(
+  topic_id: &str,
+  applications: &HashMap<String, Application>,
+  apps: &HashMap<String, AppCatalogApp>,
+) -> Vec<Dependant<TopicInjection>> {
+  let mut dependants: Vec<Dependant<TopicInjection>> = vec![];
+  for application in topic_env_vars_from_applications(topic_id, applications) {
+    dependants.push(Dependant::application(
+      application.id.to_string(),
+      application.application.instances,
+      application
+        .values
+        .iter()
+        .map(|(env_var, _)| TopicInjection::EnvVar(env_var.to_string()))
+        .collect_vec(),
+    ));
+  }
+  for (app_id, _, resource_ids) in apps_that_use_topic(topic_id, apps) {
+    dependants.push(Dependant::app(
+      app_id.to_string(),
+      resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec(),
+    ));
   }
+  dependants
 }
 
 /// # Get application environment variables referencing topic
@@ -388,7 +392,7 @@ pub fn topic_used_in_applications<'a>(topic_id: &str, applications: &'a HashMap<
     .iter()
     .filter_map(|(application_id, application)| if topic_used_in_application(topic_id, application) { Some((application_id.as_str(), application)) } else { None })
     .collect_vec();
-  application_tuples.sort_by(|(id_a, _), (id_b, _)| id_a.cmp(id_b));
+  application_tuples.sort_by_key(|(id_a, _)| *id_a);
   application_tuples
 }
 
@@ -418,7 +422,7 @@ pub fn topics_from_application(application: &Application) -> Vec<&str> {
 /// * lists of scratch topic ids used in the applications, sorted by id
 ///
 /// The list will be sorted by application id.
-pub fn topics_from_applications(applications: &HashMap<String, Application>) -> Vec<ApplicationValues<&str>> {
+pub fn topics_from_applications(applications: &HashMap<String, Application>) -> Vec<ApplicationValues<'_, &str>> {
   let mut application_tuples = applications
     .iter()
     .filter_map(|(application_id, application)| {
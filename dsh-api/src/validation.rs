@@ -0,0 +1,497 @@
+//! # Validation against the openapi specification
+//!
+//! Module that contains functions to validate `json` values against the schemas and operations
+//! in the embedded openapi specification (see [`OPENAPI_SPEC`](crate::OPENAPI_SPEC)).
+//! The validation is done client-side, without calling the DSH resource management API.
+//!
+//! The following constraints from the specification are checked:
+//! * required fields,
+//! * value types (`array`, `boolean`, `integer`, `number`, `object` and `string`),
+//! * enumerations,
+//! * string patterns and lengths,
+//! * numeric ranges and
+//! * array lengths.
+//!
+//! Each violation is reported as a [`ValidationError`], which contains the path of the field
+//! that caused the violation (e.g. `$.exposedPorts.8080.mode`) and a description of the problem.
+//!
+//! When the `generic` feature is enabled, the generic
+//! [`put`](DshApiClient::put), [`post`](DshApiClient::post) and
+//! [`patch`](DshApiClient::patch) methods use these functions to validate the request body and
+//! the path parameters before a request is sent to the DSH resource management API.
+//! When strict mode is enabled (see
+//! [`DshApiClientFactory::with_strict_mode()`](crate::dsh_api_client_factory::DshApiClientFactory::with_strict_mode)),
+//! the client validates the response bodies of all `GET` operations
+//! and collects the deviations from the specification.
+//! The functions can also be used standalone, e.g. to lint `json` files.
+//!
+//! # Examples
+//!
+//! ```
+//! use dsh_api::validation::validate_json;
+//!
+//! let secret = r#"{ "name": "my-secret" }"#;
+//! let errors = validate_json("Secret", secret).unwrap();
+//! assert_eq!(errors.len(), 1);
+//! assert_eq!(errors[0].to_string(), "$: missing required field 'value'");
+//! ```
+//!
+//! # Functions
+//!
+//! * [`schema_names() -> [name]`](schema_names)
+//! * [`validate_json(schema name, json) -> [validation error]`](validate_json)
+//! * [`validate_path_parameters(method, path, [parameter]) -> [validation error]`](validate_path_parameters)
+//! * [`validate_request_body(method, path, body) -> [validation error]`](validate_request_body)
+//! * [`validate_response_body(method, path, status, body) -> [validation error]`](validate_response_body)
+//! * [`validate_schema(schema name, value) -> [validation error]`](validate_schema)
+
+#[allow(unused_imports)]
+use crate::dsh_api_client::DshApiClient;
+use crate::{DshApiError, DshApiResult, OPENAPI_SPEC};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+
+lazy_static! {
+  static ref OPENAPI_SPEC_VALUE: Value = serde_json::from_str(OPENAPI_SPEC).expect("embedded openapi specification is not valid json");
+}
+
+/// # Describes a violation of the openapi specification
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ValidationError {
+  /// Path of the field or parameter that violates the specification
+  pub path: String,
+  /// Description of the violation
+  pub message: String,
+}
+
+impl ValidationError {
+  pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+    Self { path: path.into(), message: message.into() }
+  }
+}
+
+impl Display for ValidationError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}: {}", self.path, self.message)
+  }
+}
+
+/// # Returns the names of all schemas
+///
+/// # Returns
+/// Sorted list of the names of all schemas defined in the openapi specification.
+/// These names can be used as the `schema_name` parameter for the
+/// [`validate_schema`] and [`validate_json`] functions.
+pub fn schema_names() -> Vec<&'static str> {
+  OPENAPI_SPEC_VALUE
+    .pointer("/components/schemas")
+    .and_then(Value::as_object)
+    .map(|schemas| schemas.keys().map(String::as_str).sorted().collect_vec())
+    .unwrap_or_default()
+}
+
+/// # Validate a `json` value against a schema
+///
+/// # Parameters
+/// * `schema_name` - name of the schema from the openapi specification, e.g. `Application`
+/// * `value` - the `json` value that will be validated
+///
+/// # Returns
+/// * `Ok<Vec<ValidationError>>` - list of all violations, empty when the value is valid
+/// * `Err<`[`DshApiError::Parameter`]`>` - when the schema does not exist
+pub fn validate_schema(schema_name: &str, value: &Value) -> DshApiResult<Vec<ValidationError>> {
+  let schema = OPENAPI_SPEC_VALUE
+    .pointer(&format!("/components/schemas/{}", schema_name))
+    .ok_or(DshApiError::Parameter(format!("schema '{}' does not exist", schema_name)))?;
+  Ok(Validator::default().validate(schema, value))
+}
+
+/// # Validate a `json` string against a schema
+///
+/// # Parameters
+/// * `schema_name` - name of the schema from the openapi specification, e.g. `Application`
+/// * `json` - the `json` text that will be validated
+///
+/// # Returns
+/// * `Ok<Vec<ValidationError>>` - list of all violations, empty when the `json` is valid
+/// * `Err<`[`DshApiError::Parameter`]`>` - when the schema does not exist
+///   or when `json` could not be parsed
+pub fn validate_json(schema_name: &str, json: &str) -> DshApiResult<Vec<ValidationError>> {
+  let value = serde_json::from_str::<Value>(json).map_err(|error| DshApiError::Parameter(format!("invalid json ({})", error)))?;
+  validate_schema(schema_name, &value)
+}
+
+/// # Validate a request body against an operation
+///
+/// # Parameters
+/// * `method` - method of the operation, e.g. `put`
+/// * `path` - path of the operation, e.g. `/allocation/{tenant}/application/{appid}/configuration`
+/// * `body` - the `json` value that will be validated
+///
+/// # Returns
+/// * `Ok<Vec<ValidationError>>` - list of all violations, empty when the body is valid
+/// * `Err<`[`DshApiError::Parameter`]`>` - when the operation does not exist or has no request body
+pub fn validate_request_body(method: &str, path: &str, body: &Value) -> DshApiResult<Vec<ValidationError>> {
+  let operation = operation(method, path)?;
+  let schema = operation
+    .pointer("/requestBody/content")
+    .and_then(Value::as_object)
+    .and_then(|content| content.values().next())
+    .and_then(|media_type| media_type.get("schema"))
+    .ok_or(DshApiError::Parameter(format!("operation {} {} has no request body", method, path)))?;
+  Ok(Validator::default().validate(schema, body))
+}
+
+/// # Validate a response body against an operation
+///
+/// Other than the validation of requests, this validation will also report fields that are
+/// not defined in the specification, since these fields will be silently dropped
+/// when the response is deserialized.
+///
+/// # Parameters
+/// * `method` - method of the operation, e.g. `get`
+/// * `path` - path of the operation, e.g. `/allocation/{tenant}/application/{appid}/configuration`
+/// * `status` - status code of the response, e.g. `200`
+/// * `body` - the `json` value that will be validated
+///
+/// # Returns
+/// * `Ok<Vec<ValidationError>>` - list of all deviations, empty when the body is valid
+/// * `Err<`[`DshApiError::Parameter`]`>` - when the operation does not exist or does not define
+///   a `json` response body for `status`
+pub fn validate_response_body(method: &str, path: &str, status: u16, body: &Value) -> DshApiResult<Vec<ValidationError>> {
+  let operation = operation(method, path)?;
+  let schema = operation
+    .get("responses")
+    .and_then(|responses| responses.get(status.to_string()))
+    .and_then(|response| response.pointer("/content/application~1json/schema"))
+    .ok_or(DshApiError::Parameter(format!(
+      "operation {} {} has no json response body for status {}",
+      method, path, status
+    )))?;
+  Ok(Validator { report_unknown_fields: true }.validate(schema, body))
+}
+
+/// # Validate path parameters against an operation
+///
+/// The first path parameter of the operation (`tenant` or `manager`) is provided by the client
+/// and must not be included in `parameters`.
+///
+/// # Parameters
+/// * `method` - method of the operation, e.g. `get`
+/// * `path` - path of the operation, e.g. `/allocation/{tenant}/aclgroup/{id}/configuration`
+/// * `parameters` - values of the parameters, in the order of the specification
+///
+/// # Returns
+/// * `Ok<Vec<ValidationError>>` - list of all violations, empty when all parameters are valid
+/// * `Err<`[`DshApiError::Parameter`]`>` - when the operation does not exist
+pub fn validate_path_parameters(method: &str, path: &str, parameters: &[&str]) -> DshApiResult<Vec<ValidationError>> {
+  let operation = operation(method, path)?;
+  let validator = Validator::default();
+  let mut errors = vec![];
+  let parameter_schemas = operation
+    .get("parameters")
+    .and_then(Value::as_array)
+    .map(|parameters| parameters.iter().map(|parameter| validator.resolve(parameter)).collect_vec())
+    .unwrap_or_default();
+  for (parameter, value) in parameter_schemas
+    .iter()
+    .skip(1)
+    .filter(|parameter| parameter.get("name").and_then(Value::as_str) != Some("Authorization"))
+    .zip(parameters)
+  {
+    if let (Some(name), Some(schema)) = (parameter.get("name").and_then(Value::as_str), parameter.get("schema")) {
+      validator.validate_value(schema, &Value::String(value.to_string()), name, &mut errors);
+    }
+  }
+  Ok(errors)
+}
+
+/// Validates the parameters and body of a generic request
+///
+/// Used by the generic methods, will return a [`DshApiError::Parameter`] describing all violations.
+#[cfg(feature = "generic")]
+pub(crate) fn validate_request(method: &str, path: &str, parameters: &[&str], body: Option<&str>) -> DshApiResult<()> {
+  let mut errors = validate_path_parameters(method, path, parameters)?;
+  if let Some(body) = body {
+    let body = serde_json::from_str::<Value>(body).map_err(|error| DshApiError::Parameter(format!("json body could not be parsed ({})", error)))?;
+    errors.append(&mut validate_request_body(method, path, &body)?);
+  }
+  if errors.is_empty() {
+    Ok(())
+  } else {
+    Err(DshApiError::Parameter(format!("invalid request ({})", errors.iter().join(", "))))
+  }
+}
+
+fn operation(method: &str, path: &str) -> DshApiResult<&'static Value> {
+  OPENAPI_SPEC_VALUE
+    .get("paths")
+    .and_then(|paths| paths.get(path))
+    .and_then(|path_item| path_item.get(method.to_lowercase()))
+    .ok_or(DshApiError::Parameter(format!("operation {} {} does not exist", method, path)))
+}
+
+#[derive(Default)]
+struct Validator {
+  report_unknown_fields: bool,
+}
+
+impl Validator {
+  fn validate(&self, schema: &Value, value: &Value) -> Vec<ValidationError> {
+    let mut errors = vec![];
+    self.validate_value(schema, value, "$", &mut errors);
+    errors
+  }
+
+  fn validate_value(&self, schema: &Value, value: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    self.validate_value_with(schema, value, path, self.report_unknown_fields, errors)
+  }
+
+  // Returns the names of all fields that are allowed by the schema,
+  // or `None` when the schema allows arbitrary fields
+  fn known_fields<'a>(&self, schema: &'a Value) -> Option<Vec<&'a str>> {
+    let schema = self.resolve(schema);
+    if schema
+      .get("additionalProperties")
+      .is_some_and(|additional_properties| additional_properties != &Value::Bool(false))
+      || schema.get("oneOf").is_some()
+      || schema.get("anyOf").is_some()
+      || (schema.get("properties").is_none() && schema.get("allOf").is_none())
+    {
+      return None;
+    }
+    let mut known_fields = schema
+      .get("properties")
+      .and_then(Value::as_object)
+      .map(|properties| properties.keys().map(String::as_str).collect_vec())
+      .unwrap_or_default();
+    if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+      for sub_schema in all_of {
+        known_fields.append(&mut self.known_fields(sub_schema)?);
+      }
+    }
+    Some(known_fields)
+  }
+
+  fn resolve<'a>(&self, schema: &'a Value) -> &'a Value {
+    match schema.get("$ref").and_then(Value::as_str) {
+      Some(reference) => match reference.strip_prefix('#').and_then(|pointer| OPENAPI_SPEC_VALUE.pointer(pointer)) {
+        Some(referenced_schema) => self.resolve(referenced_schema),
+        None => schema,
+      },
+      None => schema,
+    }
+  }
+
+  fn validate_value_with(&self, schema: &Value, value: &Value, path: &str, report_unknown_fields: bool, errors: &mut Vec<ValidationError>) {
+    let schema = self.resolve(schema);
+    if value.is_null() && schema.get("nullable").and_then(Value::as_bool).unwrap_or_default() {
+      return;
+    }
+    if report_unknown_fields {
+      if let (Some(fields), Some(known_fields)) = (value.as_object(), self.known_fields(schema)) {
+        for field_name in fields.keys() {
+          if !known_fields.contains(&field_name.as_str()) {
+            errors.push(ValidationError::new(path, format!("unknown field '{}'", field_name)));
+          }
+        }
+      }
+    }
+    if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+      for sub_schema in all_of {
+        self.validate_value_with(sub_schema, value, path, false, errors);
+      }
+    }
+    if let Some(one_of) = schema.get("oneOf").or(schema.get("anyOf")).and_then(Value::as_array) {
+      self.validate_alternatives(schema, one_of, value, path, errors);
+    }
+    if let Some(enumeration) = schema.get("enum").and_then(Value::as_array) {
+      if !enumeration.contains(value) {
+        errors.push(ValidationError::new(
+          path,
+          format!("value {} is not one of the allowed values ({})", value, enumeration.iter().join(", ")),
+        ));
+        return;
+      }
+    }
+    match schema.get("type").and_then(Value::as_str) {
+      Some("array") => self.validate_array(schema, value, path, errors),
+      Some("boolean") => {
+        if !value.is_boolean() {
+          errors.push(type_error(path, "boolean", value));
+        }
+      }
+      Some("integer") => {
+        if value.is_i64() || value.is_u64() {
+          validate_range(schema, value, path, errors);
+        } else {
+          errors.push(type_error(path, "integer", value));
+        }
+      }
+      Some("number") => {
+        if value.is_number() {
+          validate_range(schema, value, path, errors);
+        } else {
+          errors.push(type_error(path, "number", value));
+        }
+      }
+      Some("object") => self.validate_object(schema, value, path, errors),
+      Some("string") => validate_string(schema, value, path, errors),
+      _ => {
+        if schema.get("properties").is_some() {
+          self.validate_object(schema, value, path, errors)
+        }
+      }
+    }
+  }
+
+  fn validate_alternatives(&self, schema: &Value, alternatives: &[Value], value: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let discriminated_schema = schema.get("discriminator").and_then(|discriminator| {
+      let property_name = discriminator.get("propertyName").and_then(Value::as_str)?;
+      let discriminator_value = value.get(property_name).and_then(Value::as_str)?;
+      discriminator.get("mapping").and_then(|mapping| mapping.get(discriminator_value))
+    });
+    match discriminated_schema {
+      Some(reference) => self.validate_value(&serde_json::json!({ "$ref": reference }), value, path, errors),
+      None => {
+        let matches_alternative = alternatives.iter().any(|alternative| {
+          let mut alternative_errors = vec![];
+          self.validate_value(alternative, value, path, &mut alternative_errors);
+          alternative_errors.is_empty()
+        });
+        if !matches_alternative {
+          errors.push(ValidationError::new(path, "value does not match any of the allowed schemas"));
+        }
+      }
+    }
+  }
+
+  fn validate_array(&self, schema: &Value, value: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    match value.as_array() {
+      Some(elements) => {
+        if let Some(min_items) = schema.get("minItems").and_then(Value::as_u64) {
+          if (elements.len() as u64) < min_items {
+            errors.push(ValidationError::new(path, format!("array must contain at least {} elements", min_items)));
+          }
+        }
+        if let Some(max_items) = schema.get("maxItems").and_then(Value::as_u64) {
+          if (elements.len() as u64) > max_items {
+            errors.push(ValidationError::new(path, format!("array must contain at most {} elements", max_items)));
+          }
+        }
+        if let Some(items) = schema.get("items") {
+          for (index, element) in elements.iter().enumerate() {
+            self.validate_value(items, element, &format!("{}[{}]", path, index), errors);
+          }
+        }
+      }
+      None => errors.push(type_error(path, "array", value)),
+    }
+  }
+
+  fn validate_object(&self, schema: &Value, value: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    match value.as_object() {
+      Some(fields) => {
+        let required_fields = schema
+          .get("required")
+          .and_then(Value::as_array)
+          .map(|required| required.iter().filter_map(Value::as_str).collect_vec())
+          .unwrap_or_default();
+        for required_field in &required_fields {
+          if !fields.contains_key(*required_field) {
+            errors.push(ValidationError::new(path, format!("missing required field '{}'", required_field)));
+          }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let additional_properties = schema.get("additionalProperties").filter(|additional_properties| additional_properties.is_object());
+        for (field_name, field_value) in fields {
+          let field_path = format!("{}.{}", path, field_name);
+          match properties.and_then(|properties| properties.get(field_name)) {
+            Some(property_schema) => {
+              if !field_value.is_null() || required_fields.contains(&field_name.as_str()) {
+                self.validate_value(property_schema, field_value, &field_path, errors)
+              }
+            }
+            None => {
+              if let Some(additional_properties_schema) = additional_properties {
+                self.validate_value(additional_properties_schema, field_value, &field_path, errors)
+              }
+            }
+          }
+        }
+      }
+      None => errors.push(type_error(path, "object", value)),
+    }
+  }
+}
+
+fn validate_range(schema: &Value, value: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+  let number = value.as_f64().unwrap_or_default();
+  if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+    if schema.get("exclusiveMinimum").and_then(Value::as_bool).unwrap_or_default() {
+      if number <= minimum {
+        errors.push(ValidationError::new(path, format!("value {} must be greater than {}", value, minimum)));
+      }
+    } else if number < minimum {
+      errors.push(ValidationError::new(path, format!("value {} must be greater than or equal to {}", value, minimum)));
+    }
+  }
+  if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+    if schema.get("exclusiveMaximum").and_then(Value::as_bool).unwrap_or_default() {
+      if number >= maximum {
+        errors.push(ValidationError::new(path, format!("value {} must be less than {}", value, maximum)));
+      }
+    } else if number > maximum {
+      errors.push(ValidationError::new(path, format!("value {} must be less than or equal to {}", value, maximum)));
+    }
+  }
+}
+
+fn validate_string(schema: &Value, value: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+  match value.as_str() {
+    Some(string) => {
+      if let Some(min_length) = schema.get("minLength").and_then(Value::as_u64) {
+        if (string.chars().count() as u64) < min_length {
+          errors.push(ValidationError::new(path, format!("string must contain at least {} characters", min_length)));
+        }
+      }
+      if let Some(max_length) = schema.get("maxLength").and_then(Value::as_u64) {
+        if (string.chars().count() as u64) > max_length {
+          errors.push(ValidationError::new(path, format!("string must contain at most {} characters", max_length)));
+        }
+      }
+      if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+        match regress::Regex::new(pattern) {
+          Ok(regex) => {
+            if regex.find(string).is_none() {
+              errors.push(ValidationError::new(path, format!("value \"{}\" does not match pattern \"{}\"", string, pattern)));
+            }
+          }
+          Err(_) => errors.push(ValidationError::new(path, format!("invalid pattern \"{}\" in specification", pattern))),
+        }
+      }
+    }
+    None => errors.push(type_error(path, "string", value)),
+  }
+}
+
+fn type_error(path: &str, expected_type: &str, value: &Value) -> ValidationError {
+  let actual_type = match value {
+    Value::Null => "null",
+    Value::Bool(_) => "boolean",
+    Value::Number(number) => {
+      if number.is_f64() {
+        "number"
+      } else {
+        "integer"
+      }
+    }
+    Value::String(_) => "string",
+    Value::Array(_) => "array",
+    Value::Object(_) => "object",
+  };
+  ValidationError::new(path, format!("expected {}, found {}", expected_type, actual_type))
+}
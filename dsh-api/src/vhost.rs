@@ -89,7 +89,7 @@ impl DshApiClient {
         ));
       }
     }
-    let mut vhosts: Vec<(String, Vec<DependantApplication<VhostInjection>>)> = Vec::from_iter(vhosts_map.into_iter());
+    let mut vhosts: Vec<(String, Vec<DependantApplication<VhostInjection>>)> = Vec::from_iter(vhosts_map);
     vhosts.sort_by(|(vhost_id_a, _), (vhost_id_b, _)| vhost_id_a.cmp(vhost_id_b));
     Ok(vhosts)
   }
@@ -122,30 +122,41 @@ impl DshApiClient {
   /// will be included.
   pub async fn vhosts_with_dependants(&self) -> DshApiResult<Vec<(String, Vec<Dependant<VhostInjection>>)>> {
     let (application_configuration_map, appcatalogapp_configuration_map) = try_join!(self.get_application_configuration_map(), self.get_appcatalogapp_configuration_map())?;
-    let mut vhosts_with_dependants_map = HashMap::<String, Vec<Dependant<VhostInjection>>>::new();
-    for ApplicationValues { id, application, values } in vhosts_from_applications(&application_configuration_map) {
-      for (vhost, port, _) in values {
-        let dependants = vhosts_with_dependants_map.entry(vhost.clone()).or_default();
-        dependants.push(Dependant::application(
-          id.to_string(),
-          application.instances,
-          vec![VhostInjection::Vhost(port.to_string(), None)],
-        ));
-      }
+    Ok(vhosts_with_dependants_from_configurations(
+      &application_configuration_map,
+      &appcatalogapp_configuration_map,
+    ))
+  }
+}
+
+// Returns all vhosts with the applications and apps that use them, from the application and app configurations
+pub(crate) fn vhosts_with_dependants_from_configurations(
+  applications: &HashMap<String, Application>,
+  apps: &HashMap<String, AppCatalogApp>,
+) -> Vec<(String, Vec<Dependant<VhostInjection>>)> {
+  let mut vhosts_with_dependants_map = HashMap::<String, Vec<Dependant<VhostInjection>>>::new();
+  for ApplicationValues { id, application, values } in vhosts_from_applications(applications) {
+    for (vhost, port, _) in values {
+      let dependants = vhosts_with_dependants_map.entry(vhost.clone()).or_default();
+      dependants.push(Dependant::application(
+        id.to_string(),
+        application.instances,
+        vec![VhostInjection::Vhost(port.to_string(), None)],
+      ));
     }
-    let mut app_ids = appcatalogapp_configuration_map.keys().collect_vec();
-    app_ids.sort();
-    for app_id in app_ids {
-      let app = appcatalogapp_configuration_map.get(app_id).unwrap();
-      for (_, vhost_string) in vhost_strings_from_app(app) {
-        let dependants = vhosts_with_dependants_map.entry(vhost_string.vhost_name.clone()).or_default();
-        dependants.push(Dependant::app(app_id.clone(), vec![vhost_string.to_string()]));
-      }
+  }
+  let mut app_ids = apps.keys().collect_vec();
+  app_ids.sort();
+  for app_id in app_ids {
+    let app = apps.get(app_id).unwrap();
+    for (_, vhost_string) in vhost_strings_from_app(app) {
+      let dependants = vhosts_with_dependants_map.entry(vhost_string.vhost_name.clone()).or_default();
+      dependants.push(Dependant::app(app_id.clone(), vec![vhost_string.to_string()]));
     }
-    let mut vhosts: Vec<(String, Vec<Dependant<VhostInjection>>)> = Vec::from_iter(vhosts_with_dependants_map.into_iter());
-    vhosts.sort_by(|(vhost_id_a, _), (vhost_id_b, _)| vhost_id_a.cmp(vhost_id_b));
-    Ok(vhosts)
   }
+  let mut vhosts: Vec<(String, Vec<Dependant<VhostInjection>>)> = Vec::from_iter(vhosts_with_dependants_map);
+  vhosts.sort_by(|(vhost_id_a, _), (vhost_id_b, _)| vhost_id_a.cmp(vhost_id_b));
+  vhosts
 }
 
 /// # Get application port mappings for vhost id
@@ -175,7 +186,7 @@ pub fn vhost_port_mappings_from_application<'a>(vhost_id: &str, application: &'a
       })
     })
     .collect_vec();
-  port_mappings.sort_by(|(port_a, _), (port_b, _)| port_a.cmp(port_b));
+  port_mappings.sort_by_key(|(port_a, _)| *port_a);
   port_mappings
 }
 
@@ -246,7 +257,7 @@ pub(crate) fn vhost_strings_from_app(app: &AppCatalogApp) -> Vec<(&str, VhostStr
       }
     }
   }
-  resources.sort_by(|(resource_id_a, _), (resource_id_b, _)| resource_id_a.cmp(resource_id_b));
+  resources.sort_by_key(|(resource_id_a, _)| *resource_id_a);
   resources
 }
 
@@ -294,7 +305,7 @@ pub fn vhosts_from_application(application: &Application) -> Vec<(String, &str,
 /// * application id
 /// * application reference
 /// * lists of vhost ids, ports and port mappings used in the application
-pub fn vhosts_from_applications(applications: &HashMap<String, Application>) -> Vec<ApplicationValues<(String, &str, &PortMapping)>> {
+pub fn vhosts_from_applications(applications: &HashMap<String, Application>) -> Vec<ApplicationValues<'_, (String, &str, &PortMapping)>> {
   let mut vhosts: Vec<ApplicationValues<(String, &str, &PortMapping)>> = vec![];
   for (application_id, application) in applications {
     for (port, port_mapping) in &application.exposed_ports {
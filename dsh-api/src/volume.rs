@@ -24,6 +24,7 @@
 //! * [`volumes_with_dependant_apps() -> [volume id, [(app id, [resource])]]`](DshApiClient::volumes_with_dependant_apps)
 //! * [`volumes_with_dependants() -> [volume id, [(id, [injection|resource])]]`](DshApiClient::volumes_with_dependants)
 
+use crate::api::DshApi;
 use crate::app::{app_resources, apps_that_use_volume};
 use crate::application_types::ApplicationValues;
 use crate::dsh_api_client::DshApiClient;
@@ -92,26 +93,14 @@ impl DshApiClient {
   /// # Returns
   /// * `Ok<(VolumeStatus, Vec<UsedBy>)>` - volume status and usage.
   /// * `Err<`[`DshApiError`]`>` - when the request could not be processed by the DSH
-  pub async fn volume_with_dependants(&self, volume_id: &str) -> DshApiResult<(VolumeStatus, Vec<Dependant<VolumeInjection>>)> {
+  pub async fn volume_with_dependants(&self, volume_id: impl AsRef<str>) -> DshApiResult<(VolumeStatus, Vec<Dependant<VolumeInjection>>)> {
+    let volume_id = volume_id.as_ref();
     let (volume_status, applications, apps) = try_join!(
       self.get_volume(volume_id),
       self.get_application_configuration_map(),
       self.get_appcatalogapp_configuration_map()
     )?;
-    let mut dependants: Vec<Dependant<VolumeInjection>> = vec![];
-    for ApplicationValues { id, application, values } in volume_paths_from_applications(volume_id, &applications) {
-      dependants.push(Dependant::application(
-        id.to_string(),
-        application.instances,
-        values.iter().map(|path| VolumeInjection::Volume(path.to_string())).collect_vec(),
-      ));
-    }
-    for (app_id, _, resource_ids) in apps_that_use_volume(volume_id, &apps) {
-      dependants.push(Dependant::app(
-        app_id.to_string(),
-        resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec(),
-      ));
-    }
+    let dependants = volume_dependants_from_configurations(volume_id, &applications, &apps);
     Ok((volume_status, dependants))
   }
 
@@ -158,31 +147,31 @@ impl DshApiClient {
   ///
   /// Returns a sorted list of all volumes together with the applications and apps that use them.
   pub async fn volumes_with_dependants(&self) -> DshApiResult<Vec<(String, Vec<Dependant<VolumeInjection>>)>> {
-    let (volume_ids, applications, apps) = try_join!(
-      self.get_volume_ids(),
-      self.get_application_configuration_map(),
-      self.get_appcatalogapp_configuration_map()
-    )?;
-    let mut volumes = Vec::<(String, Vec<Dependant<VolumeInjection>>)>::new();
-    for volume_id in volume_ids {
-      let mut dependants: Vec<Dependant<VolumeInjection>> = vec![];
-      for application in volume_paths_from_applications(volume_id.as_str(), &applications) {
-        dependants.push(Dependant::application(
-          application.id.to_string(),
-          application.application.instances,
-          application.values.iter().map(|env_var| VolumeInjection::EnvVar(env_var.to_string())).collect_vec(),
-        ));
-      }
-      for (app_id, _, resource_ids) in apps_that_use_volume(volume_id.as_str(), &apps) {
-        dependants.push(Dependant::app(
-          app_id.to_string(),
-          resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec(),
-        ));
-      }
-      volumes.push((volume_id, dependants));
-    }
-    Ok(volumes)
+    DshApi::volumes_with_dependants(self).await
+  }
+}
+
+// Returns the applications and apps that use a volume, from the application and app configurations
+pub(crate) fn volume_dependants_from_configurations(
+  volume_id: &str,
+  applications: &HashMap<String, Application>,
+  apps: &HashMap<String, AppCatalogApp>,
+) -> Vec<Dependant<VolumeInjection>> {
+  let mut dependants: Vec<Dependant<VolumeInjection>> = vec![];
+  for ApplicationValues { id, application, values } in volume_paths_from_applications(volume_id, applications) {
+    dependants.push(Dependant::application(
+      id.to_string(),
+      application.instances,
+      values.iter().map(|path| VolumeInjection::Volume(path.to_string())).collect_vec(),
+    ));
+  }
+  for (app_id, _, resource_ids) in apps_that_use_volume(volume_id, apps) {
+    dependants.push(Dependant::app(
+      app_id.to_string(),
+      resource_ids.iter().map(|resource_id| resource_id.to_string()).collect_vec(),
+    ));
   }
+  dependants
 }
 
 pub fn volume_paths_from_applications<'a>(volume_id: &str, applications: &'a HashMap<String, Application>) -> Vec<ApplicationValues<'a, &'a str>> {
@@ -227,7 +216,7 @@ pub fn volumes_from_application(application: &Application) -> Vec<(&str, &str)>
         .ok()
     })
     .collect_vec();
-  volumes.sort_by(|(volume_a, _), (volume_b, _)| volume_a.cmp(volume_b));
+  volumes.sort_by_key(|(volume_a, _)| *volume_a);
   volumes
 }
 
@@ -245,7 +234,7 @@ pub fn volumes_from_application(application: &Application) -> Vec<(&str, &str)>
 /// * sorted list of pairs of volume ids and mounting paths
 ///
 /// The list is sorted by application id.
-pub fn volumes_from_applications(applications: &HashMap<String, Application>) -> Vec<ApplicationValues<(&str, &str)>> {
+pub fn volumes_from_applications(applications: &HashMap<String, Application>) -> Vec<ApplicationValues<'_, (&str, &str)>> {
   let mut application_tuples = applications
     .iter()
     .filter_map(|(application_id, application)| {
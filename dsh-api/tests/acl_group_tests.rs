@@ -0,0 +1,94 @@
+#![cfg(not(dsh_api_1_8))]
+
+use crate::common::mock_client;
+use dsh_api::acl_group::{acl_group_coverage_from_configurations, acl_group_topic_rights, AclAccess, AclGroupTopicRights, UncoveredTopic};
+use dsh_api::mock_server::MockServer;
+use dsh_api::types::{Application, KafkaAclGroup, KafkaAclGroupTopic, KafkaAclGroupTopicKind, Topic};
+use std::collections::HashMap;
+
+mod common;
+
+fn acl_group_topic(kind: KafkaAclGroupTopicKind, name: &str) -> KafkaAclGroupTopic {
+  KafkaAclGroupTopic { kind, name: name.to_string() }
+}
+
+fn acl_group() -> KafkaAclGroup {
+  KafkaAclGroup {
+    readable_streams: vec![
+      acl_group_topic(KafkaAclGroupTopicKind::Topic, "topic-1"),
+      acl_group_topic(KafkaAclGroupTopicKind::Public, "weather"),
+      acl_group_topic(KafkaAclGroupTopicKind::Topic, "deleted-topic"),
+    ],
+    writable_streams: vec![acl_group_topic(KafkaAclGroupTopicKind::Topic, "topic-1")],
+  }
+}
+
+fn application() -> Application {
+  Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .user("1234:1234")
+    .topics(vec!["topic-1".to_string(), "topic-2".to_string()])
+    .readable_streams(vec!["stream.weather".to_string()])
+    .writable_streams(vec!["stream.weather".to_string()])
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_acl_group_topic_rights() {
+  assert_eq!(
+    acl_group_topic_rights(&acl_group()),
+    vec![
+      AclGroupTopicRights { kind: KafkaAclGroupTopicKind::Topic, name: "deleted-topic".to_string(), read: true, write: false },
+      AclGroupTopicRights { kind: KafkaAclGroupTopicKind::Topic, name: "topic-1".to_string(), read: true, write: true },
+      AclGroupTopicRights { kind: KafkaAclGroupTopicKind::Public, name: "weather".to_string(), read: true, write: false },
+    ]
+  );
+}
+
+#[test]
+fn test_acl_group_coverage_from_configurations() {
+  let acl_groups = HashMap::from([("my-acl-group".to_string(), acl_group())]);
+  let applications = HashMap::from([("my-application".to_string(), application())]);
+  let coverage = acl_group_coverage_from_configurations(&acl_groups, &applications, &["topic-1".to_string(), "topic-2".to_string()]);
+  let uncovered = |name: &str, access: AclAccess| UncoveredTopic { application_id: "my-application".to_string(), name: name.to_string(), access };
+  assert_eq!(
+    coverage.uncovered_topics,
+    vec![uncovered("stream.weather", AclAccess::Write), uncovered("topic-2", AclAccess::ReadOrWrite)]
+  );
+  assert_eq!(coverage.missing_topics, vec![("my-acl-group".to_string(), "deleted-topic".to_string())]);
+  assert!(acl_group_coverage_from_configurations(&HashMap::new(), &HashMap::new(), &[]).is_empty());
+}
+
+#[test]
+fn test_acl_group_coverage_read_only_topic() {
+  let read_only_acl_group = KafkaAclGroup { readable_streams: vec![acl_group_topic(KafkaAclGroupTopicKind::Topic, "topic-2")], writable_streams: vec![] };
+  let acl_groups = HashMap::from([("my-acl-group".to_string(), acl_group()), ("read-only-acl-group".to_string(), read_only_acl_group)]);
+  let applications = HashMap::from([("my-application".to_string(), application())]);
+  let coverage = acl_group_coverage_from_configurations(&acl_groups, &applications, &["topic-1".to_string(), "topic-2".to_string()]);
+  assert_eq!(
+    coverage.uncovered_topics,
+    vec![UncoveredTopic { application_id: "my-application".to_string(), name: "stream.weather".to_string(), access: AclAccess::Write }]
+  );
+}
+
+#[tokio::test]
+async fn test_acl_group_matrix_and_coverage() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  let id = "my-acl-group".parse().unwrap();
+  client.put_aclgroup_configuration(&id, &acl_group()).await.unwrap();
+  let topic = Topic::builder().partitions(1).replication_factor(1).build().unwrap();
+  client.put_topic_configuration("topic-1", &topic).await.unwrap();
+  client.put_application_configuration("my-application", &application()).await.unwrap();
+  let matrix = client.acl_group_matrix().await.unwrap();
+  assert_eq!(matrix.len(), 1);
+  assert_eq!(matrix[0].0, "my-acl-group");
+  assert_eq!(matrix[0].1.len(), 3);
+  assert_eq!(matrix[0].1[1].to_string(), "topic:topic-1 (read/write)");
+  let coverage = client.acl_group_coverage().await.unwrap();
+  assert_eq!(coverage.uncovered_topics.len(), 2);
+  assert_eq!(coverage.missing_topics, vec![("my-acl-group".to_string(), "deleted-topic".to_string())]);
+}
@@ -0,0 +1,59 @@
+use crate::common::mock_client;
+use dsh_api::mock_server::MockServer;
+use dsh_api::types::Bucket;
+use serde_json::json;
+
+mod common;
+
+#[tokio::test]
+async fn test_bucket_watch() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  client.put_bucket_configuration("my-bucket", &Bucket::new(true, true)).await.unwrap();
+  assert!(client.bucket_watch("my-bucket").await.unwrap().is_none());
+  client.enable_bucket_watch("my-bucket").await.unwrap();
+  assert!(server.resource("/allocation/my-tenant/bucket/my-bucket/bucketwatch").is_some());
+  // the mock server stores an empty watch, since the request has no body
+  server.insert("/allocation/my-tenant/bucket/my-bucket/bucketwatch", json!({ "bucket": "my-bucket" }));
+  let bucket_watch = client.bucket_watch("my-bucket").await.unwrap().unwrap();
+  assert_eq!(bucket_watch.configuration.unwrap().bucket, "my-bucket");
+  client.disable_bucket_watch("my-bucket").await.unwrap();
+  assert!(client.bucket_watch("my-bucket").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_bucket_access() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  client.put_bucket_configuration("my-bucket", &Bucket::new(true, true)).await.unwrap();
+  client.grant_bucket_access("my-bucket", "reader", true, false).await.unwrap();
+  let configuration = client.get_bucket_access_configuration("my-bucket", "reader").await.unwrap();
+  assert!(configuration.readable && !configuration.writable);
+  assert_eq!(configuration.bucket, "my-bucket");
+  assert_eq!(configuration.name, "reader");
+  // the mock server returns the configuration as actual value, which lacks the credential references
+  server.insert(
+    "/allocation/my-tenant/bucket/my-bucket/bucketaccess/reader",
+    json!({ "bucket": "my-bucket", "name": "reader", "readable": true, "writable": false, "credentialidentifierref": "id", "credentialsecretref": "secret" }),
+  );
+  let accesses = client.bucket_accesses("my-bucket").await.unwrap();
+  assert_eq!(accesses.len(), 1);
+  assert_eq!(accesses[0].0, "reader");
+  assert_eq!(accesses[0].1.actual.as_ref().unwrap().credentialsecretref, "secret");
+  client.revoke_bucket_access("my-bucket", "reader").await.unwrap();
+  assert!(client.bucket_accesses("my-bucket").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_bucket_with_dependants() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  client.put_bucket_configuration("my-bucket", &Bucket::new(true, true)).await.unwrap();
+  let bucket = client.bucket_with_dependants("my-bucket").await.unwrap();
+  assert!(bucket.bucket.configuration.unwrap().encrypted);
+  assert!(bucket.watch.is_none());
+  assert!(bucket.accesses.is_empty());
+  assert!(bucket.dependants.is_empty());
+  server.insert("/allocation/my-tenant/bucket/my-bucket/bucketwatch", json!({ "bucket": "my-bucket" }));
+  assert!(client.bucket_with_dependants("my-bucket").await.unwrap().watch.is_some());
+}
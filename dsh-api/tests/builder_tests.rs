@@ -0,0 +1,90 @@
+use dsh_api::types::{Application, FlinkCluster, FlinkJobManager, Secret, Topic};
+#[cfg(all(feature = "manage", not(dsh_api_1_8)))]
+use dsh_api::types::{ManagedTenant, ManagedTenantServicesName, PublicManagedStream, PublicManagedStreamContract};
+use dsh_api::DshApiError;
+
+#[test]
+fn test_application_builder() {
+  let application = Application::builder()
+    .image("registry.cp.kpn-dsh.com/my-tenant/my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .user("1234:1234")
+    .env_entry("LOG_LEVEL", "info")
+    .env_entry("PORT", "8080")
+    .build()
+    .unwrap();
+  assert_eq!(application.image, "registry.cp.kpn-dsh.com/my-tenant/my-image:0.0.1");
+  assert_eq!(application.env.len(), 2);
+  assert_eq!(application.env.get("LOG_LEVEL").unwrap(), "info");
+  assert_eq!(application.instances, 1);
+  assert!(application.needs_token);
+  assert!(!application.single_instance);
+  assert_eq!(application.health_check, None);
+}
+
+#[test]
+fn test_application_builder_missing_field() {
+  let error = Application::builder().image("image").cpus(0.5).mem(512).build().unwrap_err();
+  assert!(matches!(error, DshApiError::Parameter(_)));
+  assert_eq!(error.to_string(), "missing required field 'user' for Application");
+}
+
+#[test]
+fn test_flink_cluster_builder_invalid_field() {
+  let error = FlinkCluster::builder()
+    .version("1.20")
+    .zone("internal")
+    .job_manager(FlinkJobManager::new(0.1, 1024))
+    .build()
+    .unwrap_err();
+  match error {
+    DshApiError::Parameter(message) => assert!(message.starts_with("invalid FlinkCluster ($.jobManager.cpus: "), "{}", message),
+    other => panic!("unexpected error {:?}", other),
+  }
+}
+
+#[test]
+fn test_secret_builder() {
+  let secret = Secret::builder().name("my-secret").value("abcdef").build().unwrap();
+  assert_eq!(secret, Secret::new("my-secret", "abcdef"));
+}
+
+#[test]
+fn test_topic_builder() {
+  let topic = Topic::builder()
+    .partitions(3)
+    .replication_factor(3)
+    .kafka_properties_entry("cleanup.policy", "compact")
+    .build()
+    .unwrap();
+  assert_eq!(topic.partitions, 3);
+  assert_eq!(topic.kafka_properties.get("cleanup.policy").unwrap(), "compact");
+  assert!(Topic::builder().partitions(3).build().is_err());
+}
+
+#[test]
+#[cfg(all(feature = "manage", not(dsh_api_1_8)))]
+fn test_managed_tenant_builder() {
+  let managed_tenant = ManagedTenant::builder().manager("manager").name("tenant").build().unwrap();
+  assert_eq!(managed_tenant.manager, "manager");
+  assert_eq!(managed_tenant.name, "tenant");
+  assert_eq!(managed_tenant.services.len(), 3);
+  assert!(managed_tenant
+    .services
+    .iter()
+    .any(|service| service.name == ManagedTenantServicesName::Monitoring && service.enabled));
+}
+
+#[test]
+#[cfg(all(feature = "manage", not(dsh_api_1_8)))]
+fn test_public_managed_stream_builder() {
+  let public_managed_stream = PublicManagedStream::builder()
+    .contract(PublicManagedStreamContract::default())
+    .partitions(1)
+    .replication_factor(3)
+    .build()
+    .unwrap();
+  assert_eq!(public_managed_stream.partitions, 1);
+  assert!(public_managed_stream.kafka_properties.is_empty());
+}
@@ -0,0 +1,204 @@
+use crate::common::mock_client;
+use chrono::{DateTime, Utc};
+use dsh_api::certificate::{dns_name_matches, expiring_certificates, parse_pem_certificates, vhost_domains, CertificateKeyType, CertificatePem};
+use dsh_api::mock_server::MockServer;
+use dsh_api::platform::DshPlatform;
+use dsh_api::types::{ActualCertificate, Application, PortMapping};
+use dsh_api::vhost::VhostString;
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+mod common;
+
+static CA: &str = include_str!("certificates/ca.pem");
+static FAKE_CA: &str = include_str!("certificates/fake-ca.pem");
+static INTERMEDIATE: &str = include_str!("certificates/intermediate.pem");
+static LEAF: &str = include_str!("certificates/leaf.pem");
+static CA_KEY: &str = include_str!("certificates/ca.key");
+static LEAF_KEY: &str = include_str!("certificates/leaf.key");
+static LEAF_ECPARAM_KEY: &str = include_str!("certificates/leaf-ecparam.key");
+static OTHER_KEY: &str = include_str!("certificates/other.key");
+static ROOT_SUBJECT: &str = "CN=My Root CA, O=My Organization";
+
+fn actual_certificate_value(dns_names: &[&str], not_after: &str) -> Value {
+  json!({
+    "certChainSecret": "my-certificate-chain",
+    "keySecret": "my-certificate-key",
+    "distinguishedName": "CN=my-vhost.dsh-dev.dsh.np.aws.kpn.com",
+    "dnsNames": dns_names,
+    "notBefore": "2025-01-01T00:00:00Z",
+    "notAfter": not_after,
+    "serialNumber": "01"
+  })
+}
+
+fn application(vhost: &str) -> Application {
+  let mut application = Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .user("1234:1234")
+    .instances(1)
+    .build()
+    .unwrap();
+  let port_mapping: PortMapping = serde_json::from_value(json!({ "vhost": vhost })).unwrap();
+  application.exposed_ports.insert("8080".to_string(), port_mapping);
+  application
+}
+
+#[test]
+fn test_parse_pem_certificates() {
+  let chain = parse_pem_certificates(format!("{}{}", LEAF, INTERMEDIATE).as_str()).unwrap();
+  assert_eq!(chain.len(), 2);
+  let leaf = &chain[0];
+  assert_eq!(leaf.subject, "CN=my-vhost.dsh-dev.dsh.np.aws.kpn.com");
+  assert_eq!(leaf.issuer, chain[1].subject);
+  assert_eq!(
+    leaf.subject_alternative_names,
+    vec!["my-vhost.dsh-dev.dsh.np.aws.kpn.com".to_string(), "*.my-tenant.dsh-dev.dsh.np.aws.kpn.com".to_string()]
+  );
+  assert_eq!(leaf.key_type, CertificateKeyType::Ec(256));
+  assert!(!leaf.is_ca && chain[1].is_ca);
+  assert!(leaf.not_before < leaf.not_after);
+  assert!(leaf.covers("other-vhost.my-tenant.dsh-dev.dsh.np.aws.kpn.com"));
+  assert_eq!(parse_pem_certificates(CA).unwrap()[0].key_type, CertificateKeyType::Rsa(2048));
+  assert!(parse_pem_certificates("no certificate").is_err());
+}
+
+#[test]
+fn test_dns_name_matches() {
+  assert!(dns_name_matches("my-vhost.example.com", "MY-VHOST.example.com"));
+  assert!(dns_name_matches("*.example.com", "my-vhost.example.com"));
+  assert!(!dns_name_matches("*.example.com", "example.com"));
+  assert!(!dns_name_matches("*.example.com", "my-vhost.my-tenant.example.com"));
+}
+
+#[test]
+fn test_vhost_domains() {
+  let platform = DshPlatform::new("nplz");
+  assert_eq!(
+    vhost_domains(&VhostString::from_str("{ vhost('my-vhost') }").unwrap(), &platform, "my-tenant"),
+    vec!["my-vhost.dsh-dev.dsh.np.aws.kpn.com".to_string(), "my-vhost.my-tenant.dsh-dev.dsh.np.aws.kpn.com".to_string()]
+  );
+  assert_eq!(
+    vhost_domains(&VhostString::from_str("{ vhost('my-vhost','private') }").unwrap(), &platform, "my-tenant"),
+    vec!["my-vhost.my-tenant.dsh-dev.dsh.np.aws.kpn.org".to_string()]
+  );
+}
+
+#[test]
+fn test_expiring_certificates() {
+  let certificate = |not_after: &str| serde_json::from_value::<ActualCertificate>(actual_certificate_value(&[], not_after)).unwrap();
+  let certificates = vec![
+    ("later".to_string(), certificate("2025-03-01T00:00:00Z")),
+    ("soon".to_string(), certificate("2025-01-11T00:00:00Z")),
+    ("expired".to_string(), certificate("2024-12-01T00:00:00Z")),
+  ];
+  let moment = DateTime::<Utc>::from_str("2025-01-01T00:00:00Z").unwrap();
+  let expiring = expiring_certificates(&certificates, moment, 30);
+  assert_eq!(
+    expiring.iter().map(|expiry| expiry.certificate_id.as_str()).collect::<Vec<_>>(),
+    vec!["expired", "soon"]
+  );
+  assert!(expiring[0].is_expired());
+  assert_eq!(expiring[1].days_remaining, 10);
+}
+
+#[tokio::test]
+async fn test_certificate_chain_and_vhost_coverage() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  server.insert(
+    "/allocation/my-tenant/certificate/my-certificate",
+    actual_certificate_value(&["*.my-tenant.dsh-dev.dsh.np.aws.kpn.com"], "2125-01-01T00:00:00Z"),
+  );
+  server.insert("/allocation/my-tenant/secret/my-certificate-chain", json!(format!("{}{}", LEAF, INTERMEDIATE)));
+  let chain = client.certificate_chain("my-certificate").await.unwrap();
+  assert_eq!(chain.len(), 2);
+  assert_eq!(chain[1].subject, "CN=My Intermediate CA, O=My Organization");
+  assert!(client.certificates_expiring_within(30, 2).await.unwrap().is_empty());
+  client
+    .put_application_configuration("covered-application", &application("{ vhost('my-vhost') }"))
+    .await
+    .unwrap();
+  client
+    .put_application_configuration("uncovered-application", &application("{ vhost('my-vhost','private') }"))
+    .await
+    .unwrap();
+  let vhosts = client.vhosts_without_certificate(2).await.unwrap();
+  assert_eq!(vhosts.len(), 1);
+  assert_eq!(vhosts[0].0, "my-vhost");
+  assert_eq!(vhosts[0].1[0].application_id, "uncovered-application");
+}
+
+#[test]
+fn test_certificate_pem_validate() {
+  let chain = CertificatePem::new(LEAF, format!("{}{}", INTERMEDIATE, CA), LEAF_KEY).validate().unwrap();
+  assert_eq!(chain.len(), 3);
+  assert_eq!(chain[2].key_type, CertificateKeyType::Rsa(2048));
+  assert!(CertificatePem::new(LEAF, INTERMEDIATE, LEAF_KEY).with_omitted_root(ROOT_SUBJECT).validate().is_ok());
+  assert!(CertificatePem::new(CA, "", CA_KEY).validate().is_ok());
+  assert!(CertificatePem::new(LEAF, INTERMEDIATE, LEAF_ECPARAM_KEY)
+    .with_omitted_root(ROOT_SUBJECT)
+    .validate()
+    .is_ok());
+  assert!(CertificatePem::new(LEAF, INTERMEDIATE, LEAF_KEY)
+    .with_omitted_root("o = My Organization,cn=My Root CA ")
+    .validate()
+    .is_ok());
+  let error = |certificate_pem: CertificatePem| certificate_pem.validate().unwrap_err().to_string();
+  assert_eq!(
+    error(CertificatePem::new(LEAF, INTERMEDIATE, LEAF_KEY)),
+    format!("certificate chain is not complete, issuer '{}' is missing", ROOT_SUBJECT)
+  );
+  assert!(error(CertificatePem::new(LEAF, INTERMEDIATE, OTHER_KEY)).contains("does not match"));
+  assert!(error(CertificatePem::new(LEAF, INTERMEDIATE, CA_KEY)).contains("does not match"));
+  assert!(error(CertificatePem::new(LEAF, format!("{}{}", CA, INTERMEDIATE), LEAF_KEY)).contains("not ordered"));
+  assert!(error(CertificatePem::new(LEAF, "", LEAF_KEY)).contains("not complete"));
+  assert!(error(CertificatePem::new(LEAF, "", LEAF_KEY).with_omitted_root(ROOT_SUBJECT)).contains("not complete"));
+  assert!(error(CertificatePem::new(LEAF, CA, LEAF_KEY)).contains("not complete"));
+  assert!(error(CertificatePem::new(LEAF, INTERMEDIATE, LEAF_KEY).with_omitted_root("CN=My Root CA")).contains("not complete"));
+  assert!(error(CertificatePem::new(LEAF, format!("{}{}", INTERMEDIATE, FAKE_CA), LEAF_KEY)).contains("signature of 'CN=My Intermediate CA, O=My Organization' is not valid"));
+}
+
+#[test]
+fn test_certificate_pem_from_files() {
+  let directory = format!("{}/tests/certificates", env!("CARGO_MANIFEST_DIR"));
+  let certificate_pem = CertificatePem::from_files(
+    format!("{}/leaf.pem", directory),
+    format!("{}/intermediate.pem", directory),
+    format!("{}/leaf.key", directory),
+  )
+  .unwrap();
+  assert_eq!(certificate_pem, CertificatePem::new(LEAF, INTERMEDIATE, LEAF_KEY));
+  assert_eq!(parse_pem_certificates(certificate_pem.cert_chain().as_str()).unwrap().len(), 2);
+  assert!(CertificatePem::from_files(format!("{}/missing.pem", directory), "", "").is_err());
+}
+
+#[tokio::test]
+async fn test_upload_certificate() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  let certificate_pem = CertificatePem::new(LEAF, INTERMEDIATE, LEAF_KEY).with_omitted_root(ROOT_SUBJECT);
+  let certificate = client.upload_certificate("my-certificate", &certificate_pem).await.unwrap();
+  assert_eq!(client.get_certificate_configuration("my-certificate").await.unwrap(), certificate);
+  assert_eq!(client.get_secret(&certificate.key_secret).await.unwrap(), LEAF_KEY);
+  assert_eq!(client.get_secret(&certificate.cert_chain_secret).await.unwrap(), certificate_pem.cert_chain());
+  assert!(client
+    .upload_certificate("my-certificate", &CertificatePem::new(LEAF, INTERMEDIATE, OTHER_KEY))
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_upload_certificate_rollback() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  server.inject_error("PUT", "/allocation/{tenant}/certificate/{id}/configuration", 500);
+  assert!(client
+    .upload_certificate("my-certificate", &CertificatePem::new(LEAF, INTERMEDIATE, LEAF_KEY).with_omitted_root(ROOT_SUBJECT))
+    .await
+    .is_err());
+  assert!(client.get_secret_ids().await.unwrap().is_empty());
+  assert!(client.get_certificate_ids().await.unwrap().is_empty());
+}
@@ -1,5 +1,9 @@
 use dsh_api::dsh_api_client::DshApiClient;
 use dsh_api::dsh_api_client_factory::DshApiClientFactory;
+use dsh_api::dsh_api_tenant::DshApiTenant;
+#[cfg(feature = "mock")]
+use dsh_api::mock_server::MockServer;
+use dsh_api::platform::DshPlatform;
 use dsh_api::DshApiError;
 use std::io::Write;
 
@@ -21,7 +25,7 @@ pub fn print_header(header: &str) {
 }
 
 #[allow(unused)]
-pub async fn get_client<'a>() -> Result<DshApiClient, String> {
+pub async fn get_client() -> Result<DshApiClient, String> {
   // Explicit try_factory variable declaration is important since type inference will not work here
   let try_factory: Result<DshApiClientFactory, DshApiError> = DshApiClientFactory::try_default();
   match try_factory {
@@ -32,3 +36,15 @@ pub async fn get_client<'a>() -> Result<DshApiClient, String> {
     Err(error) => Err(format!("could not create client factory ({})", error)),
   }
 }
+
+#[allow(unused)]
+pub fn mock_client_factory(endpoint: &str) -> DshApiClientFactory {
+  let tenant = DshApiTenant::new("my-tenant".to_string(), DshPlatform::new("nplz"));
+  DshApiClientFactory::create_from_access_token(tenant, "access-token".to_string()).with_endpoint(endpoint)
+}
+
+#[allow(unused)]
+#[cfg(feature = "mock")]
+pub async fn mock_client(server: &MockServer) -> DshApiClient {
+  mock_client_factory(server.url().as_str()).client().await.unwrap()
+}
@@ -0,0 +1,73 @@
+use crate::common::mock_client;
+use dsh_api::data_catalog::{data_catalog_discrepancies_from_ids, DataCatalogAssetKind};
+use dsh_api::mock_server::MockServer;
+use dsh_api::types::{Bucket, Topic};
+use dsh_api::DshApiError;
+use std::str::FromStr;
+
+mod common;
+
+#[test]
+fn test_data_catalog_asset_kind() {
+  for kind in DataCatalogAssetKind::ALL {
+    assert_eq!(DataCatalogAssetKind::from_str(kind.to_string().as_str()).unwrap(), kind);
+  }
+  assert_eq!(DataCatalogAssetKind::WritableStream.to_string(), "writablestream");
+  assert!(matches!(DataCatalogAssetKind::from_str("topic"), Err(DshApiError::Parameter(_))));
+}
+
+#[test]
+fn test_data_catalog_discrepancies_from_ids() {
+  let asset_ids = vec![
+    (DataCatalogAssetKind::Bucket, "bucket-1".to_string()),
+    (DataCatalogAssetKind::Bucket, "deleted-bucket".to_string()),
+    (DataCatalogAssetKind::WritableStream, "topic-1".to_string()),
+  ];
+  let discrepancies = data_catalog_discrepancies_from_ids(
+    &asset_ids,
+    &["bucket-1".to_string(), "bucket-2".to_string()],
+    &["topic-1".to_string(), "topic-2".to_string()],
+  );
+  assert_eq!(discrepancies.orphan_assets, vec![(DataCatalogAssetKind::Bucket, "deleted-bucket".to_string())]);
+  assert_eq!(
+    discrepancies.unregistered_resources,
+    vec![(DataCatalogAssetKind::Bucket, "bucket-2".to_string()), (DataCatalogAssetKind::WritableStream, "topic-2".to_string())]
+  );
+  assert!(data_catalog_discrepancies_from_ids(&[], &[], &[]).is_empty());
+}
+
+#[tokio::test]
+async fn test_register_data_catalog_assets() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  client.put_bucket_configuration("my-bucket", &Bucket::new(true, true)).await.unwrap();
+  let topic = Topic::builder().partitions(1).replication_factor(1).build().unwrap();
+  client.put_topic_configuration("my-topic", &topic).await.unwrap();
+  client.put_topic_configuration("other-topic", &topic).await.unwrap();
+  client.register_data_catalog_asset(DataCatalogAssetKind::Bucket, "my-bucket").await.unwrap();
+  client.register_data_catalog_asset(DataCatalogAssetKind::WritableStream, "my-topic").await.unwrap();
+  assert!(matches!(
+    client.register_data_catalog_asset(DataCatalogAssetKind::Bucket, "my-topic").await,
+    Err(DshApiError::NotFound(_))
+  ));
+  assert_eq!(
+    client.data_catalog_asset_ids().await.unwrap(),
+    vec![(DataCatalogAssetKind::Bucket, "my-bucket".to_string()), (DataCatalogAssetKind::WritableStream, "my-topic".to_string())]
+  );
+  let assets = client.data_catalog_assets().await.unwrap();
+  assert_eq!(assets[1].2.configuration.as_ref().unwrap().kind, "writablestream");
+  let discrepancies = client.data_catalog_discrepancies().await.unwrap();
+  assert!(discrepancies.orphan_assets.is_empty());
+  assert_eq!(
+    discrepancies.unregistered_resources,
+    vec![(DataCatalogAssetKind::WritableStream, "other-topic".to_string())]
+  );
+  client.delete_topic_configuration("my-topic").await.unwrap();
+  client.unregister_data_catalog_asset(DataCatalogAssetKind::Bucket, "my-bucket").await.unwrap();
+  let discrepancies = client.data_catalog_discrepancies().await.unwrap();
+  assert_eq!(discrepancies.orphan_assets, vec![(DataCatalogAssetKind::WritableStream, "my-topic".to_string())]);
+  assert_eq!(
+    discrepancies.unregistered_resources,
+    vec![(DataCatalogAssetKind::Bucket, "my-bucket".to_string()), (DataCatalogAssetKind::WritableStream, "other-topic".to_string())]
+  );
+}
@@ -0,0 +1,139 @@
+use crate::common::mock_client;
+use dsh_api::database::{apps_that_use_database, database_env_vars_from_application, DatabaseDiff, DatabaseStatusSummary};
+use dsh_api::manifest::Manifest;
+use dsh_api::mock_server::MockServer;
+use dsh_api::types::{AllocationStatus, AppCatalogApp, Application, Database, DatabaseStatus};
+use dsh_api::{Dependant, DshApiError};
+use serde_json::json;
+use std::collections::HashMap;
+
+mod common;
+
+fn database(mem: i64) -> Database {
+  Database { cpus: 1.0, extensions: vec![], instances: 3, mem, snapshot_interval: None, version: Some("16".to_string()), volume_size: 10 }
+}
+
+fn manifest_payload() -> String {
+  json!({
+    "id": "kpn/my-service",
+    "name": "my-service",
+    "version": "0.0.1",
+    "vendor": "kpn",
+    "contact": "contact@kpn.com",
+    "resources": {
+      "allocation/${@tenant}/database/${@name}": {
+        "cpus": 1.0, "extensions": [], "instances": 1, "mem": 1024, "name": "${@name}", "snapshotInterval": 60, "version": "16", "volumeSize": 10
+      }
+    }
+  })
+  .to_string()
+}
+
+#[test]
+fn test_database_status_summary() {
+  let database_status = DatabaseStatus {
+    actual: Some(database(2048)),
+    configuration: Some(database(4096)),
+    status: AllocationStatus { derived_from: None, notifications: vec![], provisioned: false },
+  };
+  let summary = DatabaseStatusSummary::new("my-database", &database_status);
+  assert!(summary.configured && summary.deployed && !summary.provisioned);
+  assert!(!summary.is_healthy());
+  assert_eq!(summary.diff.unwrap().differences(), vec![("mem".to_string(), "4096 / 2048".to_string())]);
+  assert!(DatabaseDiff::differences_between_databases(&database(2048), &database(2048)).is_empty());
+}
+
+#[test]
+fn test_database_env_vars_from_application() {
+  let application = Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .user("1234:1234")
+    .env(HashMap::from([
+      ("DB_HOST".to_string(), "db.my-tenant.marathon.mesos".to_string()),
+      ("DB_URL".to_string(), "postgresql://db.my-tenant.marathon.mesos:5432/db?ssl=true".to_string()),
+      ("MY_DB_HOST".to_string(), "my-db.my-tenant.marathon.mesos".to_string()),
+      ("MY_DB_URL".to_string(), "postgresql://my-db.my-tenant.marathon.mesos:5432/my-db".to_string()),
+      ("OTHER_HOST".to_string(), "db.my-tenant.marathon.mesos.example.com".to_string()),
+    ]))
+    .build()
+    .unwrap();
+  assert_eq!(
+    database_env_vars_from_application("db.my-tenant.marathon.mesos", &application),
+    vec!["DB_HOST", "DB_URL"]
+  );
+  assert_eq!(
+    database_env_vars_from_application("my-db.my-tenant.marathon.mesos", &application),
+    vec!["MY_DB_HOST", "MY_DB_URL"]
+  );
+}
+
+#[test]
+fn test_apps_that_use_database() {
+  let manifest = serde_json::from_str::<Manifest>(manifest_payload().as_str()).unwrap();
+  let apps = HashMap::from([
+    (
+      "my-service".to_string(),
+      AppCatalogApp::new("my-service", "appcatalog/manifest/kpn/my-service/0.0.1"),
+    ),
+    (
+      "other-service".to_string(),
+      AppCatalogApp::new("other-service", "appcatalog/manifest/kpn/other-service/0.0.1"),
+    ),
+  ]);
+  let apps_that_use_database = apps_that_use_database("my-service", &apps, &[manifest]);
+  assert_eq!(apps_that_use_database.len(), 1);
+  assert_eq!(apps_that_use_database[0].0, "my-service");
+  assert_eq!(apps_that_use_database[0].2, vec!["allocation/${@tenant}/database/${@name}".to_string()]);
+}
+
+#[tokio::test]
+async fn test_database_connection() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  assert!(matches!(client.database_connection("my-database").await, Err(DshApiError::NotFound(_))));
+  client.put_database_configuration("my-database", &database(4096)).await.unwrap();
+  let connection = client.database_connection("my-database").await.unwrap();
+  assert_eq!(connection.url(), "postgresql://my-database.my-tenant.marathon.mesos:5432/my-database");
+  assert_eq!(connection.version, Some("16".to_string()));
+  assert!(connection
+    .environment_variables()
+    .contains(&("PGHOST".to_string(), "my-database.my-tenant.marathon.mesos".to_string())));
+}
+
+#[tokio::test]
+async fn test_databases_with_dependants() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  client.put_database_configuration("my-service", &database(4096)).await.unwrap();
+  let application = Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .user("1234:1234")
+    .env(HashMap::from([("DATABASE_HOST".to_string(), "my-service.my-tenant.marathon.mesos".to_string())]))
+    .build()
+    .unwrap();
+  client.put_application_configuration("my-application", &application).await.unwrap();
+  server.insert(
+    "/allocation/my-tenant/appcatalogapp/my-service",
+    json!({ "manifestUrn": "appcatalog/manifest/kpn/my-service/0.0.1", "name": "my-service", "resources": {} }),
+  );
+  server.insert(
+    "/appcatalog/my-tenant/manifest/my-service",
+    json!({ "draft": false, "lastModified": 0.0, "payload": manifest_payload() }),
+  );
+  let databases = client.databases_with_dependants().await.unwrap();
+  assert_eq!(databases.len(), 1);
+  assert_eq!(databases[0].0, "my-service");
+  assert_eq!(databases[0].1.len(), 2);
+  assert!(matches!(&databases[0].1[0], Dependant::Application(application) if application.application_id == "my-application"));
+  assert!(matches!(&databases[0].1[1], Dependant::App(app) if app.app_id == "my-service"));
+  let (database_status, dependants) = client.database_with_dependants("my-service").await.unwrap();
+  assert_eq!(database_status.configuration.unwrap().mem, 4096);
+  assert_eq!(dependants.len(), 2);
+  assert!(client.database_diff("my-service").await.unwrap().unwrap().is_empty());
+  let summaries = client.database_status_summaries().await.unwrap();
+  assert!(summaries[0].is_healthy());
+}
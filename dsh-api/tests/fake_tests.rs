@@ -0,0 +1,122 @@
+use dsh_api::api::{ApplicationApi, DshApi, SecretApi, TopicApi, VolumeApi};
+use dsh_api::fake::{FakeDshApiClient, FakeResourceKind};
+use dsh_api::secret::SecretInjection;
+use dsh_api::types::{AllocationStatus, Application, ApplicationSecret, ApplicationVolumes, Secret, Topic, Volume};
+use dsh_api::{Dependant, DshApiError};
+use std::collections::HashMap;
+
+fn application(secret_id: &str) -> Application {
+  Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .user("1234:1234")
+    .instances(2)
+    .secrets(vec![ApplicationSecret {
+      name: secret_id.to_string(),
+      injections: vec![HashMap::from([("env".to_string(), "SECRET".to_string())])],
+    }])
+    .volumes(HashMap::from([(
+      "/data".to_string(),
+      ApplicationVolumes { name: "volume('my-volume')".to_string() },
+    )]))
+    .env_entry("TOPIC", "scratch.topic-1.my-tenant")
+    .build()
+    .unwrap()
+}
+
+// Generic code under test, that only depends on the traits
+async fn unused_secrets(api: &impl DshApi) -> Vec<String> {
+  api
+    .secrets_with_dependants()
+    .await
+    .unwrap()
+    .into_iter()
+    .filter(|(_, dependants)| dependants.is_empty())
+    .map(|(secret_id, _)| secret_id)
+    .collect()
+}
+
+#[tokio::test]
+async fn test_fake_create_update_delete() {
+  let fake = FakeDshApiClient::new();
+  let topic = Topic::builder().partitions(1).replication_factor(1).build().unwrap();
+  fake.put_topic_configuration("topic-1", &topic).await.unwrap();
+  assert_eq!(fake.get_topic_ids().await.unwrap(), vec!["topic-1".to_string()]);
+  assert_eq!(fake.get_topic_configuration("topic-1").await.unwrap(), topic);
+  assert!(fake.get_topic_status("topic-1").await.unwrap().provisioned);
+  let updated_topic = Topic::builder().partitions(3).replication_factor(1).build().unwrap();
+  fake.put_topic_configuration("topic-1", &updated_topic).await.unwrap();
+  assert_eq!(fake.get_topic_configuration("topic-1").await.unwrap().partitions, 3);
+  fake.delete_topic_configuration("topic-1").await.unwrap();
+  assert!(fake.get_topic_ids().await.unwrap().is_empty());
+  assert!(matches!(fake.get_topic_configuration("topic-1").await, Err(DshApiError::NotFound(_))));
+  assert!(matches!(fake.get_topic_status("topic-1").await, Err(DshApiError::NotFound(_))));
+  assert!(matches!(fake.delete_topic_configuration("topic-1").await, Err(DshApiError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_fake_secrets() {
+  let fake = FakeDshApiClient::new();
+  fake.post_secret(&Secret::new("my-secret", "value")).await.unwrap();
+  assert!(matches!(
+    fake.post_secret(&Secret::new("my-secret", "other")).await,
+    Err(DshApiError::BadRequest(_))
+  ));
+  fake.put_secret("my-secret", "new-value".to_string()).await.unwrap();
+  assert_eq!(fake.get_secret("my-secret").await.unwrap(), "new-value");
+  assert!(matches!(fake.put_secret("other-secret", "value".to_string()).await, Err(DshApiError::NotFound(_))));
+  fake.delete_secret_configuration("my-secret").await.unwrap();
+  assert!(matches!(fake.get_secret("my-secret").await, Err(DshApiError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_fake_set_status() {
+  let fake = FakeDshApiClient::new();
+  fake.put_volume_configuration("my-volume", &Volume::new(1)).await.unwrap();
+  let status = AllocationStatus { derived_from: None, notifications: vec![], provisioned: false };
+  fake.set_status(FakeResourceKind::Volume, "my-volume", status.clone()).unwrap();
+  assert_eq!(fake.get_volume_status("my-volume").await.unwrap(), status);
+  assert!(matches!(
+    fake.set_status(FakeResourceKind::Volume, "other-volume", status.clone()),
+    Err(DshApiError::NotFound(_))
+  ));
+  assert!(matches!(
+    fake.set_status(FakeResourceKind::Topic, "my-volume", status),
+    Err(DshApiError::NotFound(_))
+  ));
+}
+
+#[tokio::test]
+async fn test_fake_derived_methods() {
+  let fake = FakeDshApiClient::new();
+  fake.post_secret(&Secret::new("my-secret", "value")).await.unwrap();
+  fake.post_secret(&Secret::new("unused-secret", "value")).await.unwrap();
+  fake.put_volume_configuration("my-volume", &Volume::new(1)).await.unwrap();
+  fake
+    .put_topic_configuration("topic-1", &Topic::builder().partitions(1).replication_factor(1).build().unwrap())
+    .await
+    .unwrap();
+  fake.put_application_configuration("my-application", &application("my-secret")).await.unwrap();
+
+  assert_eq!(fake.application_ids().await.unwrap(), vec!["my-application".to_string()]);
+  let dependant_applications = fake.applications_dependant_on_secret("my-secret").await.unwrap();
+  assert_eq!(dependant_applications.len(), 1);
+  assert_eq!(dependant_applications[0].application_id, "my-application");
+  assert_eq!(dependant_applications[0].instances, 2);
+  assert_eq!(dependant_applications[0].injections, vec![SecretInjection::EnvVar("SECRET".to_string())]);
+  assert!(fake.applications_dependant_on_secret("unused-secret").await.unwrap().is_empty());
+  assert_eq!(fake.applications_dependant_on_volume("my-volume").await.unwrap().len(), 1);
+  assert!(fake.apps_dependant_on_secret("my-secret").await.unwrap().is_empty());
+  assert_eq!(unused_secrets(&fake).await, vec!["unused-secret".to_string()]);
+
+  let topics = fake.topics_with_dependants().await.unwrap();
+  assert_eq!(topics.len(), 1);
+  assert!(matches!(topics[0].1.as_slice(), [Dependant::Application(_)]));
+  let volumes = fake.volumes_with_dependants().await.unwrap();
+  assert_eq!(volumes.len(), 1);
+  assert_eq!(volumes[0].1.len(), 1);
+
+  fake.delete_application_configuration("my-application").await.unwrap();
+  assert_eq!(unused_secrets(&fake).await, vec!["my-secret".to_string(), "unused-secret".to_string()]);
+}
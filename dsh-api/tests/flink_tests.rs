@@ -0,0 +1,84 @@
+use crate::common::mock_client;
+use dsh_api::flink::{flink_cluster_cpus, flink_cluster_mem, flink_cluster_violations, FlinkClusterDiff};
+use dsh_api::mock_server::MockServer;
+use dsh_api::types::{Application, FlinkCluster, FlinkJobManager, FlinkTaskManager};
+use dsh_api::DshApiError;
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+
+mod common;
+
+fn flink_cluster(instances: u64) -> FlinkCluster {
+  FlinkCluster {
+    job_manager: Some(FlinkJobManager { cpus: 0.5, mem: 1024 }),
+    task_manager: Some(FlinkTaskManager { cpus: 1.0, instances: NonZeroU64::new(instances).unwrap(), mem: 2048 }),
+    version: "1.17".to_string(),
+    zone: "internal".to_string(),
+  }
+}
+
+fn application() -> Application {
+  Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(1.0)
+    .mem(1024)
+    .instances(2_u64)
+    .user("1234:1234")
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_flink_cluster_resources() {
+  assert_eq!(flink_cluster_cpus(&flink_cluster(3)), 3.5);
+  assert_eq!(flink_cluster_mem(&flink_cluster(3)), 7168);
+  let empty = FlinkCluster { job_manager: None, task_manager: None, version: "1.17".to_string(), zone: "internal".to_string() };
+  assert_eq!(flink_cluster_cpus(&empty), 0.0);
+  assert_eq!(flink_cluster_mem(&empty), 0);
+}
+
+#[test]
+fn test_flink_cluster_diff() {
+  assert!(FlinkClusterDiff::differences_between_flink_clusters(&flink_cluster(2), &flink_cluster(2)).is_empty());
+  let diff = FlinkClusterDiff::differences_between_flink_clusters(&flink_cluster(2), &flink_cluster(3));
+  assert!(!diff.is_empty());
+  assert!(diff.job_manager.is_none());
+  assert_eq!(diff.differences().len(), 1);
+  assert_eq!(diff.differences()[0].0, "task manager");
+}
+
+#[test]
+fn test_flink_cluster_violations() {
+  let applications = HashMap::from([("my-application".to_string(), application())]);
+  assert!(flink_cluster_violations(&flink_cluster(2), &applications, 5.0, 8192).unwrap().is_empty());
+  let violations = flink_cluster_violations(&flink_cluster(3), &applications, 5.0, 8192).unwrap();
+  assert_eq!(violations.len(), 2);
+  let too_small = FlinkCluster { job_manager: Some(FlinkJobManager { cpus: 0.1, mem: 1024 }), ..flink_cluster(1) };
+  assert_eq!(flink_cluster_violations(&too_small, &HashMap::new(), 5.0, 8192).unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_flink_cluster() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  assert!(client.flink_cluster().await.unwrap().is_none());
+  assert!(client.flink_cluster_diff().await.unwrap().is_none());
+  client.put_flinkcluster_configuration(&flink_cluster(2)).await.unwrap();
+  let (cluster, _status) = client.flink_cluster().await.unwrap().unwrap();
+  assert_eq!(cluster, flink_cluster(2));
+  assert!(client.flink_cluster_diff().await.unwrap().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_deploy_flink_cluster() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  client.put_application_configuration("my-application", &application()).await.unwrap();
+  assert!(matches!(
+    client.deploy_flink_cluster(&flink_cluster(3), 5.0, 8192).await,
+    Err(DshApiError::Parameter(_))
+  ));
+  assert!(server.resource("/allocation/my-tenant/flinkcluster").is_none());
+  client.deploy_flink_cluster(&flink_cluster(2), 5.0, 8192).await.unwrap();
+  assert_eq!(client.get_flinkcluster_configuration().await.unwrap(), flink_cluster(2));
+}
@@ -0,0 +1,35 @@
+use dsh_api::types::{BucketAccess, DataCatalogAsset, FlinkCluster, KafkaProxy, KafkaProxyZone};
+use std::num::NonZeroU64;
+
+#[test]
+fn test_flink_cluster_new() {
+  let flink_cluster = FlinkCluster::new("1.20", "internal");
+  assert_eq!(flink_cluster.version, "1.20");
+  assert_eq!(flink_cluster.zone, "internal");
+  assert_eq!(flink_cluster.job_manager, None);
+  assert_eq!(flink_cluster.task_manager, None);
+}
+
+#[test]
+fn test_kafka_proxy_new() {
+  let kafka_proxy = KafkaProxy::new("certificate", 1.0, NonZeroU64::new(2).unwrap(), 512, "ca-chain", KafkaProxyZone::Private);
+  assert_eq!(kafka_proxy.certificate, "certificate");
+  assert_eq!(kafka_proxy.instances.get(), 2);
+  assert_eq!(kafka_proxy.zone, KafkaProxyZone::Private);
+  assert_eq!(kafka_proxy.name, None);
+}
+
+#[test]
+fn test_bucket_access_default() {
+  let bucket_access = BucketAccess::default();
+  assert_eq!(bucket_access.bucket, "");
+  assert!(!bucket_access.readable);
+  assert!(!bucket_access.writable);
+}
+
+#[test]
+fn test_data_catalog_asset_display() {
+  let data_catalog_asset = DataCatalogAsset::new("bucket", "my-bucket");
+  assert_eq!(data_catalog_asset.to_string(), "kind: bucket, name: my-bucket");
+  assert_eq!(format!("{:#}", data_catalog_asset), "kind: bucket\nname: my-bucket");
+}
@@ -0,0 +1,46 @@
+use dsh_api::id::{ApplicationId, BucketId, SecretId, TopicId, VhostId, VolumeId};
+use dsh_api::DshApiError;
+use std::str::FromStr;
+
+#[test]
+fn test_valid_ids() {
+  for id in ["a", "my-application", "application-1", "a1-b2-c3"] {
+    assert_eq!(ApplicationId::new(id).unwrap().as_str(), id);
+    assert_eq!(TopicId::from_str(id).unwrap().to_string(), id);
+    assert_eq!(VhostId::try_from(id).unwrap(), id);
+    assert_eq!(VolumeId::try_from(id.to_string()).unwrap().as_ref(), id);
+  }
+  assert!(SecretId::new("system!my-secret").is_ok());
+  assert!(BucketId::new("my-bucket").is_ok());
+}
+
+#[test]
+fn test_invalid_ids() {
+  for id in ["", "-", "1-topic", "my-topic-", "My-Topic", "my_topic", "my.topic", "my topic", "my!topic"] {
+    assert!(matches!(TopicId::new(id), Err(DshApiError::Parameter(_))), "{}", id);
+  }
+  assert!(SecretId::new("my!secret!").is_err());
+  assert!(BucketId::new("ab").is_err());
+  assert!(ApplicationId::new("a".repeat(64)).is_err());
+  assert!(TopicId::new("a".repeat(100)).is_ok());
+  assert!(TopicId::new("a".repeat(101)).is_err());
+}
+
+#[test]
+fn test_serde() {
+  let topic_id = TopicId::new("my-topic").unwrap();
+  assert_eq!(serde_json::to_string(&topic_id).unwrap(), r#""my-topic""#);
+  assert_eq!(serde_json::from_str::<TopicId>(r#""my-topic""#).unwrap(), topic_id);
+  assert!(serde_json::from_str::<TopicId>(r#""My-Topic""#).is_err());
+}
+
+#[test]
+fn test_as_ref_str() {
+  fn length(id: impl AsRef<str>) -> usize {
+    id.as_ref().len()
+  }
+  let secret_id = SecretId::new("my-secret").unwrap();
+  assert_eq!(length(&secret_id), 9);
+  assert_eq!(length("my-secret"), 9);
+  assert_eq!(length(String::from("my-secret")), 9);
+}
@@ -0,0 +1,123 @@
+use crate::common::mock_client;
+use dsh_api::kafka_proxy::kafka_proxy_vhosts_from_applications;
+use dsh_api::mock_server::MockServer;
+use dsh_api::platform::DshPlatform;
+use dsh_api::types::{Application, KafkaProxy, KafkaProxyZone, PortMapping};
+use dsh_api::vhost::VhostInjection;
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+
+mod common;
+
+fn kafka_proxy(schema_store: bool) -> KafkaProxy {
+  KafkaProxy::builder()
+    .certificate("my-certificate")
+    .cpus(1.0)
+    .instances(NonZeroU64::new(3).unwrap())
+    .mem(2048)
+    .schema_store(schema_store)
+    .secret_name_ca_chain("my-ca-chain")
+    .zone(KafkaProxyZone::Public)
+    .build()
+    .unwrap()
+}
+
+fn application(vhost: &str) -> Application {
+  Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .instances(2_u64)
+    .user("1234:1234")
+    .exposed_ports_entry("9091", PortMapping { vhost: Some(vhost.to_string()), ..PortMapping::default() })
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_kafka_proxy_vhosts_from_applications() {
+  let applications = HashMap::from([
+    ("application-1".to_string(), application("{ vhost('my-proxy.kafka.my-tenant','public') }")),
+    ("application-2".to_string(), application("{ vhost('my-proxy.my-tenant','public') }")),
+    ("application-3".to_string(), application("{ vhost('other-proxy.kafka.my-tenant','private') }")),
+    ("application-4".to_string(), application("{ vhost('my-proxy.kafka.other-tenant','public') }")),
+  ]);
+  let vhosts = kafka_proxy_vhosts_from_applications("my-proxy", "my-tenant", &applications);
+  assert_eq!(vhosts.len(), 1);
+  assert_eq!(vhosts[0].id, "application-1");
+  assert_eq!(vhosts[0].values[0].0, "9091");
+  assert_eq!(vhosts[0].values[0].1.zone, Some("public".to_string()));
+  assert!(kafka_proxy_vhosts_from_applications("unused-proxy", "my-tenant", &applications).is_empty());
+  assert_eq!(
+    kafka_proxy_vhosts_from_applications("my-proxy", "other-tenant", &applications)[0].id,
+    "application-4"
+  );
+}
+
+#[tokio::test]
+async fn test_kafka_proxies_with_dependant_applications() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  client.put_kafkaproxy_configuration("my-proxy", &kafka_proxy(false)).await.unwrap();
+  client.put_kafkaproxy_configuration("unused-proxy", &kafka_proxy(false)).await.unwrap();
+  client
+    .put_application_configuration("my-application", &application("{ vhost('my-proxy.kafka.my-tenant','public') }"))
+    .await
+    .unwrap();
+  let kafka_proxies = client.kafka_proxies().await.unwrap();
+  assert_eq!(
+    kafka_proxies.iter().map(|(id, _)| id.as_str()).collect::<Vec<_>>(),
+    vec!["my-proxy", "unused-proxy"]
+  );
+  let dependants = client.kafka_proxies_with_dependant_applications().await.unwrap();
+  assert_eq!(dependants.len(), 2);
+  assert_eq!(dependants[0].1.len(), 1);
+  assert_eq!(dependants[0].1[0].application_id, "my-application");
+  assert_eq!(dependants[0].1[0].instances, 2);
+  assert_eq!(
+    dependants[0].1[0].injections,
+    vec![VhostInjection::Vhost("9091".to_string(), Some("public".to_string()))]
+  );
+  assert!(dependants[1].1.is_empty());
+}
+
+#[cfg(not(dsh_api_1_8))]
+#[tokio::test]
+async fn test_kafka_proxies_with_status() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  client.put_kafkaproxy_configuration("my-proxy", &kafka_proxy(false)).await.unwrap();
+  let kafka_proxies = client.kafka_proxies_with_status().await.unwrap();
+  assert_eq!(kafka_proxies.len(), 1);
+  assert_eq!(kafka_proxies[0].0, "my-proxy");
+  assert!(kafka_proxies[0].2.provisioned);
+}
+
+#[tokio::test]
+async fn test_kafka_proxy_client_configuration() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  client.put_kafkaproxy_configuration("my-proxy", &kafka_proxy(true)).await.unwrap();
+  let configuration = client.kafka_proxy_client_configuration("my-proxy", KafkaProxyZone::Public).await.unwrap();
+  assert_eq!(
+    configuration.bootstrap_servers,
+    DshPlatform::new("nplz").tenant_proxy_public_bootstrap_servers("my-tenant", "my-proxy")
+  );
+  assert_eq!(configuration.ca_chain_secret, "my-ca-chain");
+  let properties = configuration.properties();
+  assert!(properties.contains(&("security.protocol".to_string(), "SSL".to_string())));
+  assert!(properties.contains(&("ssl.keystore.location".to_string(), "client.pem".to_string())));
+  assert!(properties.contains(&("ssl.truststore.location".to_string(), "truststore.pem".to_string())));
+  let properties = configuration.clone().with_key_material("/etc/kafka/client.pem", "/etc/kafka/ca.pem").properties();
+  assert!(properties.contains(&("ssl.keystore.location".to_string(), "/etc/kafka/client.pem".to_string())));
+  assert!(properties.contains(&("ssl.truststore.location".to_string(), "/etc/kafka/ca.pem".to_string())));
+  assert!(properties.contains(&(
+    "schema.registry.url".to_string(),
+    "https://my-proxy-schema-store.kafka.my-tenant.dsh-dev.dsh.np.aws.kpn.com:9091".to_string()
+  )));
+  assert!(configuration.to_string().contains("bootstrap.servers=my-proxy-0.kafka.my-tenant"));
+  client.put_kafkaproxy_configuration("my-proxy", &kafka_proxy(false)).await.unwrap();
+  let configuration = client.kafka_proxy_client_configuration("my-proxy", KafkaProxyZone::Private).await.unwrap();
+  assert!(configuration.schema_store_host.is_none());
+  assert!(configuration.bootstrap_servers[0].ends_with(".kafka.my-tenant.dsh-dev.dsh.np.aws.kpn.org:9091"));
+}
@@ -0,0 +1,127 @@
+use crate::common::mock_client;
+use dsh_api::id::TopicId;
+use dsh_api::mock_server::MockServer;
+use dsh_api::types::{Application, Secret, Topic, Volume};
+use dsh_api::DshApiError;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+mod common;
+
+fn topic() -> Topic {
+  Topic::builder()
+    .partitions(3)
+    .replication_factor(3)
+    .kafka_properties_entry("cleanup.policy", "delete")
+    .build()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_mock_server_put_get_delete() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  assert!(matches!(client.get_topic_configuration("my-topic").await, Err(DshApiError::NotFound(_))));
+  client.put_topic_configuration("my-topic", &topic()).await.unwrap();
+  assert_eq!(client.get_topic_configuration("my-topic").await.unwrap(), topic());
+  assert_eq!(client.get_topic_actual("my-topic").await.unwrap(), topic());
+  assert!(client.get_topic_status("my-topic").await.unwrap().provisioned);
+  let topic_status = client.get_topic("my-topic").await.unwrap();
+  assert_eq!(topic_status.configuration, Some(topic()));
+  assert_eq!(topic_status.actual, Some(topic()));
+  assert_eq!(client.get_topic_ids().await.unwrap(), vec!["my-topic".to_string()]);
+  client.delete_topic_configuration("my-topic").await.unwrap();
+  assert!(client.get_topic_ids().await.unwrap().is_empty());
+  assert!(client.delete_topic_configuration("my-topic").await.is_err());
+}
+
+#[tokio::test]
+async fn test_mock_server_typed_ids() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  let topic_id = TopicId::new("my-topic").unwrap();
+  client.put_topic_configuration(&topic_id, &topic()).await.unwrap();
+  assert_eq!(client.get_topic_configuration(&topic_id).await.unwrap(), topic());
+  assert_eq!(client.get_topic_configuration("my-topic").await.unwrap(), topic());
+  assert_eq!(client.get_topic_configuration(String::from("my-topic")).await.unwrap(), topic());
+  assert!(client.topic_dependants(&topic_id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_mock_server_configuration_map() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  let application = Application::builder().image("my-image:0.0.1").cpus(0.5).mem(512).user("1234:1234").build().unwrap();
+  client.put_application_configuration("app-1", &application).await.unwrap();
+  client.put_application_configuration("app-2", &application).await.unwrap();
+  let applications = client.get_application_configuration_map().await.unwrap();
+  assert_eq!(applications.len(), 2);
+  assert_eq!(applications.get("app-1"), Some(&application));
+  assert_eq!(client.get_application_actual_map().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_mock_server_secret() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  client.post_secret(&Secret::new("my-secret", "value-1")).await.unwrap();
+  assert_eq!(client.get_secret_ids().await.unwrap(), vec!["my-secret".to_string()]);
+  assert_eq!(client.get_secret("my-secret").await.unwrap(), "value-1");
+  client.put_secret("my-secret", "value-2".to_string()).await.unwrap();
+  assert_eq!(client.get_secret("my-secret").await.unwrap(), "value-2");
+}
+
+#[tokio::test]
+async fn test_mock_server_percent_encoded_path() {
+  let server = MockServer::start().unwrap();
+  server.insert("/allocation/my-tenant/volume/my volume/configuration", json!({ "sizeGiB": 5 }));
+  let client = mock_client(&server).await;
+  assert_eq!(client.get_volume_configuration("my volume").await.unwrap(), Volume::new(5));
+  assert_eq!(client.get_volume("my volume").await.unwrap().configuration, Some(Volume::new(5)));
+  client.post_secret(&Secret::new("my secret!", "value")).await.unwrap();
+  assert_eq!(client.get_secret("my secret!").await.unwrap(), "value");
+  assert!(server.requests().contains(&"GET /allocation/my-tenant/volume/my volume".to_string()));
+}
+
+#[tokio::test]
+async fn test_mock_server_insert_and_requests() {
+  let server = MockServer::start().unwrap();
+  server.insert("/allocation/my-tenant/volume/my-volume/configuration", json!({ "sizeGiB": 5 }));
+  let client = mock_client(&server).await;
+  assert_eq!(client.get_volume_configuration("my-volume").await.unwrap(), Volume::new(5));
+  client.put_volume_configuration("other-volume", &Volume::new(7)).await.unwrap();
+  assert_eq!(server.resource("/allocation/my-tenant/volume/other-volume"), Some(json!({ "sizeGiB": 7 })));
+  assert_eq!(
+    server.requests(),
+    vec!["GET /allocation/my-tenant/volume/my-volume/configuration".to_string(), "PUT /allocation/my-tenant/volume/other-volume/configuration".to_string()]
+  );
+}
+
+#[tokio::test]
+async fn test_mock_server_invalid_request_body() {
+  let server = MockServer::start().unwrap();
+  let response = reqwest::Client::new()
+    .put(format!("{}/allocation/my-tenant/volume/my-volume/configuration", server.url()))
+    .header("content-type", "application/json")
+    .body(r#"{ "sizeGiB": "large" }"#)
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status().as_u16(), 400);
+  assert_eq!(response.text().await.unwrap(), "$.sizeGiB: expected integer, found string");
+  assert!(server.resource_paths().is_empty());
+}
+
+#[tokio::test]
+async fn test_mock_server_inject_error_and_latency() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  server.inject_error("PUT", "/allocation/{tenant}/volume/{id}/configuration", 500);
+  assert!(client.put_volume_configuration("my-volume", &Volume::new(1)).await.is_err());
+  assert!(server.resource("/allocation/my-tenant/volume/my-volume").is_none());
+  server.clear_injections();
+  server.inject_latency("GET", "/allocation/{tenant}/volume", Duration::from_millis(200));
+  let start = Instant::now();
+  assert!(client.get_volume_ids().await.unwrap().is_empty());
+  assert!(start.elapsed() >= Duration::from_millis(200));
+}
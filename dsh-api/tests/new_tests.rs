@@ -1,4 +1,6 @@
-use dsh_api::types::{ApplicationSecret, ManagedStreamId, ManagedTenant, ManagedTenantServices, ManagedTenantServicesName};
+#[cfg(not(dsh_api_1_8))]
+use dsh_api::types::ManagedStreamId;
+use dsh_api::types::{ApplicationSecret, ManagedTenant, ManagedTenantServices, ManagedTenantServicesName};
 
 #[test]
 fn test_application_secret_new() {
@@ -10,18 +12,21 @@ fn test_application_secret_new() {
 }
 
 #[test]
+#[cfg(not(dsh_api_1_8))]
 fn test_managed_stream_id_new() {
   let managed_stream_id = ManagedStreamId::new("manager", "stream-id");
   assert_eq!(*managed_stream_id, "manager---stream-id");
 }
 
 #[test]
+#[cfg(not(dsh_api_1_8))]
 #[should_panic]
 fn test_managed_stream_id_new_panic_on_empty_manager() {
   ManagedStreamId::new("", "stream-id");
 }
 
 #[test]
+#[cfg(not(dsh_api_1_8))]
 #[should_panic(expected = "manager---stream_id is not a valid managed stream id")]
 fn test_managed_stream_id_new_panic_on_underscore() {
   ManagedStreamId::new("manager", "stream_id");
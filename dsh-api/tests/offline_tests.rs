@@ -0,0 +1,111 @@
+use crate::common::mock_client;
+use dsh_api::dsh_api_client::DshApiClient;
+use dsh_api::dsh_api_tenant::DshApiTenant;
+use dsh_api::mock_server::MockServer;
+use dsh_api::offline::DshApiOfflineClient;
+use dsh_api::platform::DshPlatform;
+use dsh_api::types::{Application, ApplicationSecret, Secret, Topic, Volume};
+use dsh_api::DshApiError;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+mod common;
+
+fn tenant() -> DshApiTenant {
+  DshApiTenant::new("my-tenant".to_string(), DshPlatform::new("nplz"))
+}
+
+fn temporary_directory(name: &str) -> PathBuf {
+  let directory = std::env::temp_dir().join(format!("dsh-api-offline-{}-{}", name, std::process::id()));
+  let _ = std::fs::remove_dir_all(&directory);
+  std::fs::create_dir_all(&directory).unwrap();
+  directory
+}
+
+async fn populated_client(server: &MockServer) -> DshApiClient {
+  let client = mock_client(server).await;
+  let topic = Topic::builder().partitions(1).replication_factor(1).build().unwrap();
+  client.put_topic_configuration("topic-1", &topic).await.unwrap();
+  client.put_volume_configuration("my-volume", &Volume::new(1)).await.unwrap();
+  client.post_secret(&Secret::new("my-secret", "value")).await.unwrap();
+  let application = Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .user("1234:1234")
+    .secrets(vec![ApplicationSecret {
+      name: "my-secret".to_string(),
+      injections: vec![HashMap::from([("env".to_string(), "SECRET".to_string())])],
+    }])
+    .env_entry("TOPIC", "scratch.topic-1.my-tenant")
+    .build()
+    .unwrap();
+  client.put_application_configuration("my-application", &application).await.unwrap();
+  client
+}
+
+#[tokio::test]
+async fn test_offline_client_from_info_directory() {
+  let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../info");
+  let expected_applications: HashMap<String, Application> = serde_json::from_str(&std::fs::read_to_string(directory.join("application-configuration-map.json")).unwrap()).unwrap();
+  let expected_manifests: Vec<serde_json::Value> = serde_json::from_str(&std::fs::read_to_string(directory.join("manifests.json")).unwrap()).unwrap();
+  let client = DshApiOfflineClient::from_directory(&directory, tenant()).await.unwrap();
+  assert_eq!(client.get_application_configuration_map().await.unwrap(), expected_applications);
+  assert_eq!(client.application_ids().await.unwrap().len(), expected_applications.len());
+  assert_eq!(client.get_appcatalog_manifests().await.unwrap().len(), expected_manifests.len());
+  assert!(!client.manifest_ids().await.unwrap().is_empty());
+  assert!(client.get_topic_ids().await.unwrap().is_empty());
+  assert!(client.app_ids().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_offline_client_is_read_only() {
+  let directory = temporary_directory("read-only");
+  let client = DshApiOfflineClient::from_directory(&directory, tenant()).await.unwrap();
+  assert!(matches!(
+    client.put_volume_configuration("my-volume", &Volume::new(1)).await,
+    Err(DshApiError::NotAuthorized(_))
+  ));
+  assert!(matches!(client.delete_topic_configuration("topic-1").await, Err(DshApiError::NotAuthorized(_))));
+  assert!(client.get_volume_ids().await.unwrap().is_empty());
+  std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[tokio::test]
+async fn test_export_to_directory_round_trip() {
+  let server = MockServer::start().unwrap();
+  let client = populated_client(&server).await;
+  let directory = temporary_directory("round-trip");
+  client.export_to_directory(&directory, 2).await.unwrap();
+  let offline_client = DshApiOfflineClient::from_directory(&directory, tenant()).await.unwrap();
+  assert_eq!(
+    offline_client.get_application_configuration_map().await.unwrap(),
+    client.get_application_configuration_map().await.unwrap()
+  );
+  assert_eq!(offline_client.get_topic_ids().await.unwrap(), vec!["topic-1".to_string()]);
+  assert_eq!(
+    offline_client.get_topic_configuration("topic-1").await.unwrap(),
+    client.get_topic_configuration("topic-1").await.unwrap()
+  );
+  assert_eq!(offline_client.get_volume_actual("my-volume").await.unwrap().size_gi_b, 1);
+  assert_eq!(offline_client.get_secret_ids().await.unwrap(), vec!["my-secret".to_string()]);
+  assert!(matches!(offline_client.get_secret("my-secret").await, Err(DshApiError::NotFound(_))));
+  let dependants = offline_client.secrets_with_dependants().await.unwrap();
+  assert_eq!(dependants.len(), 1);
+  assert_eq!(dependants[0].1.len(), 1);
+  let topics = offline_client.topics_with_dependants().await.unwrap();
+  assert_eq!(topics.len(), 1);
+  assert_eq!(topics[0].1.len(), 1);
+  std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[tokio::test]
+async fn test_offline_client_invalid_file() {
+  let directory = temporary_directory("invalid");
+  std::fs::write(directory.join("topic-configuration-map.json"), "[]").unwrap();
+  assert!(matches!(
+    DshApiOfflineClient::from_directory(&directory, tenant()).await,
+    Err(DshApiError::Configuration(_))
+  ));
+  std::fs::remove_dir_all(directory).unwrap();
+}
@@ -0,0 +1,62 @@
+use crate::common::mock_client;
+use dsh_api::dsh_api_client::DshApiClient;
+use dsh_api::mock_server::MockServer;
+use dsh_api::resource::DshResource;
+use dsh_api::topic::TopicInjection;
+use dsh_api::types::{Application, Topic, Vhost, Volume};
+use dsh_api::{Dependant, DshApiError};
+
+mod common;
+
+async fn kind_and_ids<R: DshResource>(client: &DshApiClient) -> (&'static str, Vec<String>) {
+  (R::KIND, R::list_ids(client).await.unwrap())
+}
+
+#[tokio::test]
+async fn test_resource_generic() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  let topic = Topic::builder().partitions(3).replication_factor(3).build().unwrap();
+  client.put_topic_configuration("my-topic", &topic).await.unwrap();
+  client.put_volume_configuration("my-volume", &Volume::new(5)).await.unwrap();
+  assert_eq!(kind_and_ids::<Topic>(&client).await, ("topic", vec!["my-topic".to_string()]));
+  assert_eq!(kind_and_ids::<Volume>(&client).await, ("volume", vec!["my-volume".to_string()]));
+  assert_eq!(Topic::configuration(&client, "my-topic").await.unwrap(), topic);
+  assert_eq!(Topic::actual(&client, "my-topic").await.unwrap(), topic);
+  assert!(Topic::status(&client, "my-topic").await.unwrap().provisioned);
+  assert_eq!(Volume::configuration(&client, "my-volume").await.unwrap(), Volume::new(5));
+  Volume::delete(&client, "my-volume").await.unwrap();
+  assert!(Volume::list_ids(&client).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_resource_dependants() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  let application = Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .user("1234:1234")
+    .env_entry("TOPIC", "scratch.my-topic.my-tenant")
+    .build()
+    .unwrap();
+  client.put_application_configuration("my-application", &application).await.unwrap();
+  let dependants = Topic::dependants(&client, "my-topic").await.unwrap();
+  assert_eq!(
+    dependants,
+    vec![Dependant::application("my-application".to_string(), 1, vec![TopicInjection::EnvVar("TOPIC".to_string())])]
+  );
+  assert!(Topic::dependants(&client, "other-topic").await.unwrap().is_empty());
+  assert_eq!(Application::list_ids(&client).await.unwrap(), vec!["my-application".to_string()]);
+}
+
+#[tokio::test]
+async fn test_resource_vhost_not_supported() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  assert!(Vhost::list_ids(&client).await.unwrap().is_empty());
+  assert!(Vhost::dependants(&client, "my-vhost").await.unwrap().is_empty());
+  assert!(matches!(Vhost::configuration(&client, "my-vhost").await, Err(DshApiError::Configuration(_))));
+  assert!(matches!(Vhost::delete(&client, "my-vhost").await, Err(DshApiError::Configuration(_))));
+}
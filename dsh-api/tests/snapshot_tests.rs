@@ -0,0 +1,130 @@
+use crate::common::mock_client;
+use dsh_api::dsh_api_client::DshApiClient;
+use dsh_api::mock_server::MockServer;
+use dsh_api::snapshot::TenantSnapshot;
+use dsh_api::types::{Application, ApplicationSecret, Database, KafkaProxy, KafkaProxyZone, Secret, Topic, Volume};
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+use std::time::{Duration, Instant};
+
+mod common;
+
+async fn populated_client(server: &MockServer) -> DshApiClient {
+  let client = mock_client(server).await;
+  let topic = Topic::builder().partitions(1).replication_factor(1).build().unwrap();
+  client.put_topic_configuration("topic-1", &topic).await.unwrap();
+  client.put_topic_configuration("topic-2", &topic).await.unwrap();
+  client.put_volume_configuration("my-volume", &Volume::new(1)).await.unwrap();
+  client.post_secret(&Secret::new("my-secret", "value")).await.unwrap();
+  let database = Database { cpus: 1.0, extensions: vec![], instances: 3, mem: 4096, snapshot_interval: None, version: Some("16".to_string()), volume_size: 10 };
+  client.put_database_configuration("my-database", &database).await.unwrap();
+  let kafka_proxy = KafkaProxy::builder()
+    .certificate("my-certificate")
+    .cpus(1.0)
+    .instances(NonZeroU64::new(3).unwrap())
+    .mem(2048)
+    .schema_store(false)
+    .secret_name_ca_chain("my-ca-chain")
+    .zone(KafkaProxyZone::Public)
+    .build()
+    .unwrap();
+  client.put_kafkaproxy_configuration("my-proxy", &kafka_proxy).await.unwrap();
+  let application = Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .user("1234:1234")
+    .secrets(vec![ApplicationSecret {
+      name: "my-secret".to_string(),
+      injections: vec![HashMap::from([("env".to_string(), "SECRET".to_string())])],
+    }])
+    .env_entry("TOPIC", "scratch.topic-1.my-tenant")
+    .env_entry("DATABASE_HOST", "my-database.my-tenant.marathon.mesos")
+    .build()
+    .unwrap();
+  client.put_application_configuration("my-application", &application).await.unwrap();
+  client
+}
+
+#[tokio::test]
+async fn test_tenant_snapshot() {
+  let server = MockServer::start().unwrap();
+  let client = populated_client(&server).await;
+  let snapshot = client.tenant_snapshot(4).await.unwrap();
+  assert_eq!(snapshot.tenant, "my-tenant");
+  assert_eq!(snapshot.application_ids(), vec!["my-application"]);
+  assert_eq!(snapshot.topic_ids(), vec!["topic-1", "topic-2"]);
+  assert_eq!(snapshot.volume_ids(), vec!["my-volume"]);
+  assert_eq!(snapshot.secret_ids(), vec!["my-secret"]);
+  assert_eq!(snapshot.database_ids(), vec!["my-database"]);
+  assert_eq!(snapshot.kafka_proxy_ids(), vec!["my-proxy"]);
+  assert!(snapshot.flink_cluster.is_none());
+  assert!(snapshot.manifests.is_empty());
+  assert!(snapshot.vhost_ids().is_empty());
+  assert!(snapshot.application_statuses.contains_key("my-application"));
+  assert_eq!(snapshot.topics["topic-1"].configuration.as_ref().unwrap().partitions, 1);
+}
+
+#[tokio::test]
+async fn test_tenant_snapshot_analyses() {
+  let server = MockServer::start().unwrap();
+  let client = populated_client(&server).await;
+  let snapshot = client.tenant_snapshot(4).await.unwrap();
+  let requests = server.requests().len();
+  let topics = snapshot.topics_with_dependants();
+  assert_eq!(
+    topics
+      .into_iter()
+      .map(|(topic_id, dependants)| (topic_id.to_string(), dependants))
+      .collect::<Vec<_>>(),
+    client.topics_with_dependants().await.unwrap()
+  );
+  let secrets = snapshot.secrets_with_dependants();
+  assert_eq!(
+    secrets
+      .into_iter()
+      .map(|(secret_id, dependants)| (secret_id.to_string(), dependants))
+      .collect::<Vec<_>>(),
+    client.secrets_with_dependants().await.unwrap()
+  );
+  let databases = snapshot.databases_with_dependants();
+  assert_eq!(
+    databases
+      .into_iter()
+      .map(|(database_id, _, dependants)| (database_id.to_string(), dependants))
+      .collect::<Vec<_>>(),
+    client.databases_with_dependants().await.unwrap()
+  );
+  assert_eq!(snapshot.databases_with_dependants()[0].2.len(), 1);
+  assert!(snapshot.kafka_proxies_with_dependant_applications()[0].2.is_empty());
+  assert_eq!(snapshot.secrets_with_dependants()[0].1.len(), 1);
+  assert_eq!(snapshot.secret_env_vars_from_applications("my-secret")[0].values, vec!["SECRET"]);
+  assert_eq!(snapshot.topic_env_vars_from_applications("topic-2").len(), 0);
+  assert!(snapshot.volumes_with_dependants()[0].2.is_empty());
+  assert!(snapshot.apps_that_use_topic("topic-1").is_empty());
+  assert_eq!(server.requests().len(), requests + 10);
+}
+
+#[tokio::test]
+async fn test_tenant_snapshot_serde() {
+  let server = MockServer::start().unwrap();
+  let client = populated_client(&server).await;
+  let snapshot = client.tenant_snapshot(4).await.unwrap();
+  let json = serde_json::to_string(&snapshot).unwrap();
+  let reloaded: TenantSnapshot = serde_json::from_str(&json).unwrap();
+  assert_eq!(reloaded, snapshot);
+  assert_eq!(reloaded.topics_with_dependants(), snapshot.topics_with_dependants());
+}
+
+#[tokio::test]
+async fn test_tenant_snapshot_bounded_concurrency() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  for volume_id in ["volume-1", "volume-2", "volume-3", "volume-4"] {
+    client.put_volume_configuration(volume_id, &Volume::new(1)).await.unwrap();
+  }
+  server.inject_latency("GET", "/allocation/{tenant}/volume/{id}", Duration::from_millis(200));
+  let start = Instant::now();
+  assert_eq!(client.tenant_snapshot(2).await.unwrap().volume_ids().len(), 4);
+  assert!(start.elapsed() >= Duration::from_millis(400));
+}
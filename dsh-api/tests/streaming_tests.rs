@@ -0,0 +1,107 @@
+use crate::common::mock_client;
+use dsh_api::mock_server::MockServer;
+use dsh_api::types::{Application, Topic, Volume};
+use dsh_api::DshApiError;
+use futures::{StreamExt, TryStreamExt};
+use std::time::{Duration, Instant};
+
+mod common;
+
+#[tokio::test]
+async fn test_configurations_stream() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  for (volume_id, size) in [("volume-1", 1), ("volume-2", 2), ("volume-3", 3)] {
+    client.put_volume_configuration(volume_id, &Volume::new(size)).await.unwrap();
+  }
+  let mut volumes: Vec<(String, Volume)> = client.configurations_stream::<Volume>(2).try_collect().await.unwrap();
+  volumes.sort_by(|(id_a, _), (id_b, _)| id_a.cmp(id_b));
+  assert_eq!(
+    volumes,
+    vec![("volume-1".to_string(), Volume::new(1)), ("volume-2".to_string(), Volume::new(2)), ("volume-3".to_string(), Volume::new(3))]
+  );
+}
+
+#[tokio::test]
+async fn test_configurations_stream_bounded_concurrency() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  for volume_id in ["volume-1", "volume-2", "volume-3", "volume-4"] {
+    client.put_volume_configuration(volume_id, &Volume::new(1)).await.unwrap();
+  }
+  server.inject_latency("GET", "/allocation/{tenant}/volume/{id}/configuration", Duration::from_millis(200));
+  let start = Instant::now();
+  assert_eq!(client.configurations_stream::<Volume>(2).count().await, 4);
+  assert!(start.elapsed() >= Duration::from_millis(400));
+}
+
+#[tokio::test]
+async fn test_configurations_stream_error() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  server.inject_error("GET", "/allocation/{tenant}/topic", 500);
+  let results = client.configurations_stream::<Topic>(4).collect::<Vec<_>>().await;
+  assert_eq!(results.len(), 1);
+  assert!(matches!(results[0], Err(DshApiError::Unexpected(_, _))));
+}
+
+#[tokio::test]
+async fn test_applications_stream() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  let application = Application::builder().image("my-image:0.0.1").cpus(0.5).mem(512).user("1234:1234").build().unwrap();
+  for application_id in ["app-b", "app-c", "app-a"] {
+    client.put_application_configuration(application_id, &application).await.unwrap();
+  }
+  let application_ids = client
+    .applications_stream()
+    .map_ok(|(application_id, _)| application_id)
+    .try_collect::<Vec<_>>()
+    .await
+    .unwrap();
+  assert_eq!(application_ids, vec!["app-a", "app-b", "app-c"]);
+}
+
+#[tokio::test]
+async fn test_topics_with_dependants_stream() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  let topic = Topic::builder().partitions(1).replication_factor(1).build().unwrap();
+  client.put_topic_configuration("topic-1", &topic).await.unwrap();
+  client.put_topic_configuration("topic-2", &topic).await.unwrap();
+  let application = Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .user("1234:1234")
+    .env_entry("TOPIC", "scratch.topic-1.my-tenant")
+    .build()
+    .unwrap();
+  client.put_application_configuration("my-application", &application).await.unwrap();
+  let mut topics = client.topics_with_dependants_stream(2).try_collect::<Vec<_>>().await.unwrap();
+  topics.sort_by(|(id_a, _, _), (id_b, _, _)| id_a.cmp(id_b));
+  assert_eq!(topics[0].1, topic);
+  assert_eq!(topics[0].2.len(), 1);
+  assert!(topics[1].2.is_empty());
+  assert_eq!(
+    topics.into_iter().map(|(topic_id, _, dependants)| (topic_id, dependants)).collect::<Vec<_>>(),
+    client.topics_with_dependants().await.unwrap()
+  );
+}
+
+#[tokio::test]
+async fn test_topics_with_dependants_stream_bounded_concurrency() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  let topic = Topic::builder().partitions(1).replication_factor(1).build().unwrap();
+  for topic_id in ["topic-1", "topic-2", "topic-3", "topic-4"] {
+    client.put_topic_configuration(topic_id, &topic).await.unwrap();
+  }
+  server.inject_latency("GET", "/allocation/{tenant}/topic/{id}/configuration", Duration::from_millis(200));
+  let start = Instant::now();
+  let mut topics = Box::pin(client.topics_with_dependants_stream(2));
+  assert!(topics.next().await.unwrap().is_ok());
+  assert!(start.elapsed() < Duration::from_millis(400));
+  assert_eq!(topics.count().await, 3);
+  assert!(start.elapsed() >= Duration::from_millis(400));
+}
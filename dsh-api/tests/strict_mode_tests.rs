@@ -0,0 +1,85 @@
+use crate::common::mock_client_factory;
+use dsh_api::dsh_api_client::DshApiClient;
+use dsh_api::validation::ValidationError;
+use mockito::{Server, ServerGuard};
+
+mod common;
+
+const VOLUME_PATH: &str = "/allocation/my-tenant/volume/my-volume/configuration";
+
+async fn mock_client(server: &ServerGuard, strict_mode: bool) -> DshApiClient {
+  mock_client_factory(server.url().as_str()).with_strict_mode(strict_mode).client().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_strict_mode_valid_response() {
+  let mut server = Server::new_async().await;
+  let mock = server
+    .mock("GET", VOLUME_PATH)
+    .match_header("authorization", "Bearer access-token")
+    .with_status(200)
+    .with_body(r#"{ "sizeGiB": 10 }"#)
+    .create_async()
+    .await;
+  let client = mock_client(&server, true).await;
+  assert!(client.strict_mode());
+  let volume = client.get_volume_configuration("my-volume").await.unwrap();
+  assert_eq!(volume.size_gi_b, 10);
+  assert!(client.response_deviations().is_empty());
+  mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_strict_mode_unknown_field() {
+  let mut server = Server::new_async().await;
+  server
+    .mock("GET", VOLUME_PATH)
+    .with_status(200)
+    .with_body(r#"{ "sizeGiB": 10, "owner": "someone" }"#)
+    .create_async()
+    .await;
+  let client = mock_client(&server, true).await;
+  assert!(client.get_volume_configuration("my-volume").await.is_ok());
+  let deviations = client.response_deviations();
+  assert_eq!(
+    deviations.get("GET /allocation/{tenant}/volume/{id}/configuration"),
+    Some(&vec![ValidationError::new("$", "unknown field 'owner'")])
+  );
+  client.clear_response_deviations();
+  assert!(client.response_deviations().is_empty());
+}
+
+#[tokio::test]
+async fn test_strict_mode_missing_required_field() {
+  let mut server = Server::new_async().await;
+  server.mock("GET", VOLUME_PATH).with_status(200).with_body(r#"{}"#).create_async().await;
+  let client = mock_client(&server, true).await;
+  assert!(client.get_volume_configuration("my-volume").await.is_err());
+  assert_eq!(
+    client.response_deviations().get("GET /allocation/{tenant}/volume/{id}/configuration"),
+    Some(&vec![ValidationError::new("$", "missing required field 'sizeGiB'")])
+  );
+}
+
+#[tokio::test]
+async fn test_strict_mode_not_found() {
+  let mut server = Server::new_async().await;
+  server.mock("GET", VOLUME_PATH).with_status(404).create_async().await;
+  let client = mock_client(&server, true).await;
+  assert!(client.get_volume_configuration("my-volume").await.is_err());
+  assert!(client.response_deviations().is_empty());
+}
+
+#[tokio::test]
+async fn test_non_strict_mode_unknown_field() {
+  let mut server = Server::new_async().await;
+  server
+    .mock("GET", VOLUME_PATH)
+    .with_status(200)
+    .with_body(r#"{ "sizeGiB": 10, "owner": "someone" }"#)
+    .create_async()
+    .await;
+  let client = mock_client(&server, false).await;
+  assert!(client.get_volume_configuration("my-volume").await.is_ok());
+  assert!(client.response_deviations().is_empty());
+}
@@ -0,0 +1,127 @@
+use crate::common::mock_client;
+use chrono::{DateTime, Utc};
+use dsh_api::mock_server::MockServer;
+use dsh_api::task::{application_health_from_tasks, task_timeline_from_tasks, TaskEventKind};
+use dsh_api::types::{Application, Task};
+use serde_json::{json, Value};
+
+mod common;
+
+fn application(instances: u64) -> Application {
+  Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .user("1234:1234")
+    .instances(instances)
+    .build()
+    .unwrap()
+}
+
+fn task_value(state: &str, healthy: bool, minute: u32, stopped: bool) -> Value {
+  let mut task = json!({
+    "host": "10.0.0.1",
+    "healthy": healthy,
+    "stagedAt": format!("2025-01-01T12:{:02}:00Z", minute),
+    "startedAt": format!("2025-01-01T12:{:02}:30Z", minute),
+    "state": state
+  });
+  if stopped {
+    task["stoppedAt"] = json!(format!("2025-01-01T12:{:02}:00Z", minute + 1));
+  }
+  task
+}
+
+fn task(state: &str, healthy: bool, minute: u32, stopped: bool) -> Task {
+  serde_json::from_value(task_value(state, healthy, minute, stopped)).unwrap()
+}
+
+fn since(minute: u32) -> DateTime<Utc> {
+  format!("2025-01-01T12:{:02}:00Z", minute).parse().unwrap()
+}
+
+#[test]
+fn test_application_health_from_tasks() {
+  let running = task("RUNNING", true, 10, false);
+  let unhealthy = task("RUNNING", false, 10, false);
+  let failed = task("FAILED", false, 1, true);
+  let health = application_health_from_tasks("my-application", &application(2), &[&running, &unhealthy], 3, since(0));
+  assert_eq!((health.running_tasks, health.healthy_tasks, health.stopped_tasks), (2, 1, 0));
+  assert!(!health.crash_looping);
+  assert!(!health.is_healthy());
+  let health = application_health_from_tasks("my-application", &application(1), &[&running, &failed, &failed, &failed], 3, since(0));
+  assert!(health.crash_looping);
+  assert!(!health.is_healthy());
+  assert_eq!(health.to_string(), "my-application: 1/1 healthy, 1 running, 3 stopped, crash looping");
+  assert!(application_health_from_tasks("my-application", &application(1), &[&running], 3, since(0)).is_healthy());
+}
+
+#[test]
+fn test_application_health_from_old_stopped_tasks() {
+  let running = task("RUNNING", true, 30, false);
+  let failed = task("FAILED", false, 1, true);
+  let health = application_health_from_tasks("my-application", &application(1), &[&running, &failed, &failed, &failed], 3, since(15));
+  assert_eq!(health.stopped_tasks, 3);
+  assert!(!health.crash_looping);
+  assert!(health.is_healthy());
+}
+
+#[test]
+fn test_application_health_from_redeployed_tasks() {
+  let running = task("RUNNING", true, 10, false);
+  let killed = task("KILLED", true, 5, true);
+  let finished = task("FINISHED", true, 6, true);
+  let gone_by_operator = task("GONE_BY_OPERATOR", true, 7, true);
+  let health = application_health_from_tasks("my-application", &application(1), &[&running, &killed, &finished, &gone_by_operator], 3, since(0));
+  assert_eq!(health.stopped_tasks, 3);
+  assert!(!health.crash_looping);
+  assert!(health.is_healthy());
+}
+
+#[test]
+fn test_task_timeline_from_tasks() {
+  let tasks = vec![
+    ("my-application".to_string(), "task-2".to_string(), task("RUNNING", true, 5, false)),
+    ("my-application".to_string(), "task-1".to_string(), task("KILLED", true, 1, true)),
+  ];
+  let timeline = task_timeline_from_tasks(&tasks);
+  assert_eq!(
+    timeline.iter().map(|event| (event.task_id.as_str(), event.kind)).collect::<Vec<_>>(),
+    vec![
+      ("task-1", TaskEventKind::Staged),
+      ("task-1", TaskEventKind::Started),
+      ("task-1", TaskEventKind::Stopped),
+      ("task-2", TaskEventKind::Staged),
+      ("task-2", TaskEventKind::Started)
+    ]
+  );
+}
+
+#[tokio::test]
+async fn test_application_health_summaries() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  client.put_application_configuration("my-application", &application(1)).await.unwrap();
+  client.put_application_configuration("other-application", &application(2)).await.unwrap();
+  // the mock server only lists the application ids that are stored as a resource themselves
+  server.insert("/allocation/my-tenant/task/my-application", json!({}));
+  server.insert("/allocation/my-tenant/task/other-application", json!({}));
+  server.insert("/allocation/my-tenant/task/my-application/task-1", task_value("RUNNING", true, 0, false));
+  server.insert("/allocation/my-tenant/task/other-application/task-2", task_value("RUNNING", true, 0, false));
+  server.insert("/allocation/my-tenant/task/other-application/task-3", task_value("FAILED", false, 2, true));
+  let tasks = client.tasks(2).await.unwrap();
+  assert_eq!(
+    tasks
+      .iter()
+      .map(|(application_id, task_id, _)| (application_id.as_str(), task_id.as_str()))
+      .collect::<Vec<_>>(),
+    vec![("my-application", "task-1"), ("other-application", "task-2"), ("other-application", "task-3")]
+  );
+  assert_eq!(client.task_timeline(2).await.unwrap().len(), 7);
+  let summaries = client.application_health_summaries(2).await.unwrap();
+  assert_eq!(summaries.len(), 2);
+  assert!(summaries[0].is_healthy());
+  assert_eq!(summaries[1].application_id, "other-application");
+  assert_eq!((summaries[1].healthy_tasks, summaries[1].stopped_tasks), (1, 1));
+  assert!(!summaries[1].is_healthy());
+}
@@ -1,3 +1,4 @@
+#![cfg(not(dsh_api_1_8))]
 use dsh_api::tenant::TenantLimits;
 use dsh_api::types::{
   LimitValue, LimitValueCertificateCount, LimitValueConsumerRate, LimitValueCpu, LimitValueKafkaAclGroupCount, LimitValueMem, LimitValuePartitionCount, LimitValueProducerRate,
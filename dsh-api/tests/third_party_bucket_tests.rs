@@ -0,0 +1,83 @@
+use crate::common::mock_client;
+use dsh_api::mock_server::MockServer;
+use dsh_api::third_party_bucket::third_party_bucket_injections_from_applications;
+use dsh_api::types::{Application, Bucket, ThirdPartyBucketConcessionRegistration};
+use serde_json::json;
+use std::collections::HashMap;
+
+mod common;
+
+fn application(bucket_id: &str) -> Application {
+  Application::builder()
+    .image("my-image:0.0.1")
+    .cpus(0.5)
+    .mem(512)
+    .user("1234:1234")
+    .env(HashMap::from([("BUCKET".to_string(), format!("{{ bucket_name('{}') }}", bucket_id))]))
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_third_party_bucket_injections_from_applications() {
+  let applications = HashMap::from([("my-application".to_string(), application("shared-bucket")), ("other-application".to_string(), application("my-bucket"))]);
+  let injections = third_party_bucket_injections_from_applications(&["unused-bucket".to_string(), "shared-bucket".to_string()], &applications);
+  assert_eq!(injections.len(), 2);
+  assert_eq!(injections[0].0, "shared-bucket");
+  assert_eq!(injections[0].1.len(), 1);
+  assert_eq!(injections[0].1[0].application_id, "my-application");
+  assert_eq!(injections[1].0, "unused-bucket");
+  assert!(injections[1].1.is_empty());
+}
+
+#[tokio::test]
+async fn test_incoming_bucket_concessions() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  let registration = ThirdPartyBucketConcessionRegistration {
+    credentialidentifierplaintext: "identifier".to_string(),
+    credentialsecretplaintext: "secret".to_string(),
+    name: "shared-bucket".to_string(),
+    shareidentifier: "share".to_string(),
+  };
+  client.register_third_party_bucket(&registration).await.unwrap();
+  assert!(server.resource("/allocation/my-tenant/thirdpartybucketconcession/shared-bucket").is_some());
+  // the mock server returns the registration as actual value, which lacks the credential references
+  server.insert(
+    "/allocation/my-tenant/thirdpartybucketconcession/shared-bucket",
+    json!({ "name": "shared-bucket", "shareidentifier": "share", "readable": true, "writable": false, "credentialidentifierref": "id", "credentialsecretref": "secret" }),
+  );
+  let concessions = client.incoming_bucket_concessions().await.unwrap();
+  assert_eq!(concessions.len(), 1);
+  assert_eq!(concessions[0].0, "shared-bucket");
+  assert!(concessions[0].1.actual.as_ref().unwrap().readable);
+  client.unregister_third_party_bucket("shared-bucket").await.unwrap();
+  assert!(client.incoming_bucket_concessions().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_outgoing_bucket_concessions() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  client.put_bucket_configuration("my-bucket", &Bucket::new(true, true)).await.unwrap();
+  client.put_bucket_configuration("other-bucket", &Bucket::new(true, true)).await.unwrap();
+  server.insert(
+    "/allocation/my-tenant/bucket/my-bucket/bucketaccess/reader",
+    json!({ "bucket": "my-bucket", "name": "reader", "readable": true, "writable": false, "credentialidentifierref": "id", "credentialsecretref": "secret" }),
+  );
+  let concessions = client.outgoing_bucket_concessions().await.unwrap();
+  assert_eq!(concessions.len(), 1);
+  assert_eq!((concessions[0].0.as_str(), concessions[0].1.as_str()), ("my-bucket", "reader"));
+}
+
+#[tokio::test]
+async fn test_third_party_buckets_with_dependant_applications() {
+  let server = MockServer::start().unwrap();
+  let client = mock_client(&server).await;
+  server.insert("/allocation/my-tenant/bucket/fromthirdparty/shared-bucket", json!({}));
+  client.put_application_configuration("my-application", &application("shared-bucket")).await.unwrap();
+  let buckets = client.third_party_buckets_with_dependant_applications().await.unwrap();
+  assert_eq!(buckets.len(), 1);
+  assert_eq!(buckets[0].0, "shared-bucket");
+  assert_eq!(buckets[0].1[0].application_id, "my-application");
+}
@@ -0,0 +1,118 @@
+use dsh_api::validation::{schema_names, validate_json, validate_request_body, validate_response_body, validate_schema, ValidationError};
+use serde_json::json;
+
+const VALID_APPLICATION_JSON: &str = r#"{
+    "cpus": 0.1,
+    "env": { "LOG_LEVEL": "info" },
+    "exposedPorts": { "8080": { "auth": "system-fwd-auth@view,manage" } },
+    "image": "registry.cp.kpn-dsh.com/my-tenant/my-image:0.0.1",
+    "instances": 1,
+    "mem": 256,
+    "user": "1234:1234"
+  }"#;
+
+#[test]
+fn test_schema_names() {
+  let schema_names = schema_names();
+  assert!(schema_names.contains(&"Application"));
+  assert!(schema_names.contains(&"Secret"));
+  assert!(schema_names.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[test]
+fn test_validate_valid_application() {
+  assert_eq!(validate_json("Application", VALID_APPLICATION_JSON).unwrap(), vec![]);
+}
+
+#[test]
+fn test_validate_application_errors() {
+  let application = json!({
+    "cpus": "0.1",
+    "env": { "LOG_LEVEL": 1 },
+    "image": "registry.cp.kpn-dsh.com/my-tenant/my-image:0.0.1",
+    "mem": -256,
+    "readableStreams": "stream"
+  });
+  let errors = validate_schema("Application", &application).unwrap();
+  assert!(errors.contains(&ValidationError::new("$", "missing required field 'user'")));
+  assert!(errors.contains(&ValidationError::new("$.cpus", "expected number, found string")));
+  assert!(errors.contains(&ValidationError::new("$.env.LOG_LEVEL", "expected string, found integer")));
+  assert!(errors.contains(&ValidationError::new("$.readableStreams", "expected array, found string")));
+  assert!(errors.iter().any(|error| error.path == "$.mem"));
+}
+
+#[test]
+fn test_validate_enumeration() {
+  let port_mapping = json!({ "tls": "sometimes" });
+  let errors = validate_schema("PortMapping", &port_mapping).unwrap();
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].path, "$.tls");
+  assert!(errors[0].message.starts_with("value \"sometimes\" is not one of the allowed values"));
+}
+
+#[test]
+fn test_validate_discriminated_limit_value() {
+  assert_eq!(validate_schema("LimitValue", &json!({ "name": "cpu", "value": 1.5 })).unwrap(), vec![]);
+  let errors = validate_schema("LimitValue", &json!({ "name": "mem", "value": "large" })).unwrap();
+  assert_eq!(errors, vec![ValidationError::new("$.value", "expected integer, found string")]);
+}
+
+#[test]
+fn test_validate_request_body() {
+  let path = "/allocation/{tenant}/secret";
+  assert_eq!(
+    validate_request_body("post", path, &json!({ "name": "my-secret", "value": "abcdef" })).unwrap(),
+    vec![]
+  );
+  assert_eq!(
+    validate_request_body("post", path, &json!({ "name": "my-secret" })).unwrap(),
+    vec![ValidationError::new("$", "missing required field 'value'")]
+  );
+  assert!(validate_request_body("get", path, &json!({})).is_err());
+  assert!(validate_request_body("post", "/does/not/exist", &json!({})).is_err());
+}
+
+#[test]
+#[cfg(not(dsh_api_1_8))]
+fn test_validate_path_parameters() {
+  use dsh_api::validation::validate_path_parameters;
+
+  let path = "/allocation/{tenant}/aclgroup/{id}/configuration";
+  assert_eq!(validate_path_parameters("get", path, &["my-group"]).unwrap(), vec![]);
+  let errors = validate_path_parameters("get", path, &["G_1"]).unwrap();
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].path, "id");
+}
+
+#[test]
+fn test_validate_invalid_json() {
+  assert!(validate_json("Application", "{ not json").is_err());
+  assert!(validate_json("NonExistingSchema", "{}").is_err());
+}
+
+#[test]
+fn test_validate_response_body_unknown_fields() {
+  let path = "/allocation/{tenant}/certificate/{id}";
+  let certificate_status = json!({
+    "actual": {
+      "certChainSecret": "chain",
+      "keySecret": "key",
+      "distinguishedName": "CN=my-domain",
+      "dnsNames": ["my-domain"],
+      "notBefore": "2025-01-01T00:00:00Z",
+      "notAfter": "2026-01-01T00:00:00Z",
+      "serialNumber": "0123456789",
+      "issuer": "my-ca"
+    },
+    "status": { "notifications": [], "provisioned": true, "phase": "ready" }
+  });
+  assert_eq!(
+    validate_response_body("get", path, 200, &certificate_status).unwrap(),
+    vec![ValidationError::new("$.actual", "unknown field 'issuer'"), ValidationError::new("$.status", "unknown field 'phase'")]
+  );
+  assert!(
+    validate_request_body("put", "/allocation/{tenant}/volume/{id}/configuration", &json!({ "sizeGiB": 1, "size": 1 }))
+      .unwrap()
+      .is_empty()
+  );
+}